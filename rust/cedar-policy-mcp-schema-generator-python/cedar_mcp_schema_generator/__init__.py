@@ -23,17 +23,22 @@ import json
 from typing import Any
 
 from cedar_mcp_schema_generator._native import (
+    AuthorizationComponents,
+    DeserializationError,
+    RequestGenerator,
+    RequestGeneratorError,
+    SchemaGenerator,
+    SchemaGeneratorConfig,
+    SchemaGeneratorError,
+    ValidationError,
+    authorize as _authorize,
     generate_request as _generate_request,
     generate_schema as _generate_schema,
 )
 
 
-class SchemaGeneratorError(Exception):
-    """Raised when schema generation fails."""
-
-
-class RequestGeneratorError(Exception):
-    """Raised when request generation fails."""
+class AuthorizationError(Exception):
+    """Raised when authorization fails to produce a decision."""
 
 
 def generate_schema(
@@ -117,6 +122,7 @@ def generate_request(
             - action (str | None): Cedar action EntityUID string.
             - resource (str | None): Cedar resource EntityUID string.
             - entitiesJson (str | None): Entities as a JSON array string.
+            - contextJson (str | None): Context as a JSON object string.
             - error (str | None): Error message if generation failed.
     """
     tools_json = json.dumps(tools) if isinstance(tools, list) else tools
@@ -163,11 +169,112 @@ def generate_request_or_raise(
     return result
 
 
+def authorize(
+    schema_stub: str,
+    tools: str | list[dict[str, Any]],
+    input: str | dict[str, Any],
+    *,
+    principal_type: str,
+    principal_id: str,
+    resource_type: str,
+    resource_id: str,
+    policies: str,
+    entities: str | list[dict[str, Any]] | None = None,
+    config: dict[str, Any] | None = None,
+) -> dict[str, Any]:
+    """Authorize an MCP tool call against a set of Cedar policies.
+
+    Args:
+        schema_stub: A Cedar schema stub string.
+        tools: MCP tool descriptions as a JSON string or list of dicts.
+        input: MCP tool call input as a JSON string or dict.
+        principal_type: The Cedar entity type for the principal (e.g., "User").
+        principal_id: The principal identifier (e.g., "alice").
+        resource_type: The Cedar entity type for the resource (e.g., "McpServer").
+        resource_id: The resource identifier (e.g., "my-server").
+        policies: Cedar policies in Cedar policy syntax.
+        entities: Optional additional entities (e.g., the principal's) in
+            Cedar's entity JSON format, as a JSON string or list of dicts.
+        config: Optional configuration dict (same keys as generate_schema).
+
+    Returns:
+        A dict with keys:
+            - isOk (bool): Whether authorization succeeded.
+            - decision (str | None): "allow" or "deny".
+            - determiningPolicies (list[str]): IDs of the policies that
+              determined the decision.
+            - errors (list[dict]): Policy evaluation errors, each with
+              policyId and message keys.
+            - error (str | None): Error message if authorization failed.
+    """
+    tools_json = json.dumps(tools) if isinstance(tools, list) else tools
+    input_json = json.dumps(input) if isinstance(input, dict) else input
+    if entities is None:
+        entities_json = "[]"
+    else:
+        entities_json = json.dumps(entities) if isinstance(entities, list) else entities
+    config_json = json.dumps(config) if config else None
+    return json.loads(
+        _authorize(
+            schema_stub,
+            tools_json,
+            input_json,
+            principal_type,
+            principal_id,
+            resource_type,
+            resource_id,
+            policies,
+            entities_json,
+            config_json,
+        )
+    )
+
+
+def authorize_or_raise(
+    schema_stub: str,
+    tools: str | list[dict[str, Any]],
+    input: str | dict[str, Any],
+    *,
+    principal_type: str,
+    principal_id: str,
+    resource_type: str,
+    resource_id: str,
+    policies: str,
+    entities: str | list[dict[str, Any]] | None = None,
+    config: dict[str, Any] | None = None,
+) -> dict[str, Any]:
+    """Like authorize but raises AuthorizationError on failure."""
+    result = authorize(
+        schema_stub,
+        tools,
+        input,
+        principal_type=principal_type,
+        principal_id=principal_id,
+        resource_type=resource_type,
+        resource_id=resource_id,
+        policies=policies,
+        entities=entities,
+        config=config,
+    )
+    if not result["isOk"]:
+        raise AuthorizationError(result.get("error", "Unknown error"))
+    return result
+
+
 __all__ = [
+    "SchemaGenerator",
+    "SchemaGeneratorConfig",
+    "RequestGenerator",
+    "AuthorizationComponents",
     "generate_schema",
     "generate_schema_or_raise",
     "generate_request",
     "generate_request_or_raise",
+    "authorize",
+    "authorize_or_raise",
     "SchemaGeneratorError",
     "RequestGeneratorError",
+    "DeserializationError",
+    "ValidationError",
+    "AuthorizationError",
 ]
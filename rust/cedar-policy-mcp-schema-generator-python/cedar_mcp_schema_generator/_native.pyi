@@ -0,0 +1,119 @@
+# Copyright Cedar Contributors
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#      https://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+
+"""Type stubs for the native Cedar MCP Schema Generator module."""
+
+from __future__ import annotations
+
+class SchemaGeneratorError(Exception):
+    """Raised when schema generation fails."""
+
+class RequestGeneratorError(Exception):
+    """Raised when request generation fails."""
+
+class DeserializationError(ValueError):
+    """Raised when an MCP tool description, input or output cannot be parsed."""
+
+class ValidationError(RequestGeneratorError):
+    """Raised when an MCP tool input or output does not validate against its tool description."""
+
+class SchemaGeneratorConfig:
+    include_outputs: bool
+    objects_as_records: bool
+    erase_annotations: bool
+    flatten_namespaces: bool
+    numbers_as_decimal: bool
+    deduplicate_entity_types: bool
+    annotation_action_groups: bool
+    doc_annotations: bool
+    redaction_actions: bool
+    deterministic_entity_ids: bool
+    entity_id_key: str | None
+
+    def __init__(
+        self,
+        *,
+        include_outputs: bool = False,
+        objects_as_records: bool = False,
+        erase_annotations: bool = True,
+        flatten_namespaces: bool = False,
+        numbers_as_decimal: bool = False,
+        deduplicate_entity_types: bool = False,
+        annotation_action_groups: bool = False,
+        doc_annotations: bool = False,
+        redaction_actions: bool = False,
+        deterministic_entity_ids: bool = False,
+        entity_id_key: str | None = None,
+    ) -> None: ...
+
+class SchemaGenerator:
+    def __init__(
+        self, schema_stub: str, config: SchemaGeneratorConfig | None = None
+    ) -> None: ...
+    def add_tools(self, tools_json: str) -> None: ...
+    def add_tool(self, tool_json: str) -> None: ...
+    @property
+    def schema(self) -> str: ...
+    @property
+    def schema_json(self) -> str: ...
+    def request_generator(self) -> RequestGenerator: ...
+
+class RequestGenerator:
+    def generate_request(
+        self,
+        input_json: str,
+        principal: str,
+        resource: str,
+        context_json: str = "{}",
+        entities_json: str = "[]",
+        output_json: str | None = None,
+    ) -> AuthorizationComponents: ...
+
+class AuthorizationComponents:
+    @property
+    def principal(self) -> str: ...
+    @property
+    def action(self) -> str: ...
+    @property
+    def resource(self) -> str: ...
+    @property
+    def entities_json(self) -> str: ...
+    @property
+    def context_json(self) -> str: ...
+
+def generate_schema(
+    schema_stub: str, tools_json: str, config_json: str | None = None
+) -> str: ...
+def generate_request(
+    schema_stub: str,
+    tools_json: str,
+    input_json: str,
+    principal_type: str,
+    principal_id: str,
+    resource_type: str,
+    resource_id: str,
+    config_json: str | None = None,
+) -> str: ...
+def authorize(
+    schema_stub: str,
+    tools_json: str,
+    input_json: str,
+    principal_type: str,
+    principal_id: str,
+    resource_type: str,
+    resource_id: str,
+    policies: str,
+    entities_json: str,
+    config_json: str | None = None,
+) -> str: ...
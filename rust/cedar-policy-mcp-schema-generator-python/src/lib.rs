@@ -20,16 +20,78 @@
 //! to generate Cedar schemas from MCP tool descriptions with the exact same
 //! behavior as the Rust implementation.
 //!
+//! Besides the JSON-string functions, the module provides stateful
+//! `SchemaGenerator` and `RequestGenerator` classes raising native Python
+//! exceptions, mirroring the Rust API.
+//!
 //! This crate is a thin wrapper: all schema generation logic is delegated to
 //! [`cedar_policy_mcp_schema_generator`].
 
-use cedar_policy_mcp_schema_generator::{SchemaGenerator, SchemaGeneratorConfig};
-use mcp_tools_sdk::data::Input;
-use mcp_tools_sdk::description::ServerDescription;
+use cedar_policy_mcp_schema_generator::{
+    AuthorizationComponents, AuthorizationResponse, Decision, RequestGenerator, SchemaGenerator,
+    SchemaGeneratorConfig,
+};
+use mcp_tools_sdk::data::{Input, Output};
+use mcp_tools_sdk::description::{ServerDescription, ToolDescription};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
 use pyo3::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+create_exception!(
+    cedar_mcp_schema_generator,
+    SchemaGeneratorError,
+    PyException,
+    "Raised when schema generation fails."
+);
+create_exception!(
+    cedar_mcp_schema_generator,
+    RequestGeneratorError,
+    PyException,
+    "Raised when request generation fails."
+);
+create_exception!(
+    cedar_mcp_schema_generator,
+    DeserializationError,
+    PyValueError,
+    "Raised when an MCP tool description, input or output cannot be parsed."
+);
+create_exception!(
+    cedar_mcp_schema_generator,
+    ValidationError,
+    RequestGeneratorError,
+    "Raised when an MCP tool input or output does not validate against its tool description."
+);
+
+fn schema_generator_error(e: &cedar_policy_mcp_schema_generator::SchemaGeneratorError) -> PyErr {
+    SchemaGeneratorError::new_err(e.to_string())
+}
+
+fn request_generator_error(e: cedar_policy_mcp_schema_generator::RequestGeneratorError) -> PyErr {
+    match e {
+        cedar_policy_mcp_schema_generator::RequestGeneratorError::MCPValidationError(e) => {
+            ValidationError::new_err(e.to_string())
+        }
+        e => RequestGeneratorError::new_err(e.to_string()),
+    }
+}
+
+fn deserialization_error(e: &mcp_tools_sdk::err::DeserializationError) -> PyErr {
+    DeserializationError::new_err(e.to_string())
+}
+
+/// Configuration options for schema generation, matching the Rust
+/// [`SchemaGeneratorConfig`] options. Exposed to Python as
+/// `SchemaGeneratorConfig` and deserialized from the `config_json` argument
+/// of the JSON-string functions.
+#[pyclass(
+    name = "SchemaGeneratorConfig",
+    module = "cedar_mcp_schema_generator",
+    get_all,
+    set_all,
+    from_py_object
+)]
+#[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 struct PyConfig {
     #[serde(default)]
@@ -44,6 +106,16 @@ struct PyConfig {
     numbers_as_decimal: bool,
     #[serde(default)]
     deduplicate_entity_types: bool,
+    #[serde(default)]
+    annotation_action_groups: bool,
+    #[serde(default)]
+    doc_annotations: bool,
+    #[serde(default)]
+    redaction_actions: bool,
+    #[serde(default)]
+    deterministic_entity_ids: bool,
+    #[serde(default)]
+    entity_id_key: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -59,10 +131,72 @@ impl Default for PyConfig {
             flatten_namespaces: false,
             numbers_as_decimal: false,
             deduplicate_entity_types: false,
+            annotation_action_groups: false,
+            doc_annotations: false,
+            redaction_actions: false,
+            deterministic_entity_ids: false,
+            entity_id_key: None,
         }
     }
 }
 
+#[pymethods]
+impl PyConfig {
+    #[new]
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "Mirrors the keyword arguments of the Python constructor."
+    )]
+    #[pyo3(signature = (*, include_outputs=false, objects_as_records=false, erase_annotations=true, flatten_namespaces=false, numbers_as_decimal=false, deduplicate_entity_types=false, annotation_action_groups=false, doc_annotations=false, redaction_actions=false, deterministic_entity_ids=false, entity_id_key=None))]
+    fn new(
+        include_outputs: bool,
+        objects_as_records: bool,
+        erase_annotations: bool,
+        flatten_namespaces: bool,
+        numbers_as_decimal: bool,
+        deduplicate_entity_types: bool,
+        annotation_action_groups: bool,
+        doc_annotations: bool,
+        redaction_actions: bool,
+        deterministic_entity_ids: bool,
+        entity_id_key: Option<String>,
+    ) -> Self {
+        Self {
+            include_outputs,
+            objects_as_records,
+            erase_annotations,
+            flatten_namespaces,
+            numbers_as_decimal,
+            deduplicate_entity_types,
+            annotation_action_groups,
+            doc_annotations,
+            redaction_actions,
+            deterministic_entity_ids,
+            entity_id_key,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        let py_bool = |b: bool| if b { "True" } else { "False" };
+        format!(
+            "SchemaGeneratorConfig(include_outputs={}, objects_as_records={}, erase_annotations={}, flatten_namespaces={}, numbers_as_decimal={}, deduplicate_entity_types={}, annotation_action_groups={}, doc_annotations={}, redaction_actions={}, deterministic_entity_ids={}, entity_id_key={})",
+            py_bool(self.include_outputs),
+            py_bool(self.objects_as_records),
+            py_bool(self.erase_annotations),
+            py_bool(self.flatten_namespaces),
+            py_bool(self.numbers_as_decimal),
+            py_bool(self.deduplicate_entity_types),
+            py_bool(self.annotation_action_groups),
+            py_bool(self.doc_annotations),
+            py_bool(self.redaction_actions),
+            py_bool(self.deterministic_entity_ids),
+            self.entity_id_key
+                .as_ref()
+                .map_or_else(|| "None".to_string(), |key| format!("{key:?}")),
+        )
+    }
+}
+
 impl From<PyConfig> for SchemaGeneratorConfig {
     fn from(c: PyConfig) -> Self {
         SchemaGeneratorConfig::default()
@@ -72,6 +206,11 @@ impl From<PyConfig> for SchemaGeneratorConfig {
             .flatten_namespaces(c.flatten_namespaces)
             .encode_numbers_as_decimal(c.numbers_as_decimal)
             .deduplicate_entity_types(c.deduplicate_entity_types)
+            .annotation_action_groups(c.annotation_action_groups)
+            .doc_annotations(c.doc_annotations)
+            .redaction_actions(c.redaction_actions)
+            .deterministic_entity_ids(c.deterministic_entity_ids)
+            .entity_id_key(c.entity_id_key.map(Into::into))
     }
 }
 
@@ -91,10 +230,51 @@ struct RequestResult {
     action: Option<String>,
     resource: Option<String>,
     entities_json: Option<String>,
+    context_json: Option<String>,
+    error: Option<String>,
+    is_ok: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PolicyEvaluationErrorResult {
+    policy_id: String,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthorizationResult {
+    decision: Option<String>,
+    determining_policies: Vec<String>,
+    errors: Vec<PolicyEvaluationErrorResult>,
     error: Option<String>,
     is_ok: bool,
 }
 
+impl From<AuthorizationResponse> for AuthorizationResult {
+    fn from(response: AuthorizationResponse) -> Self {
+        let decision = match response.decision {
+            Decision::Allow => "allow",
+            Decision::Deny => "deny",
+        };
+        Self {
+            decision: Some(decision.to_string()),
+            determining_policies: response.determining_policies,
+            errors: response
+                .errors
+                .into_iter()
+                .map(|err| PolicyEvaluationErrorResult {
+                    policy_id: err.policy_id,
+                    message: err.message,
+                })
+                .collect(),
+            error: None,
+            is_ok: true,
+        }
+    }
+}
+
 /// Generate a Cedar schema from a schema stub and MCP tool descriptions.
 ///
 /// # Arguments
@@ -138,7 +318,7 @@ fn generate_schema(schema_stub: &str, tools_json: &str, config_json: Option<&str
 /// # Returns
 ///
 /// A JSON string with `principal`, `action`, `resource`, `entitiesJson`,
-/// `error`, and `isOk` fields.
+/// `contextJson`, `error`, and `isOk` fields.
 #[pyfunction]
 #[pyo3(signature = (schema_stub, tools_json, input_json, principal_type, principal_id, resource_type, resource_id, config_json=None))]
 #[expect(
@@ -167,7 +347,64 @@ fn generate_request(
     );
     serde_json::to_string(&result).unwrap_or_else(|e| {
         format!(
-            r#"{{"isOk":false,"error":"Serialization error: {}","principal":null,"action":null,"resource":null,"entitiesJson":null}}"#,
+            r#"{{"isOk":false,"error":"Serialization error: {}","principal":null,"action":null,"resource":null,"entitiesJson":null,"contextJson":null}}"#,
+            e
+        )
+    })
+}
+
+/// Authorize an MCP tool call against a set of Cedar policies.
+///
+/// # Arguments
+///
+/// * `schema_stub` - A Cedar schema stub as a `.cedarschema` string.
+/// * `tools_json` - MCP tool descriptions as a JSON string.
+/// * `input_json` - MCP tool call input as a JSON string.
+/// * `principal_type` - The Cedar entity type for the principal (e.g., `"User"`).
+/// * `principal_id` - The principal identifier (e.g., `"alice"`).
+/// * `resource_type` - The Cedar entity type for the resource (e.g., `"McpServer"`).
+/// * `resource_id` - The resource identifier (e.g., `"my-server"`).
+/// * `policies` - Cedar policies in Cedar policy syntax.
+/// * `entities_json` - Additional entities as a JSON array in Cedar's entity format.
+/// * `config_json` - Optional configuration as a JSON string.
+///
+/// # Returns
+///
+/// A JSON string with `decision`, `determiningPolicies`, `errors`, `error`,
+/// and `isOk` fields.
+#[pyfunction]
+#[pyo3(signature = (schema_stub, tools_json, input_json, principal_type, principal_id, resource_type, resource_id, policies, entities_json, config_json=None))]
+#[expect(
+    clippy::too_many_arguments,
+    reason = "wasm-bindgen/pyo3 requires flat parameter lists; cannot use struct across FFI boundary"
+)]
+fn authorize(
+    schema_stub: &str,
+    tools_json: &str,
+    input_json: &str,
+    principal_type: &str,
+    principal_id: &str,
+    resource_type: &str,
+    resource_id: &str,
+    policies: &str,
+    entities_json: &str,
+    config_json: Option<&str>,
+) -> String {
+    let result = authorize_inner(
+        schema_stub,
+        tools_json,
+        input_json,
+        principal_type,
+        principal_id,
+        resource_type,
+        resource_id,
+        policies,
+        entities_json,
+        config_json,
+    );
+    serde_json::to_string(&result).unwrap_or_else(|e| {
+        format!(
+            r#"{{"isOk":false,"error":"Serialization error: {}","decision":null,"determiningPolicies":[],"errors":[]}}"#,
             e
         )
     })
@@ -287,6 +524,7 @@ fn generate_request_inner(
             action: Some(components.action),
             resource: Some(components.resource),
             entities_json: Some(components.entities_json),
+            context_json: Some(components.context_json),
             error: None,
             is_ok: true,
         },
@@ -294,6 +532,224 @@ fn generate_request_inner(
     }
 }
 
+#[expect(
+    clippy::too_many_arguments,
+    reason = "Mirrors the flat parameter list from the WASM crate"
+)]
+fn authorize_inner(
+    schema_stub: &str,
+    tools_json: &str,
+    input_json: &str,
+    principal_type: &str,
+    principal_id: &str,
+    resource_type: &str,
+    resource_id: &str,
+    policies: &str,
+    entities_json: &str,
+    config_json: Option<&str>,
+) -> AuthorizationResult {
+    let config: SchemaGeneratorConfig = match config_json {
+        Some(json) if !json.is_empty() => {
+            let Ok(c) = serde_json::from_str::<PyConfig>(json) else {
+                return authorization_err(format!(
+                    "Invalid config: {}",
+                    serde_json::from_str::<serde_json::Value>(json)
+                        .err()
+                        .map_or_else(|| "unrecognized fields".to_string(), |e| e.to_string())
+                ));
+            };
+            c.into()
+        }
+        _ => SchemaGeneratorConfig::default(),
+    };
+
+    let Ok(mut generator) = SchemaGenerator::from_cedarschema_str_with_config(schema_stub, config)
+    else {
+        return authorization_err("Schema error: failed to parse schema stub".to_string());
+    };
+
+    let Ok(server_desc) = ServerDescription::from_json_str(tools_json) else {
+        return authorization_err("Invalid tool descriptions: failed to parse JSON".to_string());
+    };
+
+    if let Err(e) = generator.add_actions_from_server_description(&server_desc) {
+        return authorization_err(format!("Error adding tools: {e}"));
+    }
+
+    let Ok(req_gen) = generator.new_request_generator() else {
+        return authorization_err("Failed to create request generator".to_string());
+    };
+
+    let Ok(input) = Input::from_json_str(input_json) else {
+        return authorization_err("Invalid tool input: failed to parse JSON".to_string());
+    };
+
+    match req_gen.is_authorized_from_strings(
+        &input,
+        None,
+        principal_type,
+        principal_id,
+        resource_type,
+        resource_id,
+        policies,
+        entities_json,
+    ) {
+        Ok(response) => response.into(),
+        Err(e) => authorization_err(format!("Authorization error: {e}")),
+    }
+}
+
+/// A stateful schema generator, exposed to Python as `SchemaGenerator`.
+///
+/// Tools are added incrementally with `add_tools` / `add_tool`. The schema
+/// stub and tool descriptions are only parsed once.
+#[pyclass(name = "SchemaGenerator", module = "cedar_mcp_schema_generator")]
+#[derive(Debug)]
+struct PySchemaGenerator {
+    generator: SchemaGenerator,
+}
+
+#[pymethods]
+impl PySchemaGenerator {
+    #[new]
+    #[pyo3(signature = (schema_stub, config=None))]
+    fn new(schema_stub: &str, config: Option<PyConfig>) -> PyResult<Self> {
+        let config = config.unwrap_or_default().into();
+        let generator = SchemaGenerator::from_cedarschema_str_with_config(schema_stub, config)
+            .map_err(|e| schema_generator_error(&e))?;
+        Ok(Self { generator })
+    }
+
+    /// Add an action for each tool of an MCP `tools/list` response (or its
+    /// `tools` array) given as a JSON string.
+    fn add_tools(&mut self, tools_json: &str) -> PyResult<()> {
+        let server_desc =
+            ServerDescription::from_json_str(tools_json).map_err(|e| deserialization_error(&e))?;
+        self.generator
+            .add_actions_from_server_description(&server_desc)
+            .map_err(|e| schema_generator_error(&e))
+    }
+
+    /// Add an action for a single MCP tool description given as a JSON string.
+    fn add_tool(&mut self, tool_json: &str) -> PyResult<()> {
+        let tool_desc =
+            ToolDescription::from_json_str(tool_json).map_err(|e| deserialization_error(&e))?;
+        self.generator
+            .add_action_from_tool_description(&tool_desc)
+            .map_err(|e| schema_generator_error(&e))
+    }
+
+    /// The generated Cedar schema in `.cedarschema` format.
+    #[getter]
+    fn schema(&self) -> String {
+        self.generator.get_schema_as_str()
+    }
+
+    /// The generated Cedar schema as a JSON string.
+    #[getter]
+    fn schema_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(self.generator.get_schema())
+            .map_err(|e| SchemaGeneratorError::new_err(e.to_string()))
+    }
+
+    /// Create a `RequestGenerator` for the tools added so far.
+    fn request_generator(&self) -> PyResult<PyRequestGenerator> {
+        let generator = self
+            .generator
+            .new_request_generator()
+            .map_err(|e| schema_generator_error(&e))?;
+        Ok(PyRequestGenerator { generator })
+    }
+}
+
+/// A stateful request generator, exposed to Python as `RequestGenerator`.
+///
+/// Created with `SchemaGenerator.request_generator()`.
+#[pyclass(
+    name = "RequestGenerator",
+    module = "cedar_mcp_schema_generator",
+    frozen
+)]
+#[derive(Debug)]
+struct PyRequestGenerator {
+    generator: RequestGenerator,
+}
+
+#[pymethods]
+impl PyRequestGenerator {
+    /// Generate the Cedar authorization request components for an MCP tool call.
+    ///
+    /// `principal` and `resource` are Cedar EntityUIDs (e.g.,
+    /// `MyServer::User::"alice"`), `context_json` and `entities_json` are
+    /// additional context attributes and entities in Cedar's JSON format.
+    #[pyo3(signature = (input_json, principal, resource, context_json="{}", entities_json="[]", output_json=None))]
+    fn generate_request(
+        &self,
+        input_json: &str,
+        principal: &str,
+        resource: &str,
+        context_json: &str,
+        entities_json: &str,
+        output_json: Option<&str>,
+    ) -> PyResult<PyAuthorizationComponents> {
+        let input = Input::from_json_str(input_json).map_err(|e| deserialization_error(&e))?;
+        let output = output_json
+            .map(Output::from_json_str)
+            .transpose()
+            .map_err(|e| deserialization_error(&e))?;
+        self.generator
+            .generate_request_components_from_json(
+                &input,
+                output.as_ref(),
+                principal,
+                resource,
+                context_json,
+                entities_json,
+            )
+            .map(PyAuthorizationComponents::from)
+            .map_err(request_generator_error)
+    }
+}
+
+/// Cedar authorization request components, exposed to Python as
+/// `AuthorizationComponents`.
+#[pyclass(
+    name = "AuthorizationComponents",
+    module = "cedar_mcp_schema_generator",
+    frozen,
+    get_all
+)]
+#[derive(Debug)]
+struct PyAuthorizationComponents {
+    principal: String,
+    action: String,
+    resource: String,
+    entities_json: String,
+    context_json: String,
+}
+
+impl From<AuthorizationComponents> for PyAuthorizationComponents {
+    fn from(components: AuthorizationComponents) -> Self {
+        Self {
+            principal: components.principal,
+            action: components.action,
+            resource: components.resource,
+            entities_json: components.entities_json,
+            context_json: components.context_json,
+        }
+    }
+}
+
+#[pymethods]
+impl PyAuthorizationComponents {
+    fn __repr__(&self) -> String {
+        format!(
+            "AuthorizationComponents(principal={:?}, action={:?}, resource={:?})",
+            self.principal, self.action, self.resource
+        )
+    }
+}
+
 fn schema_err(error: String) -> SchemaResult {
     SchemaResult {
         schema: None,
@@ -309,6 +765,17 @@ fn request_err(error: String) -> RequestResult {
         action: None,
         resource: None,
         entities_json: None,
+        context_json: None,
+        error: Some(error),
+        is_ok: false,
+    }
+}
+
+fn authorization_err(error: String) -> AuthorizationResult {
+    AuthorizationResult {
+        decision: None,
+        determining_policies: Vec::new(),
+        errors: Vec::new(),
         error: Some(error),
         is_ok: false,
     }
@@ -319,5 +786,24 @@ fn request_err(error: String) -> RequestResult {
 fn cedar_mcp_schema_generator(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(generate_schema, m)?)?;
     m.add_function(wrap_pyfunction!(generate_request, m)?)?;
+    m.add_function(wrap_pyfunction!(authorize, m)?)?;
+    m.add_class::<PyConfig>()?;
+    m.add_class::<PySchemaGenerator>()?;
+    m.add_class::<PyRequestGenerator>()?;
+    m.add_class::<PyAuthorizationComponents>()?;
+    let py = m.py();
+    m.add(
+        "SchemaGeneratorError",
+        py.get_type::<SchemaGeneratorError>(),
+    )?;
+    m.add(
+        "RequestGeneratorError",
+        py.get_type::<RequestGeneratorError>(),
+    )?;
+    m.add(
+        "DeserializationError",
+        py.get_type::<DeserializationError>(),
+    )?;
+    m.add("ValidationError", py.get_type::<ValidationError>())?;
     Ok(())
 }
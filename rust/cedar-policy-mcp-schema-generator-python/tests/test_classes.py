@@ -0,0 +1,150 @@
+# Copyright Cedar Contributors
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#      https://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+
+"""Tests for the stateful classes of the Cedar MCP Schema Generator Python bindings."""
+
+import json
+
+import pytest
+
+from cedar_mcp_schema_generator import (
+    AuthorizationComponents,
+    DeserializationError,
+    RequestGeneratorError,
+    SchemaGenerator,
+    SchemaGeneratorConfig,
+    SchemaGeneratorError,
+    ValidationError,
+    generate_schema,
+)
+
+STUB = """
+namespace TestServer {
+    @mcp_principal
+    entity User;
+    @mcp_resource
+    entity McpServer;
+    action "call_tool" appliesTo {
+        principal: [User],
+        resource: [McpServer]
+    };
+}
+"""
+
+TOOLS = [
+    {
+        "name": "read_file",
+        "description": "Read a file from disk",
+        "inputSchema": {
+            "type": "object",
+            "properties": {"path": {"type": "string"}},
+            "required": ["path"],
+        },
+    }
+]
+
+
+def read_file_input(path):
+    return json.dumps({"params": {"tool": "read_file", "args": {"path": path}}})
+
+
+class TestSchemaGeneratorConfig:
+    def test_defaults(self):
+        config = SchemaGeneratorConfig()
+        assert config.include_outputs is False
+        assert config.erase_annotations is True
+
+    def test_attributes_are_settable(self):
+        config = SchemaGeneratorConfig(numbers_as_decimal=True)
+        config.include_outputs = True
+        assert config.numbers_as_decimal is True
+        assert config.include_outputs is True
+
+
+class TestSchemaGenerator:
+    def test_matches_generate_schema(self):
+        generator = SchemaGenerator(STUB)
+        generator.add_tools(json.dumps(TOOLS))
+        result = generate_schema(STUB, TOOLS)
+        assert generator.schema == result["schema"]
+        assert json.loads(generator.schema_json) == json.loads(result["schemaJson"])
+
+    def test_add_tool_incrementally(self):
+        generator = SchemaGenerator(STUB, SchemaGeneratorConfig(erase_annotations=False))
+        generator.add_tool(json.dumps(TOOLS[0]))
+        assert "read_file" in generator.schema
+        with pytest.raises(SchemaGeneratorError):
+            generator.add_tool(json.dumps(TOOLS[0]))
+
+    def test_invalid_stub_raises(self):
+        with pytest.raises(SchemaGeneratorError):
+            SchemaGenerator("not a valid schema")
+
+    def test_invalid_tools_raises(self):
+        generator = SchemaGenerator(STUB)
+        with pytest.raises(DeserializationError):
+            generator.add_tools("not valid json")
+        assert isinstance(DeserializationError("x"), ValueError)
+
+
+class TestRequestGenerator:
+    def test_generate_request(self):
+        generator = SchemaGenerator(STUB)
+        generator.add_tools(json.dumps(TOOLS))
+        request_generator = generator.request_generator()
+
+        for path in ["/tmp/a", "/tmp/b"]:
+            components = request_generator.generate_request(
+                read_file_input(path),
+                'TestServer::User::"alice"',
+                'TestServer::McpServer::"s1"',
+            )
+            assert isinstance(components, AuthorizationComponents)
+            assert components.principal == 'TestServer::User::"alice"'
+            assert components.action == 'TestServer::Action::"read_file"'
+            assert components.resource == 'TestServer::McpServer::"s1"'
+            assert json.loads(components.context_json) == {"input": {"path": path}}
+            assert isinstance(json.loads(components.entities_json), list)
+
+    def test_validation_error(self):
+        generator = SchemaGenerator(STUB)
+        generator.add_tools(json.dumps(TOOLS))
+        request_generator = generator.request_generator()
+        with pytest.raises(ValidationError):
+            request_generator.generate_request(
+                json.dumps({"params": {"tool": "read_file", "args": {"path": 1}}}),
+                'TestServer::User::"alice"',
+                'TestServer::McpServer::"s1"',
+            )
+        assert issubclass(ValidationError, RequestGeneratorError)
+
+    def test_invalid_input_raises(self):
+        generator = SchemaGenerator(STUB)
+        generator.add_tools(json.dumps(TOOLS))
+        with pytest.raises(DeserializationError):
+            generator.request_generator().generate_request(
+                "not valid json",
+                'TestServer::User::"alice"',
+                'TestServer::McpServer::"s1"',
+            )
+
+    def test_invalid_principal_raises(self):
+        generator = SchemaGenerator(STUB)
+        generator.add_tools(json.dumps(TOOLS))
+        with pytest.raises(RequestGeneratorError):
+            generator.request_generator().generate_request(
+                read_file_input("/tmp"),
+                "alice",
+                'TestServer::McpServer::"s1"',
+            )
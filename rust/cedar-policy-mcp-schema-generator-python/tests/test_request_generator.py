@@ -19,7 +19,10 @@ import json
 import pytest
 
 from cedar_mcp_schema_generator import (
+    AuthorizationError,
     RequestGeneratorError,
+    authorize,
+    authorize_or_raise,
     generate_request,
     generate_request_or_raise,
 )
@@ -111,6 +114,19 @@ class TestGenerateRequest:
         entities = json.loads(result["entitiesJson"])
         assert isinstance(entities, list)
 
+    def test_context_json_contains_input(self):
+        result = generate_request(
+            STUB,
+            TOOLS,
+            {"params": {"tool": "read_file", "args": {"path": "/tmp"}}},
+            principal_type="User",
+            principal_id="alice",
+            resource_type="McpServer",
+            resource_id="s1",
+        )
+        context = json.loads(result["contextJson"])
+        assert context == {"input": {"path": "/tmp"}}
+
     def test_invalid_input(self):
         result = generate_request(
             STUB,
@@ -250,6 +266,55 @@ class TestGenerateRequest:
         entities = json.loads(result["entitiesJson"])
         assert len(entities) > 0
 
+    def test_deterministic_entity_ids(self):
+        tools = [
+            {
+                "name": "update_customer",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "customer": {
+                            "type": "object",
+                            "properties": {
+                                "id": {"type": "string"},
+                                "name": {"type": "string"},
+                            },
+                            "additionalProperties": {"type": "string"},
+                        }
+                    },
+                },
+            }
+        ]
+        tool_input = {
+            "params": {
+                "tool": "update_customer",
+                "args": {"customer": {"id": "c1", "name": "Bob"}},
+            }
+        }
+
+        def entity_ids(config):
+            result = generate_request(
+                STUB,
+                tools,
+                tool_input,
+                principal_type="User",
+                principal_id="alice",
+                resource_type="McpServer",
+                resource_id="s1",
+                config=config,
+            )
+            assert result["isOk"] is True
+            return [
+                e["uid"]["id"]
+                for e in json.loads(result["entitiesJson"])
+                if not e["uid"]["type"].endswith("Action")
+            ]
+
+        assert entity_ids({}) != entity_ids({})
+        config = {"deterministicEntityIds": True}
+        assert entity_ids(config) == entity_ids(config)
+        assert entity_ids({"entityIdKey": "id"}) == ["c1"]
+
     def test_config_with_request(self):
         result = generate_request(
             STUB,
@@ -289,3 +354,68 @@ class TestGenerateRequestOrRaise:
                 resource_type="McpServer",
                 resource_id="s1",
             )
+
+
+class TestAuthorize:
+    def test_allow(self):
+        result = authorize(
+            STUB,
+            TOOLS,
+            {"params": {"tool": "read_file", "args": {"path": "/tmp/notes"}}},
+            principal_type="User",
+            principal_id="alice",
+            resource_type="McpServer",
+            resource_id="s1",
+            policies='permit(principal, action, resource) when { context.input.path like "/tmp/*" };',
+        )
+        assert result["isOk"] is True
+        assert result["decision"] == "allow"
+        assert result["determiningPolicies"] == ["policy0"]
+        assert result["errors"] == []
+
+    def test_deny_with_evaluation_error(self):
+        result = authorize(
+            STUB,
+            TOOLS,
+            {"params": {"tool": "read_file", "args": {"path": "/etc/hosts"}}},
+            principal_type="User",
+            principal_id="alice",
+            resource_type="McpServer",
+            resource_id="s1",
+            policies='permit(principal, action, resource) when { principal.admin };',
+            entities=[{"uid": {"type": "TestServer::User", "id": "alice"}, "attrs": {}, "parents": []}],
+        )
+        assert result["isOk"] is True
+        assert result["decision"] == "deny"
+        assert result["determiningPolicies"] == []
+        assert [e["policyId"] for e in result["errors"]] == ["policy0"]
+
+    def test_invalid_policies(self):
+        result = authorize(
+            STUB,
+            TOOLS,
+            {"params": {"tool": "read_file", "args": {"path": "/tmp"}}},
+            principal_type="User",
+            principal_id="alice",
+            resource_type="McpServer",
+            resource_id="s1",
+            policies="permit(",
+        )
+        assert result["isOk"] is False
+        assert result["decision"] is None
+        assert "Failed to parse Cedar policies" in result["error"]
+
+
+class TestAuthorizeOrRaise:
+    def test_raises_on_error(self):
+        with pytest.raises(AuthorizationError, match="Invalid tool input"):
+            authorize_or_raise(
+                STUB,
+                TOOLS,
+                "bad json",
+                principal_type="User",
+                principal_id="alice",
+                resource_type="McpServer",
+                resource_id="s1",
+                policies="",
+            )
@@ -119,10 +119,51 @@ class TestGenerateSchema:
                 "flattenNamespaces": True,
                 "numbersAsDecimal": True,
                 "deduplicateEntityTypes": True,
+                "annotationActionGroups": True,
+                "docAnnotations": True,
+                "redactionActions": True,
             },
         )
         assert result["isOk"] is True
 
+    def test_config_annotation_action_groups(self):
+        tools = [
+            {
+                "name": "read_item",
+                "inputSchema": {"type": "object", "properties": {}},
+                "annotations": {"readOnlyHint": True},
+            }
+        ]
+        result = generate_schema(STUB, tools, config={"annotationActionGroups": True})
+        assert result["isOk"] is True
+        assert 'action "readOnlyTools";' in result["schema"]
+        assert (
+            'action "read_item" in [Action::"readOnlyTools", Action::"idempotentTools", '
+            'Action::"openWorldTools"]' in result["schema"]
+        )
+
+    def test_config_redaction_actions(self):
+        tools = [
+            {
+                "name": "get_customer",
+                "inputSchema": {"type": "object", "properties": {}},
+                "outputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "ssn": {"type": "string"},
+                    },
+                    "required": ["name"],
+                },
+            }
+        ]
+        result = generate_schema(
+            STUB, tools, config={"includeOutputs": True, "redactionActions": True}
+        )
+        assert result["isOk"] is True
+        assert 'action "get_customer.ssn"' in result["schema"]
+        assert 'action "get_customer.name"' not in result["schema"]
+
     def test_multi_tool(self):
         tools = [
             {
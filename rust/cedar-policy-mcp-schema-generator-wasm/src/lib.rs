@@ -24,7 +24,10 @@
 //! [`cedar_policy_mcp_schema_generator`], including schema stub parsing. This
 //! avoids a direct dependency on `cedar-policy-core` in the bindings crate.
 
-use cedar_policy_mcp_schema_generator::{SchemaGenerator, SchemaGeneratorConfig};
+use cedar_policy_mcp_schema_generator::{
+    AuthorizationComponents, AuthorizationResponse, Decision, RequestGenerator, SchemaGenerator,
+    SchemaGeneratorConfig,
+};
 use mcp_tools_sdk::data::Input;
 use mcp_tools_sdk::description::ServerDescription;
 use serde::{Deserialize, Serialize};
@@ -47,6 +50,16 @@ struct WasmConfig {
     numbers_as_decimal: bool,
     #[serde(default)]
     deduplicate_entity_types: bool,
+    #[serde(default)]
+    annotation_action_groups: bool,
+    #[serde(default)]
+    doc_annotations: bool,
+    #[serde(default)]
+    redaction_actions: bool,
+    #[serde(default)]
+    deterministic_entity_ids: bool,
+    #[serde(default)]
+    entity_id_key: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -62,10 +75,37 @@ impl Default for WasmConfig {
             flatten_namespaces: false,
             numbers_as_decimal: false,
             deduplicate_entity_types: false,
+            annotation_action_groups: false,
+            doc_annotations: false,
+            redaction_actions: false,
+            deterministic_entity_ids: false,
+            entity_id_key: None,
         }
     }
 }
 
+/// Parse an optional configuration JSON string. `None` and empty strings
+/// produce the default configuration.
+///
+/// Surfaces the underlying serde_json message when one is available, and
+/// falls back to "unrecognized fields" only when the JSON is structurally
+/// valid but does not match the expected shape.
+fn parse_config(config_json: Option<&str>) -> Result<SchemaGeneratorConfig, String> {
+    match config_json {
+        Some(json) if !json.is_empty() => serde_json::from_str::<WasmConfig>(json)
+            .map(SchemaGeneratorConfig::from)
+            .map_err(|_| {
+                format!(
+                    "Invalid config: {}",
+                    serde_json::from_str::<serde_json::Value>(json)
+                        .err()
+                        .map_or_else(|| "unrecognized fields".to_string(), |e| e.to_string())
+                )
+            }),
+        _ => Ok(SchemaGeneratorConfig::default()),
+    }
+}
+
 impl From<WasmConfig> for SchemaGeneratorConfig {
     fn from(c: WasmConfig) -> Self {
         SchemaGeneratorConfig::default()
@@ -75,6 +115,11 @@ impl From<WasmConfig> for SchemaGeneratorConfig {
             .flatten_namespaces(c.flatten_namespaces)
             .encode_numbers_as_decimal(c.numbers_as_decimal)
             .deduplicate_entity_types(c.deduplicate_entity_types)
+            .annotation_action_groups(c.annotation_action_groups)
+            .doc_annotations(c.doc_annotations)
+            .redaction_actions(c.redaction_actions)
+            .deterministic_entity_ids(c.deterministic_entity_ids)
+            .entity_id_key(c.entity_id_key.map(Into::into))
     }
 }
 
@@ -139,12 +184,28 @@ struct WasmRequestResult {
     resource: Option<String>,
     /// The entities as a JSON array string.
     entities_json: Option<String>,
+    /// The context as a JSON object string (Cedar JSON format).
+    context_json: Option<String>,
     /// Error message, `null` if successful.
     error: Option<String>,
     /// Whether generation succeeded.
     is_ok: bool,
 }
 
+impl From<AuthorizationComponents> for WasmRequestResult {
+    fn from(components: AuthorizationComponents) -> Self {
+        Self {
+            principal: Some(components.principal),
+            action: Some(components.action),
+            resource: Some(components.resource),
+            entities_json: Some(components.entities_json),
+            context_json: Some(components.context_json),
+            error: None,
+            is_ok: true,
+        }
+    }
+}
+
 /// Generate a Cedar authorization request from an MCP tool call.
 ///
 /// Takes the same schema stub and tool descriptions used for schema generation,
@@ -167,7 +228,8 @@ struct WasmRequestResult {
 /// # Returns
 ///
 /// A JSON object with `principal`, `action`, `resource` (Cedar EntityUID
-/// strings), `entitiesJson` (JSON array string), `error`, and `isOk` fields.
+/// strings), `entitiesJson` (JSON array string), `contextJson` (JSON object
+/// string), `error`, and `isOk` fields.
 #[wasm_bindgen(js_name = "generateRequest")]
 #[expect(
     clippy::too_many_arguments,
@@ -197,7 +259,7 @@ pub fn generate_request(
     drop(config_json);
     serde_json::to_string(&result).unwrap_or_else(|e| {
         format!(
-            r#"{{"isOk":false,"error":"Serialization error: {}","principal":null,"action":null,"resource":null,"entitiesJson":null}}"#,
+            r#"{{"isOk":false,"error":"Serialization error: {}","principal":null,"action":null,"resource":null,"entitiesJson":null,"contextJson":null}}"#,
             e
         )
     })
@@ -217,23 +279,10 @@ fn generate_request_inner(
     resource_id: &str,
     config_json: Option<&str>,
 ) -> WasmRequestResult {
-    // Parse config. Mirrors the detailed error path in schema generation:
-    // surface the underlying serde_json message when one is available, and
-    // fall back to "unrecognized fields" only when the JSON is structurally
-    // valid but does not match the expected shape.
-    let config: SchemaGeneratorConfig = match config_json {
-        Some(json) if !json.is_empty() => {
-            let Ok(c) = serde_json::from_str::<WasmConfig>(json) else {
-                return req_err(format!(
-                    "Invalid config: {}",
-                    serde_json::from_str::<serde_json::Value>(json)
-                        .err()
-                        .map_or_else(|| "unrecognized fields".to_string(), |e| e.to_string())
-                ));
-            };
-            c.into()
-        }
-        _ => SchemaGeneratorConfig::default(),
+    // Parse config
+    let config = match parse_config(config_json) {
+        Ok(config) => config,
+        Err(e) => return req_err(e),
     };
 
     // Build SchemaGenerator
@@ -271,15 +320,188 @@ fn generate_request_inner(
         resource_type,
         resource_id,
     ) {
-        Ok(components) => WasmRequestResult {
-            principal: Some(components.principal),
-            action: Some(components.action),
-            resource: Some(components.resource),
-            entities_json: Some(components.entities_json),
+        Ok(components) => components.into(),
+        Err(e) => req_err(format!("Request generation error: {e}")),
+    }
+}
+
+/// An error encountered while evaluating a policy, returned to JavaScript.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WasmPolicyEvaluationError {
+    /// The ID of the policy that produced the error.
+    policy_id: String,
+    /// The error message.
+    message: String,
+}
+
+/// Result returned to JavaScript from authorization.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WasmAuthorizationResult {
+    /// The authorization decision, `"allow"` or `"deny"`. `null` if
+    /// authorization failed.
+    decision: Option<String>,
+    /// The IDs of the policies that determined the decision.
+    determining_policies: Vec<String>,
+    /// The errors encountered while evaluating policies.
+    errors: Vec<WasmPolicyEvaluationError>,
+    /// Error message, `null` if successful.
+    error: Option<String>,
+    /// Whether authorization succeeded.
+    is_ok: bool,
+}
+
+impl From<AuthorizationResponse> for WasmAuthorizationResult {
+    fn from(response: AuthorizationResponse) -> Self {
+        let decision = match response.decision {
+            Decision::Allow => "allow",
+            Decision::Deny => "deny",
+        };
+        Self {
+            decision: Some(decision.to_string()),
+            determining_policies: response.determining_policies,
+            errors: response
+                .errors
+                .into_iter()
+                .map(|err| WasmPolicyEvaluationError {
+                    policy_id: err.policy_id,
+                    message: err.message,
+                })
+                .collect(),
             error: None,
             is_ok: true,
-        },
-        Err(e) => req_err(format!("Request generation error: {e}")),
+        }
+    }
+}
+
+/// Authorize an MCP tool call against a set of Cedar policies.
+///
+/// Generates the Cedar authorization request for the tool call (see
+/// [`generate_request`]) and evaluates `policies` against it with an empty
+/// context.
+///
+/// # Arguments
+///
+/// * `schema_stub` - A Cedar schema stub as a `.cedarschema` string.
+/// * `tools_json` - MCP tool descriptions as a JSON string.
+/// * `input_json` - MCP tool call input as a JSON string.
+/// * `principal_type` - The Cedar entity type for the principal (e.g., `"User"`).
+/// * `principal_id` - The principal identifier (e.g., `"alice"`).
+/// * `resource_type` - The Cedar entity type for the resource (e.g., `"McpServer"`).
+/// * `resource_id` - The resource identifier (e.g., `"my-server"`).
+/// * `policies` - Cedar policies in Cedar policy syntax.
+/// * `entities_json` - Additional entities (e.g., the principal's) as a JSON
+///   array in Cedar's entity format.
+/// * `config_json` - Optional configuration as a JSON string.
+///
+/// # Returns
+///
+/// A JSON object with `decision` (`"allow"` or `"deny"`), `determiningPolicies`
+/// (policy IDs), `errors` (`{policyId, message}` objects), `error`, and `isOk` fields.
+#[wasm_bindgen(js_name = "authorize")]
+#[expect(
+    clippy::too_many_arguments,
+    reason = "wasm-bindgen requires flat parameter lists; cannot use struct across WASM boundary"
+)]
+pub fn authorize(
+    schema_stub: &str,
+    tools_json: &str,
+    input_json: &str,
+    principal_type: &str,
+    principal_id: &str,
+    resource_type: &str,
+    resource_id: &str,
+    policies: &str,
+    entities_json: &str,
+    config_json: Option<String>,
+) -> String {
+    let config_ref = config_json.as_deref();
+    let result = authorize_inner(
+        schema_stub,
+        tools_json,
+        input_json,
+        principal_type,
+        principal_id,
+        resource_type,
+        resource_id,
+        policies,
+        entities_json,
+        config_ref,
+    );
+    drop(config_json);
+    serde_json::to_string(&result).unwrap_or_else(|e| {
+        format!(
+            r#"{{"isOk":false,"error":"Serialization error: {}","decision":null,"determiningPolicies":[],"errors":[]}}"#,
+            e
+        )
+    })
+}
+
+#[expect(
+    clippy::too_many_arguments,
+    reason = "Mirrors authorize's flat parameter list for WASM boundary"
+)]
+fn authorize_inner(
+    schema_stub: &str,
+    tools_json: &str,
+    input_json: &str,
+    principal_type: &str,
+    principal_id: &str,
+    resource_type: &str,
+    resource_id: &str,
+    policies: &str,
+    entities_json: &str,
+    config_json: Option<&str>,
+) -> WasmAuthorizationResult {
+    let config = match parse_config(config_json) {
+        Ok(config) => config,
+        Err(e) => return auth_err(e),
+    };
+
+    let Ok(mut generator) = SchemaGenerator::from_cedarschema_str_with_config(schema_stub, config)
+    else {
+        return auth_err("Schema error: failed to parse schema stub".to_string());
+    };
+
+    let Ok(server_desc) = ServerDescription::from_json_str(tools_json) else {
+        return auth_err("Invalid tool descriptions: failed to parse JSON".to_string());
+    };
+
+    if let Err(e) = generator.add_actions_from_server_description(&server_desc) {
+        return auth_err(format!("Error adding tools: {e}"));
+    }
+
+    let Ok(req_gen) = generator.new_request_generator() else {
+        return auth_err("Failed to create request generator".to_string());
+    };
+
+    let Ok(input) = Input::from_json_str(input_json) else {
+        return auth_err("Invalid tool input: failed to parse JSON".to_string());
+    };
+
+    match req_gen.is_authorized_from_strings(
+        &input,
+        None,
+        principal_type,
+        principal_id,
+        resource_type,
+        resource_id,
+        policies,
+        entities_json,
+    ) {
+        Ok(response) => response.into(),
+        Err(e) => auth_err(format!("Authorization error: {e}")),
+    }
+}
+
+fn auth_err(error: String) -> WasmAuthorizationResult {
+    WasmAuthorizationResult {
+        decision: None,
+        determining_policies: Vec::new(),
+        errors: Vec::new(),
+        error: Some(error),
+        is_ok: false,
     }
 }
 
@@ -289,6 +511,7 @@ fn req_err(error: String) -> WasmRequestResult {
         action: None,
         resource: None,
         entities_json: None,
+        context_json: None,
         error: Some(error),
         is_ok: false,
     }
@@ -310,19 +533,9 @@ fn generate_schema_inner(
     config_json: Option<&str>,
 ) -> WasmSchemaResult {
     // Parse config
-    let config: SchemaGeneratorConfig = match config_json {
-        Some(json) if !json.is_empty() => {
-            let Ok(c) = serde_json::from_str::<WasmConfig>(json) else {
-                return err_result(format!(
-                    "Invalid config: {}",
-                    serde_json::from_str::<serde_json::Value>(json)
-                        .err()
-                        .map_or_else(|| "unrecognized fields".to_string(), |e| e.to_string())
-                ));
-            };
-            c.into()
-        }
-        _ => SchemaGeneratorConfig::default(),
+    let config = match parse_config(config_json) {
+        Ok(config) => config,
+        Err(e) => return err_result(e),
     };
 
     // Parse schema stub and create generator via the generator crate's
@@ -362,9 +575,182 @@ fn generate_schema_inner(
     }
 }
 
+/// A reusable schema generator, exposed to JavaScript as `SchemaGenerator`.
+///
+/// Unlike [`generate_schema`] and [`generate_request`], the schema stub and
+/// tool descriptions are parsed once when building the generator, so
+/// repeated request generation does not rebuild the schema.
+#[wasm_bindgen(js_name = "SchemaGenerator")]
+#[derive(Debug)]
+pub struct WasmSchemaGenerator {
+    generator: SchemaGenerator,
+}
+
+#[wasm_bindgen(js_class = "SchemaGenerator")]
+impl WasmSchemaGenerator {
+    /// Create a schema generator from a schema stub and an optional
+    /// configuration as a JSON string.
+    ///
+    /// Throws if the schema stub or configuration is invalid.
+    #[wasm_bindgen(constructor)]
+    pub fn new(schema_stub: &str, config_json: Option<String>) -> Result<Self, JsError> {
+        let result = Self::new_inner(schema_stub, config_json.as_deref());
+        drop(config_json);
+        result.map_err(|e| JsError::new(&e))
+    }
+
+    /// Add an action for each MCP tool description in `tools_json` (the
+    /// `tools` array from an MCP `tools/list` response).
+    ///
+    /// Throws if the tool descriptions are invalid. The generator is left
+    /// unchanged in that case.
+    #[wasm_bindgen(js_name = "addTools")]
+    pub fn add_tools(&mut self, tools_json: &str) -> Result<(), JsError> {
+        self.add_tools_inner(tools_json)
+            .map_err(|e| JsError::new(&e))
+    }
+
+    /// The generated Cedar schema as human-readable `.cedarschema` text.
+    pub fn schema(&self) -> String {
+        self.generator.get_schema_as_str()
+    }
+
+    /// The generated Cedar schema as JSON (for `isAuthorized()`).
+    #[wasm_bindgen(js_name = "schemaJson")]
+    pub fn schema_json(&self) -> Result<String, JsError> {
+        self.schema_json_inner().map_err(|e| JsError::new(&e))
+    }
+
+    /// Create a [`WasmRequestGenerator`] for the tools added so far.
+    ///
+    /// Tools added afterwards are not known to the returned request generator.
+    #[wasm_bindgen(js_name = "requestGenerator")]
+    pub fn request_generator(&self) -> Result<WasmRequestGenerator, JsError> {
+        self.request_generator_inner().map_err(|e| JsError::new(&e))
+    }
+}
+
+// `JsError` can only be constructed on wasm targets, so the logic lives in
+// helpers returning `String` errors that can be exercised by native tests.
+impl WasmSchemaGenerator {
+    fn new_inner(schema_stub: &str, config_json: Option<&str>) -> Result<Self, String> {
+        let config = parse_config(config_json)?;
+        let generator = SchemaGenerator::from_cedarschema_str_with_config(schema_stub, config)
+            .map_err(|_| "Schema error: failed to parse schema stub".to_string())?;
+        Ok(Self { generator })
+    }
+
+    fn add_tools_inner(&mut self, tools_json: &str) -> Result<(), String> {
+        let server_desc = ServerDescription::from_json_str(tools_json)
+            .map_err(|_| "Invalid tool descriptions: failed to parse JSON".to_string())?;
+        self.generator
+            .add_actions_from_server_description(&server_desc)
+            .map_err(|e| format!("Error adding tools: {e}"))
+    }
+
+    fn schema_json_inner(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self.generator.get_schema())
+            .map_err(|e| format!("JSON serialization error: {e}"))
+    }
+
+    fn request_generator_inner(&self) -> Result<WasmRequestGenerator, String> {
+        let generator = self
+            .generator
+            .new_request_generator()
+            .map_err(|_| "Failed to create request generator".to_string())?;
+        Ok(WasmRequestGenerator { generator })
+    }
+}
+
+/// A reusable request generator, exposed to JavaScript as `RequestGenerator`.
+///
+/// Created with `SchemaGenerator.requestGenerator()`.
+#[wasm_bindgen(js_name = "RequestGenerator")]
+#[derive(Debug)]
+pub struct WasmRequestGenerator {
+    generator: RequestGenerator,
+}
+
+#[wasm_bindgen(js_class = "RequestGenerator")]
+impl WasmRequestGenerator {
+    /// Generate a Cedar authorization request from an MCP tool call.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_json` - MCP tool call input as a JSON string. Format:
+    ///   `{"params": {"tool": "tool_name", "args": {"key": "value"}}}`.
+    /// * `principal` - The principal EntityUID (e.g., `MyServer::User::"alice"`).
+    /// * `resource` - The resource EntityUID (e.g., `MyServer::McpServer::"server1"`).
+    /// * `context_json` - Optional additional context attributes as a JSON
+    ///   object in Cedar's JSON format. Defaults to `{}`.
+    /// * `entities_json` - Optional additional entities as a JSON array in
+    ///   Cedar's entity format. Defaults to `[]`.
+    ///
+    /// # Returns
+    ///
+    /// A JSON object with the same fields as returned by [`generate_request`].
+    #[wasm_bindgen(js_name = "generateRequest")]
+    pub fn generate_request(
+        &self,
+        input_json: &str,
+        principal: &str,
+        resource: &str,
+        context_json: Option<String>,
+        entities_json: Option<String>,
+    ) -> String {
+        let result = self.generate_request_inner(
+            input_json,
+            principal,
+            resource,
+            context_json.as_deref(),
+            entities_json.as_deref(),
+        );
+        drop(context_json);
+        drop(entities_json);
+        serde_json::to_string(&result).unwrap_or_else(|e| {
+            format!(
+                r#"{{"isOk":false,"error":"Serialization error: {}","principal":null,"action":null,"resource":null,"entitiesJson":null,"contextJson":null}}"#,
+                e
+            )
+        })
+    }
+}
+
+impl WasmRequestGenerator {
+    fn generate_request_inner(
+        &self,
+        input_json: &str,
+        principal: &str,
+        resource: &str,
+        context_json: Option<&str>,
+        entities_json: Option<&str>,
+    ) -> WasmRequestResult {
+        let Ok(input) = Input::from_json_str(input_json) else {
+            return req_err("Invalid tool input: failed to parse JSON".to_string());
+        };
+
+        match self.generator.generate_request_components_from_json(
+            &input,
+            None,
+            principal,
+            resource,
+            context_json.unwrap_or("{}"),
+            entities_json.unwrap_or("[]"),
+        ) {
+            Ok(components) => components.into(),
+            Err(e) => req_err(format!("Request generation error: {e}")),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    #![allow(clippy::expect_used, clippy::panic, clippy::unwrap_used)]
+    #![allow(
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unwrap_used,
+        reason = "test assertions"
+    )]
     use super::*;
 
     #[test]
@@ -569,6 +955,11 @@ mod tests {
         assert!(!config.flatten_namespaces);
         assert!(!config.numbers_as_decimal);
         assert!(!config.deduplicate_entity_types);
+        assert!(!config.annotation_action_groups);
+        assert!(!config.doc_annotations);
+        assert!(!config.redaction_actions);
+        assert!(!config.deterministic_entity_ids);
+        assert!(config.entity_id_key.is_none());
     }
 
     #[test]
@@ -580,6 +971,11 @@ mod tests {
             flatten_namespaces: true,
             numbers_as_decimal: true,
             deduplicate_entity_types: true,
+            annotation_action_groups: true,
+            doc_annotations: true,
+            redaction_actions: true,
+            deterministic_entity_ids: true,
+            entity_id_key: Some("id".to_string()),
         };
         let _config: SchemaGeneratorConfig = wasm_config.into();
         // Conversion should not panic
@@ -637,7 +1033,12 @@ mod tests {
 
 #[cfg(test)]
 mod coverage_tests {
-    #![allow(clippy::expect_used, clippy::panic, clippy::unwrap_used)]
+    #![allow(
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unwrap_used,
+        reason = "test assertions"
+    )]
     use super::*;
 
     /// Stub shared across coverage tests.
@@ -894,6 +1295,23 @@ mod coverage_tests {
         );
     }
 
+    #[test]
+    fn test_annotation_action_groups_config() {
+        let tools = r#"[{
+            "name": "delete_item",
+            "inputSchema": { "type": "object", "properties": {} },
+            "annotations": { "destructiveHint": true, "openWorldHint": false }
+        }]"#;
+        let result =
+            generate_schema_inner(STUB, tools, Some(r#"{"annotationActionGroups": true}"#));
+        assert!(result.is_ok, "Error: {:?}", result.error);
+        let schema = result.schema.unwrap();
+        assert!(
+            schema.contains(r#"action "delete_item" in [Action::"destructiveTools"]"#),
+            "{schema}"
+        );
+    }
+
     #[test]
     fn test_deduplicate_entity_types_config() {
         // Two tools share an identical enum; with deduplication enabled,
@@ -983,7 +1401,13 @@ mod coverage_tests {
 
 #[cfg(test)]
 mod request_tests {
-    #![allow(clippy::expect_used, clippy::panic, clippy::unwrap_used)]
+    #![allow(
+        clippy::expect_used,
+        clippy::indexing_slicing,
+        clippy::panic,
+        clippy::unwrap_used,
+        reason = "test assertions"
+    )]
     use super::*;
 
     const STUB: &str = r#"
@@ -1163,6 +1587,7 @@ mod request_tests {
         assert!(result.action.is_none());
         assert!(result.resource.is_none());
         assert!(result.entities_json.is_none());
+        assert!(result.context_json.is_none());
         assert!(result.error.is_some());
     }
 
@@ -1331,6 +1756,58 @@ mod request_tests {
         );
     }
 
+    #[test]
+    fn test_authorize_reports_decision_and_diagnostics() {
+        let input = r#"{"params": {"tool": "read_file", "args": {"path": "/tmp"}}}"#;
+        let policies = r#"
+            permit(principal, action, resource) when { context.input.path like "/tmp*" };
+            permit(principal, action, resource) when { context.input.path.foo == "" };
+        "#;
+        let result_json = authorize(
+            STUB,
+            TOOLS,
+            input,
+            "User",
+            "alice",
+            "McpServer",
+            "server1",
+            policies,
+            "[]",
+            None,
+        );
+        let result: WasmAuthorizationResult =
+            serde_json::from_str(&result_json).expect("Should parse result");
+        assert!(result.is_ok, "Error: {:?}", result.error);
+        assert_eq!(result.decision.as_deref(), Some("allow"));
+        assert_eq!(result.determining_policies, vec!["policy0".to_string()]);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].policy_id, "policy1");
+    }
+
+    #[test]
+    fn test_authorize_malformed_policies() {
+        let input = r#"{"params": {"tool": "read_file", "args": {"path": "/tmp"}}}"#;
+        let result = authorize_inner(
+            STUB,
+            TOOLS,
+            input,
+            "User",
+            "alice",
+            "McpServer",
+            "server1",
+            "permit(",
+            "[]",
+            None,
+        );
+        assert!(!result.is_ok);
+        assert!(result.decision.is_none());
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("Failed to parse Cedar policies"));
+    }
+
     #[test]
     fn test_req_err_helper() {
         let result = req_err("test error message".to_string());
@@ -1340,6 +1817,7 @@ mod request_tests {
         assert!(result.action.is_none());
         assert!(result.resource.is_none());
         assert!(result.entities_json.is_none());
+        assert!(result.context_json.is_none());
     }
 
     #[test]
@@ -1349,6 +1827,7 @@ mod request_tests {
             action: Some("NS::Action::\"read\"".to_string()),
             resource: Some("NS::McpServer::\"s1\"".to_string()),
             entities_json: Some("[]".to_string()),
+            context_json: Some("{}".to_string()),
             error: None,
             is_ok: true,
         };
@@ -1404,3 +1883,146 @@ mod request_tests {
         assert!(r.is_ok, "Empty config should succeed: {:?}", r.error);
     }
 }
+
+#[cfg(test)]
+mod class_tests {
+    #![allow(
+        clippy::expect_used,
+        clippy::panic,
+        clippy::unwrap_used,
+        reason = "test assertions"
+    )]
+    use super::*;
+
+    const STUB: &str = r#"
+        namespace TestServer {
+            @mcp_principal
+            entity User { admin: Bool };
+            @mcp_resource
+            entity McpServer;
+            action "call_tool" appliesTo {
+                principal: [User],
+                resource: [McpServer]
+            };
+        }
+    "#;
+
+    const TOOLS: &str = r#"[
+        {
+            "name": "read_file",
+            "description": "Read a file from disk",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string" }
+                },
+                "required": ["path"]
+            }
+        }
+    ]"#;
+
+    fn schema_generator() -> WasmSchemaGenerator {
+        let mut generator =
+            WasmSchemaGenerator::new_inner(STUB, None).expect("Should build generator");
+        generator.add_tools_inner(TOOLS).expect("Should add tools");
+        generator
+    }
+
+    #[test]
+    fn test_schema_generator_matches_generate_schema() {
+        let generator = schema_generator();
+        let result: WasmSchemaResult =
+            serde_json::from_str(&generate_schema(STUB, TOOLS, None)).expect("Should parse");
+        assert_eq!(Some(generator.schema()), result.schema);
+        assert_eq!(generator.schema_json_inner().ok(), result.schema_json);
+    }
+
+    #[test]
+    fn test_schema_generator_errors() {
+        let err = WasmSchemaGenerator::new_inner("bad", None).unwrap_err();
+        assert!(err.contains("Schema error"), "Got: {err}");
+        let err = WasmSchemaGenerator::new_inner(STUB, Some("{bad")).unwrap_err();
+        assert!(err.contains("Invalid config"), "Got: {err}");
+
+        let mut generator = schema_generator();
+        let schema = generator.schema();
+        let err = generator.add_tools_inner("bad").unwrap_err();
+        assert!(err.contains("Invalid tool descriptions"), "Got: {err}");
+        // Adding the same tool twice fails, leaving the schema unchanged
+        let err = generator.add_tools_inner(TOOLS).unwrap_err();
+        assert!(err.contains("Error adding tools"), "Got: {err}");
+        assert_eq!(generator.schema(), schema);
+    }
+
+    #[test]
+    fn test_request_generator_is_reusable() {
+        let request_generator = schema_generator()
+            .request_generator_inner()
+            .expect("Should build request generator");
+        let entities = r#"[{"uid": {"type": "TestServer::User", "id": "alice"}, "attrs": {"admin": true}, "parents": []}]"#;
+
+        for path in ["/tmp/a", "/tmp/b"] {
+            let input =
+                format!(r#"{{"params": {{"tool": "read_file", "args": {{"path": "{path}"}}}}}}"#);
+            let result = request_generator.generate_request_inner(
+                &input,
+                r#"TestServer::User::"alice""#,
+                r#"TestServer::McpServer::"s1""#,
+                None,
+                Some(entities),
+            );
+            assert!(result.is_ok, "Error: {:?}", result.error);
+            assert_eq!(
+                result.principal.as_deref(),
+                Some(r#"TestServer::User::"alice""#)
+            );
+            assert_eq!(
+                result.action.as_deref(),
+                Some(r#"TestServer::Action::"read_file""#)
+            );
+            assert!(result.context_json.unwrap().contains(path));
+            assert!(result.entities_json.unwrap().contains("alice"));
+        }
+    }
+
+    #[test]
+    fn test_request_generator_errors() {
+        let request_generator = schema_generator()
+            .request_generator_inner()
+            .expect("Should build request generator");
+        let input = r#"{"params": {"tool": "read_file", "args": {"path": "/tmp"}}}"#;
+
+        let result = request_generator.generate_request_inner(
+            "bad",
+            r#"TestServer::User::"alice""#,
+            r#"TestServer::McpServer::"s1""#,
+            None,
+            None,
+        );
+        assert!(!result.is_ok);
+        assert!(result.error.unwrap().contains("Invalid tool input"));
+
+        let result = request_generator.generate_request_inner(
+            input,
+            "alice",
+            r#"TestServer::McpServer::"s1""#,
+            None,
+            None,
+        );
+        assert!(!result.is_ok);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("Failed to parse Cedar entity UID"));
+
+        let result = request_generator.generate_request_inner(
+            input,
+            r#"TestServer::User::"alice""#,
+            r#"TestServer::McpServer::"s1""#,
+            Some(r#"{"unknown": 1}"#),
+            None,
+        );
+        assert!(!result.is_ok);
+        assert!(result.principal.is_none());
+    }
+}
@@ -16,7 +16,8 @@
 
 //! WASM integration tests for the Cedar MCP Schema Generator bindings.
 //!
-//! These tests exercise `generateSchema` and `generateRequest` through
+//! These tests exercise `generateSchema`, `generateRequest`, `authorize` and the
+//! `SchemaGenerator` / `RequestGenerator` classes through
 //! wasm-bindgen, verifying correct behavior at the JS/WASM boundary.
 
 #![expect(
@@ -26,7 +27,9 @@
     reason = "test assertions"
 )]
 
-use cedar_policy_mcp_schema_generator_wasm::{generate_request, generate_schema};
+use cedar_policy_mcp_schema_generator_wasm::{
+    authorize, generate_request, generate_schema, WasmSchemaGenerator,
+};
 use wasm_bindgen_test::*;
 
 // ─── Shared constants ───────────────────────────────────────────────────────
@@ -87,6 +90,7 @@ fn assert_request_ok(result: &serde_json::Value) {
     assert!(!result["action"].is_null());
     assert!(!result["resource"].is_null());
     assert!(!result["entitiesJson"].is_null());
+    assert!(!result["contextJson"].is_null());
 }
 
 /// Assert a request result failed and return the error message.
@@ -96,6 +100,7 @@ fn assert_request_err(result: &serde_json::Value) -> &str {
     assert!(result["action"].is_null());
     assert!(result["resource"].is_null());
     assert!(result["entitiesJson"].is_null());
+    assert!(result["contextJson"].is_null());
     result["error"].as_str().expect("error should be a string")
 }
 
@@ -368,6 +373,11 @@ fn test_generate_request_basic() {
     assert!(result["resource"].as_str().unwrap().contains("McpServer"));
     assert!(result["resource"].as_str().unwrap().contains("s1"));
 
+    // context_json should hold the encoded tool input
+    let context: serde_json::Value =
+        serde_json::from_str(result["contextJson"].as_str().unwrap()).unwrap();
+    assert_eq!(context["input"]["path"], "/tmp/test.txt");
+
     // entities_json should be a valid JSON array
     let entities: serde_json::Value =
         serde_json::from_str(result["entitiesJson"].as_str().unwrap()).unwrap();
@@ -454,6 +464,12 @@ fn test_generate_request_with_nested_object_produces_entities() {
         serde_json::from_str(result["entitiesJson"].as_str().unwrap()).unwrap();
     let arr = entities.as_array().unwrap();
     assert!(!arr.is_empty(), "Nested objects should produce entities");
+
+    // The context references the nested object's entity with an `__entity` escape
+    let context: serde_json::Value =
+        serde_json::from_str(result["contextJson"].as_str().unwrap()).unwrap();
+    let metadata = &context["input"]["metadata"]["__entity"];
+    assert!(arr.iter().any(|e| e["uid"] == *metadata));
 }
 
 #[wasm_bindgen_test]
@@ -635,3 +651,116 @@ fn test_schema_json_is_parseable_and_contains_actions() {
     let ns = schema_json.as_object().unwrap();
     assert!(!ns.is_empty(), "schemaJson should have namespace entries");
 }
+
+// ─── Authorization tests ────────────────────────────────────────────────────
+
+#[wasm_bindgen_test]
+fn test_authorize_allow_and_deny() {
+    let policies = r#"permit(principal == TestServer::User::"alice", action, resource);"#;
+    let input = tool_input("read_file", r#"{"path": "/tmp/test.txt"}"#);
+
+    let result = parse_result(&authorize(
+        STUB,
+        SINGLE_TOOL,
+        &input,
+        "User",
+        "alice",
+        "McpServer",
+        "s1",
+        policies,
+        "[]",
+        None,
+    ));
+    assert_eq!(result["isOk"], true, "Error: {:?}", result["error"]);
+    assert_eq!(result["decision"], "allow");
+    assert_eq!(
+        result["determiningPolicies"],
+        serde_json::json!(["policy0"])
+    );
+    assert_eq!(result["errors"], serde_json::json!([]));
+
+    let result = parse_result(&authorize(
+        STUB,
+        SINGLE_TOOL,
+        &input,
+        "User",
+        "bob",
+        "McpServer",
+        "s1",
+        policies,
+        "[]",
+        None,
+    ));
+    assert_eq!(result["isOk"], true, "Error: {:?}", result["error"]);
+    assert_eq!(result["decision"], "deny");
+    assert_eq!(result["determiningPolicies"], serde_json::json!([]));
+}
+
+#[wasm_bindgen_test]
+fn test_authorize_invalid_entities_returns_error() {
+    let input = tool_input("read_file", r#"{"path": "/tmp/test.txt"}"#);
+    let result = parse_result(&authorize(
+        STUB,
+        SINGLE_TOOL,
+        &input,
+        "User",
+        "alice",
+        "McpServer",
+        "s1",
+        "",
+        "not valid json",
+        None,
+    ));
+    assert_eq!(result["isOk"], false);
+    assert!(result["decision"].is_null());
+    assert!(result["error"]
+        .as_str()
+        .unwrap()
+        .contains("Failed to parse Cedar entities"));
+}
+
+// ─── Stateful class tests ───────────────────────────────────────────────────
+
+#[wasm_bindgen_test]
+fn test_schema_generator_class_matches_generate_schema() {
+    let mut generator = WasmSchemaGenerator::new(STUB, None).unwrap();
+    generator.add_tools(SINGLE_TOOL).unwrap();
+
+    let result = parse_result(&generate_schema(STUB, SINGLE_TOOL, None));
+    assert_eq!(generator.schema(), assert_schema_ok(&result));
+    assert_eq!(generator.schema_json().unwrap(), result["schemaJson"]);
+}
+
+#[wasm_bindgen_test]
+fn test_request_generator_class_matches_generate_request() {
+    let mut generator = WasmSchemaGenerator::new(STUB, None).unwrap();
+    generator.add_tools(SINGLE_TOOL).unwrap();
+    let request_generator = generator.request_generator().unwrap();
+
+    let input = tool_input("read_file", r#"{"path": "/tmp/test.txt"}"#);
+    let result = parse_result(&request_generator.generate_request(
+        &input,
+        r#"TestServer::User::"alice""#,
+        r#"TestServer::McpServer::"s1""#,
+        None,
+        None,
+    ));
+    assert_request_ok(&result);
+
+    let expected = parse_result(&generate_request(
+        STUB,
+        SINGLE_TOOL,
+        &input,
+        "User",
+        "alice",
+        "McpServer",
+        "s1",
+        None,
+    ));
+    assert_eq!(result, expected);
+}
+
+#[wasm_bindgen_test]
+fn test_schema_generator_class_invalid_stub_throws() {
+    assert!(WasmSchemaGenerator::new("invalid schema", None).is_err());
+}
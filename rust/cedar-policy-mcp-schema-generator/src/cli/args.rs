@@ -93,6 +93,27 @@ pub(crate) struct ConfigOptions {
     /// names and types.
     #[arg(long, default_value_t = false)]
     pub(crate) deduplicate_entity_types: bool,
+    /// Whether to add the action groups `readOnlyTools`, `destructiveTools`, `idempotentTools` and
+    /// `openWorldTools`, and make the action of each tool a member of the groups matching the
+    /// tool's MCP annotations (default: false).
+    #[arg(long, default_value_t = false)]
+    pub(crate) annotation_action_groups: bool,
+    /// Whether to add the descriptions of MCP tools, of their properties and of their type
+    /// definitions as `@doc` annotations of the generated actions, attributes and types (default: false).
+    #[arg(long, default_value_t = false)]
+    pub(crate) doc_annotations: bool,
+    /// With `--include-outputs`, add an action `"{tool}.{property}"` per optional output property
+    /// of each tool, deciding whether the property is disclosed in the tool's response (default: false).
+    #[arg(long, default_value_t = false)]
+    pub(crate) redaction_actions: bool,
+    /// Name the entities generated for objects after a hash of their type and value instead of
+    /// a random UUID, so that the same MCP request always generates the same entities (default: false).
+    #[arg(long, default_value_t = false)]
+    pub(crate) deterministic_entity_ids: bool,
+    /// Name the entity generated for an object after the value of its property `KEY`, if it is a
+    /// string or an integer (e.g., `--entity-id-key id`).
+    #[arg(long, value_name = "KEY")]
+    pub(crate) entity_id_key: Option<String>,
 }
 
 #[derive(ValueEnum, Clone, Copy, Debug, Serialize)]
@@ -166,6 +187,80 @@ pub(crate) enum Command {
         #[clap(flatten)]
         config: ConfigOptions,
     },
+    /// Validate a set of policies against the Cedar Schema generated from a Cedar Schema stub file
+    /// and an MCP Tools Description file, using Cedar's strict validation mode.
+    ///
+    /// Exits with a non-zero status if any policy fails to validate.
+    Validate {
+        /// A Cedar Schema stub file used as the basis of the schema.
+        #[clap(required = true)]
+        schema_stub: PathBuf,
+        /// A file containing the MCP Tool Descriptions to add as actions to schema stub file.
+        #[clap(required = true)]
+        tool_descriptions: PathBuf,
+        #[clap(flatten)]
+        policies: PoliciesArgs,
+        #[arg(long, default_value = "human")]
+        error_format: ErrorFormat,
+        #[clap(flatten)]
+        config: ConfigOptions,
+    },
+    /// Report the impact of a change of MCP tool descriptions (e.g., an MCP server upgrade) on a
+    /// set of policies.
+    ///
+    /// Generates a Cedar Schema for both the old and the new MCP Tool Descriptions, and reports
+    /// the actions and context attributes that were added, removed or retyped, along with the
+    /// policies that no longer validate or that reference removed or retyped attributes.
+    Impact {
+        /// A Cedar Schema stub file used as the basis of both schemas.
+        #[clap(required = true)]
+        schema_stub: PathBuf,
+        /// A file containing the old MCP Tool Descriptions.
+        #[clap(required = true)]
+        old_tool_descriptions: PathBuf,
+        /// A file containing the new MCP Tool Descriptions.
+        #[clap(required = true)]
+        new_tool_descriptions: PathBuf,
+        #[clap(flatten)]
+        policies: PoliciesArgs,
+        #[arg(long, default_value = "human")]
+        output_format: OutputFormat,
+        #[arg(long, default_value = "human")]
+        error_format: ErrorFormat,
+        #[clap(flatten)]
+        config: ConfigOptions,
+    },
+    /// Run an MCP server and proxy the JSON-RPC messages of an MCP client, read from stdin and
+    /// written to stdout, to and from the server's stdio, authorizing each `tools/call` request
+    /// against a set of policies.
+    ///
+    /// Denied tool calls are not forwarded to the server: the client receives a JSON-RPC error instead.
+    Proxy {
+        /// A Cedar Schema stub file used as the basis of the schema.
+        #[clap(required = true)]
+        schema_stub: PathBuf,
+        /// A file containing the MCP Tool Descriptions of the server.
+        #[clap(required = true)]
+        tool_descriptions: PathBuf,
+        #[clap(flatten)]
+        request: RequestArgs,
+        #[clap(flatten)]
+        policies: PoliciesArgs,
+        /// File containing the entities of the requests, in Cedar's JSON entity format (default: no entities).
+        #[arg(long = "entities", value_name = "FILE")]
+        entities: Option<PathBuf>,
+        /// Whether to also authorize the server's response to each allowed tool call (default: false).
+        /// Responses are authorized with the tool's output if `--include-outputs` is set.
+        #[arg(long, default_value_t = false)]
+        authorize_responses: bool,
+        #[arg(long, default_value = "human")]
+        error_format: ErrorFormat,
+        #[clap(flatten)]
+        config: ConfigOptions,
+        /// The command (and its arguments) starting the MCP server, given after `--`.
+        #[arg(last = true, required = true, value_name = "SERVER_COMMAND")]
+        server_command: Vec<String>,
+    },
 }
 
 /// Command Line Interface for Cedar MCP Schema Generator
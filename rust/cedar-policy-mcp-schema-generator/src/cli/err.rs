@@ -89,6 +89,14 @@ pub enum CliError {
         help("Provide a principal, e.g., using `--resource ResourceType::\"resource_id\".")
     )]
     MissingResource,
+    #[error("Missing MCP server command")]
+    #[diagnostic(
+        code(cli_error::missing_server_command),
+        help(
+            "Provide the command starting the MCP server after `--`, e.g., `-- npx my-mcp-server`."
+        )
+    )]
+    MissingServerCommand,
     #[error("Could not open cedar context file `{}`: {}", .0.file.display(), .0.error)]
     #[diagnostic(code(cli_error::file_open_error), help("Make sure {} exists and you have permissions to read it.", .0.file.display()))]
     ContextFileOpen(FileOpenError),
@@ -113,6 +121,15 @@ pub enum CliError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     RequestGeneration(#[from] crate::RequestGeneratorError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Proxy(#[from] crate::ProxyError),
+    #[error("The MCP server exited with {0}")]
+    #[diagnostic(code(cli_error::mcp_server_exit))]
+    McpServerExit(std::process::ExitStatus),
+    #[error("Policies failed to validate against the generated schema")]
+    #[diagnostic(code(cli_error::policy_validation))]
+    PolicyValidation(#[related] Vec<cedar_policy_core::validator::ValidationError>),
 }
 
 impl CliError {
@@ -17,16 +17,25 @@
 use crate::cli::{
     CliArgs, CliError, Command, ConfigOptions, ErrorFormat, OutputFormat, PoliciesArgs, RequestArgs,
 };
-use crate::{SchemaGenerator, SchemaGeneratorConfig};
+use crate::{
+    AttributeChange, AuthorizationProxy, AuthorizationResponse, Decision, PolicyImpactReport,
+    SchemaGenerator, SchemaGeneratorConfig,
+};
 
 use cedar_policy_core::ast::{Context, EntityUID, PolicySet};
 use cedar_policy_core::entities::Entities;
 use cedar_policy_core::extensions::Extensions;
-use cedar_policy_core::validator::{json_schema::Fragment, RawName};
+use cedar_policy_core::validator::{
+    json_schema::Fragment, RawName, ValidationMode, Validator, ValidatorSchema,
+};
 
 use mcp_tools_sdk::data::{Input, Output};
 use mcp_tools_sdk::description::ServerDescription;
+use miette::{
+    Diagnostic, JSONReportHandler, MietteHandler, NarratableReportHandler, ReportHandler,
+};
 
+use std::io::BufReader;
 use std::path::{Path, PathBuf};
 
 fn get_config(config_options: &ConfigOptions) -> SchemaGeneratorConfig {
@@ -37,6 +46,11 @@ fn get_config(config_options: &ConfigOptions) -> SchemaGeneratorConfig {
         .flatten_namespaces(config_options.flatten_namespaces)
         .encode_numbers_as_decimal(config_options.encode_numbers_as_decimal)
         .deduplicate_entity_types(config_options.deduplicate_entity_types)
+        .annotation_action_groups(config_options.annotation_action_groups)
+        .doc_annotations(config_options.doc_annotations)
+        .redaction_actions(config_options.redaction_actions)
+        .deterministic_entity_ids(config_options.deterministic_entity_ids)
+        .entity_id_key(config_options.entity_id_key.as_deref().map(Into::into))
 }
 
 fn read_schema(file: impl AsRef<Path>) -> Result<Fragment<RawName>, CliError> {
@@ -90,6 +104,163 @@ fn output_schema(
     })
 }
 
+fn output_response(response: &AuthorizationResponse, output_format: OutputFormat) {
+    match output_format {
+        OutputFormat::Human => {
+            match response.decision {
+                Decision::Allow => println!("ALLOW"),
+                Decision::Deny => println!("DENY"),
+            }
+            for error in &response.errors {
+                eprintln!(
+                    "warning: error while evaluating policy `{}`: {}",
+                    error.policy_id, error.message
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let errors = response
+                .errors
+                .iter()
+                .map(|error| {
+                    serde_json::json!({
+                        "policyId": error.policy_id,
+                        "message": error.message,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let response = serde_json::json!({
+                "decision": response.decision,
+                "determiningPolicies": response.determining_policies,
+                "errors": errors,
+            });
+            println!("{response}");
+        }
+    }
+}
+
+fn attribute_change_json(change: &AttributeChange) -> serde_json::Value {
+    serde_json::json!({
+        "action": change.action,
+        "path": change.path,
+        "oldType": change.old_type,
+        "newType": change.new_type,
+    })
+}
+
+fn output_report(report: &PolicyImpactReport, output_format: OutputFormat) {
+    match output_format {
+        OutputFormat::Human => {
+            if !report.has_changes() {
+                println!("No changes to actions or context types.");
+                return;
+            }
+            let print_actions = |heading: &str, actions: &[String]| {
+                if !actions.is_empty() {
+                    println!("{heading}:");
+                    for action in actions {
+                        println!("  {action}");
+                    }
+                }
+            };
+            print_actions("Added actions", &report.added_actions);
+            print_actions("Removed actions", &report.removed_actions);
+            let print_attributes = |heading: &str, changes: &[AttributeChange]| {
+                if !changes.is_empty() {
+                    println!("{heading}:");
+                    for change in changes {
+                        match (&change.old_type, &change.new_type) {
+                            (Some(old_type), Some(new_type)) => println!(
+                                "  {}: {} ({old_type} -> {new_type})",
+                                change.action, change.path
+                            ),
+                            (Some(ty), None) | (None, Some(ty)) => {
+                                println!("  {}: {} ({ty})", change.action, change.path)
+                            }
+                            (None, None) => println!("  {}: {}", change.action, change.path),
+                        }
+                    }
+                }
+            };
+            print_attributes("Added attributes", &report.added_attributes);
+            print_attributes("Removed attributes", &report.removed_attributes);
+            print_attributes("Retyped attributes", &report.retyped_attributes);
+            if report.policies.is_empty() {
+                println!("No impacted policies.");
+                return;
+            }
+            println!("Impacted policies:");
+            for policy in &report.policies {
+                println!("  {}:", policy.policy_id);
+                for error in &policy.validation_errors {
+                    println!("    fails to validate: {error}");
+                }
+                for change in &policy.removed_attributes {
+                    println!(
+                        "    references removed attribute `{}` of `{}`",
+                        change.path, change.action
+                    );
+                }
+                for change in &policy.retyped_attributes {
+                    println!(
+                        "    references retyped attribute `{}` of `{}`",
+                        change.path, change.action
+                    );
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let changes = |changes: &[AttributeChange]| {
+                changes
+                    .iter()
+                    .map(attribute_change_json)
+                    .collect::<Vec<_>>()
+            };
+            let policies = report
+                .policies
+                .iter()
+                .map(|policy| {
+                    serde_json::json!({
+                        "policyId": policy.policy_id,
+                        "validationErrors": policy.validation_errors,
+                        "removedAttributes": changes(&policy.removed_attributes),
+                        "retypedAttributes": changes(&policy.retyped_attributes),
+                    })
+                })
+                .collect::<Vec<_>>();
+            let report = serde_json::json!({
+                "addedActions": report.added_actions,
+                "removedActions": report.removed_actions,
+                "addedAttributes": changes(&report.added_attributes),
+                "removedAttributes": changes(&report.removed_attributes),
+                "retypedAttributes": changes(&report.retyped_attributes),
+                "policies": policies,
+            });
+            println!("{report}");
+        }
+    }
+}
+
+impl ErrorFormat {
+    /// The handler reporting errors and warnings in this format
+    pub fn report_handler(self) -> Box<dyn ReportHandler> {
+        match self {
+            Self::Human => Box::new(MietteHandler::new()),
+            Self::Plain => Box::new(NarratableReportHandler::new()),
+            Self::Json => Box::new(JSONReportHandler::new()),
+        }
+    }
+}
+
+/// A diagnostic rendered by a report handler
+struct Rendered<'a>(&'a dyn ReportHandler, &'a dyn Diagnostic);
+
+impl std::fmt::Display for Rendered<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.debug(self.1, f)
+    }
+}
+
 impl CliArgs {
     pub fn exec(&self) -> Result<(), CliError> {
         match &self.command {
@@ -117,6 +288,7 @@ impl CliArgs {
                 entities,
                 mcp_tool_input,
                 mcp_tool_output,
+                output_format,
                 ..
             } => {
                 let config = get_config(config);
@@ -133,24 +305,119 @@ impl CliArgs {
                     .as_ref()
                     .map(Output::from_json_file)
                     .transpose()?;
-                let (request, entities) = request_generator.generate_request(
+                let response = request_generator.is_authorized(
                     principal,
                     resource,
-                    context.into_iter(),
+                    context,
                     entities,
                     &input,
                     output.as_ref(),
+                    &policies,
                 )?;
-                let authorizer = cedar_policy_core::authorizer::Authorizer::new();
-                match authorizer
-                    .is_authorized(request, &policies, &entities)
-                    .decision
-                {
-                    cedar_policy_core::authorizer::Decision::Allow => println!("ALLOW"),
-                    cedar_policy_core::authorizer::Decision::Deny => println!("DENY"),
-                };
+                output_response(&response, *output_format);
                 Ok(())
             }
+            Command::Validate {
+                schema_stub,
+                tool_descriptions,
+                policies,
+                config,
+                error_format,
+            } => {
+                let config = get_config(config);
+                let schema_stub = read_schema(schema_stub)?;
+                let tool_descriptions = ServerDescription::from_json_file(tool_descriptions)?;
+                let mut schema_generator = SchemaGenerator::new_with_config(schema_stub, config)?;
+                schema_generator.add_actions_from_server_description(&tool_descriptions)?;
+                let schema = ValidatorSchema::try_from(schema_generator.get_schema().clone())?;
+                let policies = read_policies(policies)?;
+                let (errors, warnings) = Validator::new(schema)
+                    .validate(&policies, ValidationMode::Strict)
+                    .into_errors_and_warnings();
+                let handler = error_format.report_handler();
+                for warning in warnings {
+                    eprintln!("{}", Rendered(handler.as_ref(), &warning));
+                }
+                let errors = errors.collect::<Vec<_>>();
+                if errors.is_empty() {
+                    println!("Validation passed");
+                    Ok(())
+                } else {
+                    Err(CliError::PolicyValidation(errors))
+                }
+            }
+            Command::Impact {
+                schema_stub,
+                old_tool_descriptions,
+                new_tool_descriptions,
+                policies,
+                output_format,
+                config,
+                ..
+            } => {
+                let config = get_config(config);
+                let schema_stub = read_schema(schema_stub)?;
+                let old_tool_descriptions =
+                    ServerDescription::from_json_file(old_tool_descriptions)?;
+                let new_tool_descriptions =
+                    ServerDescription::from_json_file(new_tool_descriptions)?;
+                let policies = read_policies(policies)?;
+                let report = PolicyImpactReport::new(
+                    schema_stub,
+                    config,
+                    &old_tool_descriptions,
+                    &new_tool_descriptions,
+                    &policies,
+                )?;
+                output_report(&report, *output_format);
+                Ok(())
+            }
+            Command::Proxy {
+                schema_stub,
+                tool_descriptions,
+                request,
+                policies,
+                entities,
+                authorize_responses,
+                config,
+                server_command,
+                ..
+            } => {
+                let config = get_config(config);
+                let schema_stub = read_schema(schema_stub)?;
+                let tool_descriptions = ServerDescription::from_json_file(tool_descriptions)?;
+                let mut schema_generator = SchemaGenerator::new_with_config(schema_stub, config)?;
+                schema_generator.add_actions_from_server_description(&tool_descriptions)?;
+                let request_generator = schema_generator.new_request_generator()?;
+                let policies = read_policies(policies)?;
+                let entities = entities
+                    .as_ref()
+                    .map(read_entities)
+                    .transpose()?
+                    .unwrap_or_else(Entities::new);
+                let (principal, resource, context) = read_request(request)?;
+                let proxy =
+                    AuthorizationProxy::new(request_generator, policies, principal, resource)
+                        .with_context(context)
+                        .with_entities(entities)
+                        .authorize_responses(*authorize_responses);
+
+                let (program, args) = server_command
+                    .split_first()
+                    .ok_or(CliError::MissingServerCommand)?;
+                let mut command = std::process::Command::new(program);
+                command.args(args);
+                let status = proxy.run_command(
+                    &mut command,
+                    BufReader::new(std::io::stdin()),
+                    std::io::stdout(),
+                )?;
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(CliError::McpServerExit(status))
+                }
+            }
         }
     }
 
@@ -158,6 +425,9 @@ impl CliArgs {
         match &self.command {
             Command::Generate { error_format, .. } => *error_format,
             Command::Authorize { error_format, .. } => *error_format,
+            Command::Validate { error_format, .. } => *error_format,
+            Command::Impact { error_format, .. } => *error_format,
+            Command::Proxy { error_format, .. } => *error_format,
         }
     }
 }
@@ -1,8 +1,17 @@
+mod entity_id;
 mod err;
 mod identifiers;
+mod impact;
+mod pattern;
 mod request;
 mod schema;
 
 pub use err::{RequestGeneratorError, SchemaGeneratorError};
-pub use request::{AuthorizationComponents, RequestGenerator};
+pub use impact::{AttributeChange, PolicyImpact, PolicyImpactReport};
+pub use request::{
+    AuthorizationComponents, AuthorizationResponse, AuthorizedTools, PolicyEvaluationError,
+    RequestGenerator,
+};
+
+pub use cedar_policy_core::authorizer::Decision;
 pub use schema::{SchemaGenerator, SchemaGeneratorConfig};
@@ -0,0 +1,243 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Deterministic EIDs for the entities generated from MCP values.
+
+use std::collections::HashMap;
+use std::hash::Hasher;
+
+use cedar_policy_core::ast::EntityType;
+use mcp_tools_sdk::data::{TypedValue, Value};
+use siphasher::sip128::{Hasher128, SipHasher13};
+use smol_str::{SmolStr, ToSmolStr};
+
+/// Returns the EID of the entity of type `ty` representing `val` as the hex encoded
+/// 128-bit SipHash-1-3 (with a zero key) of a canonical encoding of `ty` and `val`.
+///
+/// The entity type identifies where `val` occurs in the tool's input or output, so equal
+/// values at the same place of different requests are given the same EID.
+pub(super) fn content_hash(ty: &EntityType, val: &TypedValue) -> SmolStr {
+    let mut hasher = SipHasher13::new();
+    write_str(&mut hasher, &ty.to_string());
+    write_typed_value(&mut hasher, val);
+    format!("{:032x}", u128::from(hasher.finish128())).into()
+}
+
+/// Returns the EID of an object whose key property has the value `val`, if `val`
+/// is a string or an integer.
+pub(super) fn key_value(val: &TypedValue) -> Option<SmolStr> {
+    match val {
+        TypedValue::String(s) | TypedValue::Enum(s) => Some(s.clone()),
+        TypedValue::Integer(i) => Some(i.to_smolstr()),
+        TypedValue::Ref { val, .. } => key_value(val),
+        _ => None,
+    }
+}
+
+// Each value is encoded as a tag byte followed by its contents. Strings and
+// collections are prefixed with their length, so that the encoding is unambiguous.
+
+fn write_len(hasher: &mut SipHasher13, len: usize) {
+    hasher.write(&(len as u64).to_le_bytes());
+}
+
+fn write_str(hasher: &mut SipHasher13, s: &str) {
+    write_len(hasher, s.len());
+    hasher.write(s.as_bytes());
+}
+
+fn write_tagged_str(hasher: &mut SipHasher13, tag: u8, s: &str) {
+    hasher.write_u8(tag);
+    write_str(hasher, s);
+}
+
+/// Write the entries of `map` ordered by key
+fn write_map<T>(
+    hasher: &mut SipHasher13,
+    map: &HashMap<SmolStr, T>,
+    write_val: impl Fn(&mut SipHasher13, &T),
+) {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(key, _)| *key);
+    write_len(hasher, entries.len());
+    for (key, val) in entries {
+        write_str(hasher, key);
+        write_val(hasher, val);
+    }
+}
+
+fn write_typed_value(hasher: &mut SipHasher13, val: &TypedValue) {
+    match val {
+        TypedValue::Null => hasher.write_u8(0),
+        TypedValue::Bool(b) => {
+            hasher.write_u8(1);
+            hasher.write_u8(u8::from(*b));
+        }
+        TypedValue::Integer(i) => {
+            hasher.write_u8(2);
+            hasher.write(&i.to_le_bytes());
+        }
+        TypedValue::Float(f) => {
+            hasher.write_u8(3);
+            hasher.write(&f.to_bits().to_le_bytes());
+        }
+        TypedValue::Number(n) => write_tagged_str(hasher, 4, n.as_str()),
+        TypedValue::String(s) => write_tagged_str(hasher, 5, s),
+        TypedValue::Decimal(s) => write_tagged_str(hasher, 6, s),
+        TypedValue::Datetime(s) => write_tagged_str(hasher, 7, s),
+        TypedValue::Duration(s) => write_tagged_str(hasher, 8, s),
+        TypedValue::IpAddr(s) => write_tagged_str(hasher, 9, s),
+        TypedValue::Enum(s) => write_tagged_str(hasher, 10, s),
+        TypedValue::Array(vals) => write_typed_values(hasher, 11, vals),
+        TypedValue::Tuple(vals) => write_typed_values(hasher, 12, vals),
+        TypedValue::Union { index, tag, value } => {
+            hasher.write_u8(13);
+            write_len(hasher, *index);
+            match tag {
+                Some(tag) => write_tagged_str(hasher, 1, tag),
+                None => hasher.write_u8(0),
+            }
+            write_typed_value(hasher, value);
+        }
+        TypedValue::Object {
+            properties,
+            additional_properties,
+        } => {
+            hasher.write_u8(14);
+            write_map(hasher, properties, write_typed_value);
+            write_map(hasher, additional_properties, write_typed_value);
+        }
+        TypedValue::Ref { name, val } => {
+            write_tagged_str(hasher, 15, name);
+            write_typed_value(hasher, val);
+        }
+        TypedValue::Unknown(val) => {
+            hasher.write_u8(16);
+            write_value(hasher, val);
+        }
+    }
+}
+
+fn write_typed_values(hasher: &mut SipHasher13, tag: u8, vals: &[TypedValue]) {
+    hasher.write_u8(tag);
+    write_len(hasher, vals.len());
+    for val in vals {
+        write_typed_value(hasher, val);
+    }
+}
+
+fn write_value(hasher: &mut SipHasher13, val: &Value) {
+    match val {
+        Value::Null => hasher.write_u8(0),
+        Value::Bool(b) => {
+            hasher.write_u8(1);
+            hasher.write_u8(u8::from(*b));
+        }
+        Value::Number(n) => write_tagged_str(hasher, 2, n.as_str()),
+        Value::String(s) => write_tagged_str(hasher, 3, s),
+        Value::Array(vals) => {
+            hasher.write_u8(4);
+            write_len(hasher, vals.len());
+            for val in vals {
+                write_value(hasher, val);
+            }
+        }
+        Value::Map(map) => {
+            hasher.write_u8(5);
+            write_map(hasher, map, write_value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![expect(clippy::unwrap_used, reason = "test assertions")]
+    use super::*;
+
+    fn object(props: &[(&str, TypedValue)]) -> TypedValue {
+        TypedValue::Object {
+            properties: props
+                .iter()
+                .map(|(name, val)| ((*name).into(), val.clone()))
+                .collect(),
+            additional_properties: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_content_hash_is_canonical() {
+        let ty: EntityType = "Test::read_file::Input::options".parse().unwrap();
+        let val = object(&[
+            ("encoding", TypedValue::String("utf-8".into())),
+            ("limit", TypedValue::Integer(10)),
+        ]);
+        let hash = content_hash(&ty, &val);
+        assert_eq!(hash.len(), 32);
+        // Stable across releases
+        assert_eq!(hash, "917f127562cbceebaf35827ed1b384b2");
+
+        // The same, whatever the order of properties
+        let reordered = object(&[
+            ("limit", TypedValue::Integer(10)),
+            ("encoding", TypedValue::String("utf-8".into())),
+        ]);
+        assert_eq!(content_hash(&ty, &reordered), hash);
+
+        // Different for different values or types
+        let other = object(&[
+            ("encoding", TypedValue::String("utf-8".into())),
+            ("limit", TypedValue::Integer(11)),
+        ]);
+        assert_ne!(content_hash(&ty, &other), hash);
+        let other_ty: EntityType = "Test::write_file::Input::options".parse().unwrap();
+        assert_ne!(content_hash(&other_ty, &val), hash);
+        assert_ne!(
+            content_hash(&ty, &TypedValue::String("ab".into())),
+            content_hash(&ty, &TypedValue::Enum("ab".into()))
+        );
+        assert_ne!(
+            content_hash(
+                &ty,
+                &TypedValue::Array(vec![TypedValue::String("ab".into())])
+            ),
+            content_hash(
+                &ty,
+                &TypedValue::Array(vec![
+                    TypedValue::String("a".into()),
+                    TypedValue::String("b".into())
+                ])
+            )
+        );
+    }
+
+    #[test]
+    fn test_key_value() {
+        assert_eq!(
+            key_value(&TypedValue::String("c1".into())),
+            Some("c1".into())
+        );
+        assert_eq!(key_value(&TypedValue::Integer(42)), Some("42".into()));
+        assert_eq!(
+            key_value(&TypedValue::Ref {
+                name: "Id".into(),
+                val: Box::new(TypedValue::Enum("c2".into()))
+            }),
+            Some("c2".into())
+        );
+        assert_eq!(key_value(&TypedValue::Bool(true)), None);
+        assert_eq!(key_value(&object(&[])), None);
+    }
+}
@@ -38,6 +38,21 @@ pub struct EmptyEnum {
     name: String,
 }
 
+/// SchemaGenerator encountered a tool provided by two MCP servers
+#[derive(Debug, Clone)]
+pub struct ToolNameCollision {
+    tool: SmolStr,
+    server: SmolStr,
+    other_server: SmolStr,
+}
+
+/// SchemaGenerator encountered a tool argument that cannot identify the resource of the tool's requests
+#[derive(Debug, Clone)]
+pub struct InvalidResourceArgument {
+    tool: SmolStr,
+    argument: SmolStr,
+}
+
 /// SchemaGenerator encountered an error during generation
 #[derive(Debug, Error, Diagnostic)]
 pub enum SchemaGeneratorError {
@@ -115,6 +130,20 @@ pub enum SchemaGeneratorError {
         help("Server Descriptions cannot be merged. Consider pre-merging Server descriptions and using add_actions_from_server_description API.")
     )]
     ServerDescriptionMerge,
+    /// SchemaGenerator failed because a Server Description was added with the id of a previously added server
+    #[error("Server `{0}` has already been added.")]
+    #[diagnostic(
+        code(schema_generator::duplicate_server_id),
+        help("Ensure that each MCP Server Description is added with a distinct server id.")
+    )]
+    DuplicateServerId(SmolStr),
+    /// SchemaGenerator failed because two MCP servers provide a tool of the same name
+    #[error("Tool `{}` is provided by both server `{}` and server `{}`.", .0.tool, .0.server, .0.other_server)]
+    #[diagnostic(
+        code(schema_generator::tool_name_collision),
+        help("Each tool corresponds to a Cedar action, so tool names must be unique across MCP servers. Consider renaming one of the tools.")
+    )]
+    ToolNameCollision(ToolNameCollision),
     /// SchemaGenerator failed to parse a Cedar schema string
     #[error("Failed to parse Cedar schema: {0}")]
     #[diagnostic(
@@ -122,6 +151,41 @@ pub enum SchemaGeneratorError {
         help("Ensure the input is a valid .cedarschema string.")
     )]
     SchemaParseError(String),
+    /// SchemaGenerator failed because an `@mcp_resource_from` annotation of the schema stub is malformed
+    #[error("Malformed `@mcp_resource_from` annotation: `{0}`.")]
+    #[diagnostic(
+        code(schema_generator::malformed_resource_from),
+        help("Expected a comma separated list of `tool.argument` pairs (e.g., `@mcp_resource_from(\"read_file.path\")`), naming each tool at most once. The argument is the part of each pair after its last `.`.")
+    )]
+    MalformedResourceFrom(SmolStr),
+    /// SchemaGenerator failed because the argument identifying the resource of a tool's requests is invalid
+    #[error("Argument `{}` of tool `{}` cannot identify the resource of the tool's requests.", .0.argument, .0.tool)]
+    #[diagnostic(
+        code(schema_generator::invalid_resource_argument),
+        help("The argument named by `@mcp_resource_from` should be a required string, enum or integer input property of the tool.")
+    )]
+    InvalidResourceArgument(InvalidResourceArgument),
+    /// SchemaGenerator failed because an `@mcp_tools` or `@mcp_action_group` annotation of the schema stub is malformed
+    #[error("Malformed tool name pattern: `{0}`.")]
+    #[diagnostic(
+        code(schema_generator::malformed_tool_pattern),
+        help("Expected a `|` separated list of tool names, in which `*` matches any sequence of characters (e.g., `@mcp_tools(\"github_*|list_repos\")`).")
+    )]
+    MalformedToolPattern(SmolStr),
+    /// SchemaGenerator failed because no MCP principal type applies to a tool
+    #[error("No MCP Principal Entity type applies to tool `{0}`.")]
+    #[diagnostic(
+        code(schema_generator::no_principal_types_for_tool),
+        help("At least 1 Entity Type annotated with `@mcp_principal` should either have no `@mcp_tools` annotation or match the tool's name.")
+    )]
+    NoPrincipalTypesForTool(SmolStr),
+    /// SchemaGenerator failed because no MCP resource type applies to a tool
+    #[error("No MCP Resource Entity type applies to tool `{0}`.")]
+    #[diagnostic(
+        code(schema_generator::no_resource_types_for_tool),
+        help("At least 1 Entity Type annotated with `@mcp_resource` should either have no `@mcp_tools` annotation or match the tool's name.")
+    )]
+    NoResourceTypesForTool(SmolStr),
 }
 
 impl SchemaGeneratorError {
@@ -142,6 +206,26 @@ impl SchemaGeneratorError {
     pub(crate) fn empty_enum_choice(name: String) -> Self {
         Self::EmptyEnumChoice(EmptyEnum { name })
     }
+
+    /// Construct a `SchemaGeneratorError` representing that the tool `tool` of `server`
+    /// is also provided by `other_server`
+    pub(crate) fn tool_name_collision(
+        tool: SmolStr,
+        server: SmolStr,
+        other_server: SmolStr,
+    ) -> Self {
+        Self::ToolNameCollision(ToolNameCollision {
+            tool,
+            server,
+            other_server,
+        })
+    }
+
+    /// Construct a `SchemaGeneratorError` representing that the argument `argument` of `tool`
+    /// cannot identify the resource of the tool's requests
+    pub(crate) fn invalid_resource_argument(tool: SmolStr, argument: SmolStr) -> Self {
+        Self::InvalidResourceArgument(InvalidResourceArgument { tool, argument })
+    }
 }
 
 /// RequestGenerator encountered an error during generation
@@ -180,4 +264,55 @@ pub enum RequestGeneratorError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     DuplicateEntities(#[from] cedar_policy_core::entities::err::EntitiesError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ContextSerialization(#[from] cedar_policy_core::entities::json::err::JsonSerializationError),
+    /// RequestGenerator failed to parse the Cedar policies to authorize a request against
+    #[error("Failed to parse Cedar policies: {0}")]
+    #[diagnostic(
+        code = "request_generator::malformed_policies",
+        help = "Ensure the policies are written in valid Cedar policy syntax"
+    )]
+    MalformedPolicies(cedar_policy_core::parser::err::ParseErrors),
+    /// RequestGenerator failed to parse the Cedar entities to authorize a request with
+    #[error("Failed to parse Cedar entities: {0}")]
+    #[diagnostic(
+        code = "request_generator::malformed_entities",
+        help = "Ensure the entities are a JSON array in Cedar's entity JSON format"
+    )]
+    MalformedEntities(cedar_policy_core::entities::err::EntitiesError),
+    /// RequestGenerator failed to parse a Cedar entity UID
+    #[error("Failed to parse Cedar entity UID: {0}")]
+    #[diagnostic(
+        code = "request_generator::malformed_entity_uid",
+        help = "Ensure the entity UID is of the form `Namespace::Type::\"id\"`"
+    )]
+    MalformedEntityUid(cedar_policy_core::parser::err::ParseErrors),
+    /// RequestGenerator failed to parse the Cedar context to generate a request with
+    #[error("Failed to parse Cedar context: {0}")]
+    #[diagnostic(
+        code = "request_generator::malformed_context",
+        help = "Ensure the context is a JSON object in Cedar's JSON format"
+    )]
+    MalformedContext(cedar_policy_core::entities::json::ContextJsonDeserializationError),
+    /// RequestGenerator found no resource template matching the URI of a `resources/read` request
+    #[error("No resource template matches the URI `{0}`")]
+    #[diagnostic(
+        code = "request_generator::no_matching_resource_template",
+        help = "Ensure the resource templates of the MCP server were added to the SchemaGenerator"
+    )]
+    NoMatchingResourceTemplate(SmolStr),
+    /// RequestGenerator could not identify the resource of a tool's request from the argument
+    /// named by the `@mcp_resource_from` annotation
+    #[error("Argument `{argument}` of the call to tool `{tool}` does not identify a resource")]
+    #[diagnostic(
+        code = "request_generator::missing_resource_argument",
+        help = "The argument must be a string, an integer or an enum variant"
+    )]
+    MissingResourceArgument {
+        /// The name of the called tool
+        tool: SmolStr,
+        /// The name of the argument identifying the resource
+        argument: SmolStr,
+    },
 }
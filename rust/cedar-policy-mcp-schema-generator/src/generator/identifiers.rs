@@ -28,11 +28,32 @@ pub(super) static MCP_PRINCIPAL: LazyLock<AnyId> =
 pub(super) static MCP_RESOURCE: LazyLock<AnyId> = LazyLock::new(|| "mcp_resource".parse().unwrap());
 pub(super) static MCP_CONTEXT: LazyLock<AnyId> = LazyLock::new(|| "mcp_context".parse().unwrap());
 pub(super) static MCP_ACTION: LazyLock<AnyId> = LazyLock::new(|| "mcp_action".parse().unwrap());
+pub(super) static MCP_RESOURCE_FROM: LazyLock<AnyId> =
+    LazyLock::new(|| "mcp_resource_from".parse().unwrap());
+pub(super) static MCP_TOOLS: LazyLock<AnyId> = LazyLock::new(|| "mcp_tools".parse().unwrap());
+pub(super) static MCP_ACTION_GROUP: LazyLock<AnyId> =
+    LazyLock::new(|| "mcp_action_group".parse().unwrap());
+
+// Annotations added to generated schemas
+pub(super) static MCP_ENUM: LazyLock<AnyId> = LazyLock::new(|| "mcp_enum".parse().unwrap());
+pub(super) static DOC: LazyLock<AnyId> = LazyLock::new(|| "doc".parse().unwrap());
 
 // Namespace names
 pub(super) static INPUT_NAME: LazyLock<Name> = LazyLock::new(|| "Input".parse().unwrap());
 pub(super) static OUTPUT_NAME: LazyLock<Name> = LazyLock::new(|| "Output".parse().unwrap());
 
+// Action groups of tools with the corresponding MCP tool annotations
+pub(super) const READ_ONLY_TOOLS: &str = "readOnlyTools";
+pub(super) const DESTRUCTIVE_TOOLS: &str = "destructiveTools";
+pub(super) const IDEMPOTENT_TOOLS: &str = "idempotentTools";
+pub(super) const OPEN_WORLD_TOOLS: &str = "openWorldTools";
+pub(super) const ANNOTATION_ACTION_GROUPS: [&str; 4] = [
+    READ_ONLY_TOOLS,
+    DESTRUCTIVE_TOOLS,
+    IDEMPOTENT_TOOLS,
+    OPEN_WORLD_TOOLS,
+];
+
 // Cedar built-in and extension types
 pub(super) static BOOL_TYPE: LazyLock<RawName> = LazyLock::new(|| "Bool".parse().unwrap());
 pub(super) static LONG_TYPE: LazyLock<RawName> = LazyLock::new(|| "Long".parse().unwrap());
@@ -69,6 +90,11 @@ mod test {
         let _ = *MCP_RESOURCE;
         let _ = *MCP_CONTEXT;
         let _ = *MCP_ACTION;
+        let _ = *MCP_RESOURCE_FROM;
+        let _ = *MCP_TOOLS;
+        let _ = *MCP_ACTION_GROUP;
+        let _ = *MCP_ENUM;
+        let _ = *DOC;
         let _ = *INPUT_NAME;
         let _ = *OUTPUT_NAME;
         let _ = *BOOL_TYPE;
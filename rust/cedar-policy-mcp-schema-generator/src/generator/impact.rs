@@ -0,0 +1,540 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use cedar_policy_core::ast::{ActionConstraint, EntityUID, Expr, ExprKind, Policy, PolicySet, Var};
+use cedar_policy_core::validator::json_schema::Fragment;
+use cedar_policy_core::validator::types::{EntityKind, Type};
+use cedar_policy_core::validator::{
+    RawName, ValidationMode, Validator, ValidatorEntityType, ValidatorEntityTypeKind,
+    ValidatorSchema,
+};
+use mcp_tools_sdk::description::ServerDescription;
+
+use crate::{SchemaGenerator, SchemaGeneratorConfig, SchemaGeneratorError};
+
+/// A change to an attribute of the context of an action (i.e., of an MCP tool's input or output)
+/// between the schemas generated from two versions of an MCP server's tool descriptions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributeChange {
+    /// The name of the action (i.e., of the MCP tool).
+    pub action: String,
+    /// The path of the attribute within the action's context, e.g., `input.path`.
+    pub path: String,
+    /// The type of the attribute in the old schema, `None` if the attribute was added.
+    pub old_type: Option<String>,
+    /// The type of the attribute in the new schema, `None` if the attribute was removed.
+    pub new_type: Option<String>,
+}
+
+/// How a policy is impacted by a change of an MCP server's tool descriptions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyImpact {
+    /// The ID of the policy.
+    pub policy_id: String,
+    /// The errors validating the policy against the new schema.
+    /// Only reported for policies that validate against the old schema.
+    pub validation_errors: Vec<String>,
+    /// The removed attributes the policy references (for an action it applies to).
+    pub removed_attributes: Vec<AttributeChange>,
+    /// The retyped attributes the policy references (for an action it applies to).
+    pub retyped_attributes: Vec<AttributeChange>,
+}
+
+/// A report of the changes between the Cedar schemas generated from an old and a new version of
+/// an MCP server's tool descriptions (e.g., when the MCP server is upgraded), and of the policies
+/// these changes impact.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyImpactReport {
+    /// The actions (i.e., MCP tools) only in the new schema.
+    pub added_actions: Vec<String>,
+    /// The actions (i.e., MCP tools) only in the old schema.
+    pub removed_actions: Vec<String>,
+    /// The context attributes of actions in both schemas that are only in the new schema.
+    pub added_attributes: Vec<AttributeChange>,
+    /// The context attributes of actions in both schemas that are only in the old schema.
+    pub removed_attributes: Vec<AttributeChange>,
+    /// The context attributes of actions in both schemas whose type (or optionality) changed.
+    pub retyped_attributes: Vec<AttributeChange>,
+    /// The impacted policies, in the order of the policy set.
+    pub policies: Vec<PolicyImpact>,
+}
+
+impl PolicyImpactReport {
+    /// Generate the schemas for `old_tools` and `new_tools` from `schema_stub` using `config`,
+    /// diff their actions and context types, and report the policies of `policies` that no
+    /// longer validate against the new schema or that reference removed or retyped attributes.
+    pub fn new(
+        schema_stub: Fragment<RawName>,
+        config: SchemaGeneratorConfig,
+        old_tools: &ServerDescription,
+        new_tools: &ServerDescription,
+        policies: &PolicySet,
+    ) -> Result<Self, SchemaGeneratorError> {
+        let old_schema = generate_schema(schema_stub.clone(), config.clone(), old_tools)?;
+        let new_schema = generate_schema(schema_stub, config, new_tools)?;
+
+        let old_actions = actions(&old_schema);
+        let new_actions = actions(&new_schema);
+        let added_actions = new_actions
+            .keys()
+            .filter(|action| !old_actions.contains_key(*action))
+            .cloned()
+            .collect();
+        let removed_actions = old_actions
+            .keys()
+            .filter(|action| !new_actions.contains_key(*action))
+            .cloned()
+            .collect();
+
+        let mut added_attributes = Vec::new();
+        let mut removed_attributes = Vec::new();
+        let mut retyped_attributes = Vec::new();
+        for (action, (_, old_attrs)) in &old_actions {
+            let Some((_, new_attrs)) = new_actions.get(action) else {
+                continue;
+            };
+            let change = |path: &String| AttributeChange {
+                action: action.clone(),
+                path: path.clone(),
+                old_type: old_attrs.get(path).cloned(),
+                new_type: new_attrs.get(path).cloned(),
+            };
+            for path in new_attrs.keys() {
+                // Only report the outermost added attribute of an added record or entity
+                if !old_attrs.contains_key(path) && !has_parent_in(path, new_attrs, old_attrs) {
+                    added_attributes.push(change(path));
+                }
+            }
+            for (path, old_type) in old_attrs {
+                match new_attrs.get(path) {
+                    None if !has_parent_in(path, old_attrs, new_attrs) => {
+                        removed_attributes.push(change(path))
+                    }
+                    Some(new_type) if new_type != old_type => retyped_attributes.push(change(path)),
+                    _ => (),
+                }
+            }
+        }
+
+        let old_validator = Validator::new(old_schema.clone());
+        let new_validator = Validator::new(new_schema);
+        let mut impacted = Vec::new();
+        for policy in policies.policies() {
+            let single = PolicySet::singleton(policy.clone());
+            let validation_errors = if old_validator
+                .validate(&single, ValidationMode::Strict)
+                .validation_passed()
+            {
+                new_validator
+                    .validate(&single, ValidationMode::Strict)
+                    .validation_errors()
+                    .map(ToString::to_string)
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            let paths = context_paths(&policy.condition());
+            let referenced = |change: &&AttributeChange| {
+                old_actions
+                    .get(&change.action)
+                    .is_some_and(|(action, _)| applies_to(policy, action, &old_schema))
+                    && paths.iter().any(|path| {
+                        path == &change.path
+                            || path
+                                .strip_prefix(change.path.as_str())
+                                .is_some_and(|rest| rest.starts_with('.'))
+                    })
+            };
+            let impact = PolicyImpact {
+                policy_id: policy.id().to_string(),
+                validation_errors,
+                removed_attributes: removed_attributes
+                    .iter()
+                    .filter(referenced)
+                    .cloned()
+                    .collect(),
+                retyped_attributes: retyped_attributes
+                    .iter()
+                    .filter(referenced)
+                    .cloned()
+                    .collect(),
+            };
+            if !impact.validation_errors.is_empty()
+                || !impact.removed_attributes.is_empty()
+                || !impact.retyped_attributes.is_empty()
+            {
+                impacted.push(impact);
+            }
+        }
+
+        Ok(Self {
+            added_actions,
+            removed_actions,
+            added_attributes,
+            removed_attributes,
+            retyped_attributes,
+            policies: impacted,
+        })
+    }
+
+    /// Whether the schemas generated from the old and new tool descriptions differ in their
+    /// actions or context types.
+    pub fn has_changes(&self) -> bool {
+        !self.added_actions.is_empty()
+            || !self.removed_actions.is_empty()
+            || !self.added_attributes.is_empty()
+            || !self.removed_attributes.is_empty()
+            || !self.retyped_attributes.is_empty()
+    }
+}
+
+fn generate_schema(
+    schema_stub: Fragment<RawName>,
+    config: SchemaGeneratorConfig,
+    tools: &ServerDescription,
+) -> Result<ValidatorSchema, SchemaGeneratorError> {
+    let mut generator = SchemaGenerator::new_with_config(schema_stub, config)?;
+    generator.add_actions_from_server_description(tools)?;
+    Ok(ValidatorSchema::try_from(generator.get_schema().clone())?)
+}
+
+/// The type of each context attribute of an action, keyed by the attribute's path
+type ContextAttributes = BTreeMap<String, String>;
+
+/// The actions of `schema` along with the types of their context attributes, keyed by action name
+fn actions(schema: &ValidatorSchema) -> BTreeMap<String, (EntityUID, ContextAttributes)> {
+    schema
+        .action_ids()
+        .map(|action| {
+            let mut attrs = ContextAttributes::new();
+            collect_attributes(
+                schema,
+                action.context_type(),
+                "",
+                &mut Vec::new(),
+                &mut attrs,
+            );
+            (
+                action.name().eid().as_ref().to_string(),
+                (action.name().clone(), attrs),
+            )
+        })
+        .collect()
+}
+
+/// Record the type of each attribute of `ty` (if a record or entity type) under `prefix`,
+/// recursing into the attributes of records and entities. `visiting` holds the entity types
+/// being visited, to stop at recursive entity types.
+fn collect_attributes<'a>(
+    schema: &'a ValidatorSchema,
+    ty: &Type,
+    prefix: &str,
+    visiting: &mut Vec<&'a ValidatorEntityType>,
+    attrs: &mut ContextAttributes,
+) {
+    let (attributes, entity) = match ty {
+        Type::Record { attrs, .. } => (attrs.clone(), None),
+        Type::Entity(EntityKind::Entity(lub)) => {
+            let Some(entity) = lub
+                .get_single_entity()
+                .and_then(|name| schema.get_entity_type(name))
+            else {
+                return;
+            };
+            if visiting
+                .iter()
+                .any(|visited| visited.name() == entity.name())
+            {
+                return;
+            }
+            (entity.attributes().clone(), Some(entity))
+        }
+        _ => return,
+    };
+    visiting.extend(entity);
+    for (name, attr) in attributes.iter() {
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        let mut summary = type_summary(schema, &attr.attr_type);
+        if !attr.is_required {
+            summary.push_str(" (optional)");
+        }
+        attrs.insert(path.clone(), summary);
+        collect_attributes(schema, &attr.attr_type, &path, visiting, attrs);
+    }
+    if entity.is_some() {
+        visiting.pop();
+    }
+}
+
+/// A description of `ty` which does not depend on the attributes of records and entities,
+/// as these are compared separately
+fn type_summary(schema: &ValidatorSchema, ty: &Type) -> String {
+    match ty {
+        Type::Record { .. } => "Record".to_string(),
+        Type::Entity(EntityKind::Entity(lub)) => match lub
+            .get_single_entity()
+            .and_then(|name| schema.get_entity_type(name))
+        {
+            Some(entity) => match &entity.kind {
+                ValidatorEntityTypeKind::Enum(choices) => format!(
+                    "{} (enum of {})",
+                    entity.name(),
+                    choices
+                        .iter()
+                        .map(|choice| format!("\"{}\"", choice.escaped()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                ValidatorEntityTypeKind::Standard(_) => entity.name().to_string(),
+            },
+            None => ty.to_string(),
+        },
+        _ => ty.to_string(),
+    }
+}
+
+/// Whether a parent of the attribute at `path` of `attrs` is missing from `other`
+fn has_parent_in(path: &str, attrs: &ContextAttributes, other: &ContextAttributes) -> bool {
+    let mut parent = path;
+    while let Some((prefix, _)) = parent.rsplit_once('.') {
+        if attrs.contains_key(prefix) && !other.contains_key(prefix) {
+            return true;
+        }
+        parent = prefix;
+    }
+    false
+}
+
+/// Whether `policy` may apply to requests for `action` in `schema`
+fn applies_to(policy: &Policy, action: &EntityUID, schema: &ValidatorSchema) -> bool {
+    match policy.action_constraint() {
+        ActionConstraint::Any => true,
+        ActionConstraint::Eq(euid) => euid.as_ref() == action,
+        ActionConstraint::In(euids) => euids.iter().any(|group| {
+            group.as_ref() == action
+                || schema
+                    .get_action_id(group)
+                    .is_some_and(|group| group.descendants().any(|member| member == action))
+        }),
+    }
+}
+
+/// The paths of the context attributes accessed (or tested) in `expr`, e.g., `input.path`
+/// for `context.input.path`
+fn context_paths(expr: &Expr) -> BTreeSet<String> {
+    expr.subexpressions()
+        .filter_map(|expr| match expr.expr_kind() {
+            ExprKind::GetAttr { expr, attr } | ExprKind::HasAttr { expr, attr } => {
+                context_path(expr).map(|path| join_path(&path, attr))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The path of the context attribute `expr` evaluates to, if any
+fn context_path(expr: &Expr) -> Option<String> {
+    match expr.expr_kind() {
+        ExprKind::Var(Var::Context) => Some(String::new()),
+        ExprKind::GetAttr { expr, attr } => context_path(expr).map(|path| join_path(&path, attr)),
+        _ => None,
+    }
+}
+
+fn join_path(path: &str, attr: &str) -> String {
+    if path.is_empty() {
+        attr.to_string()
+    } else {
+        format!("{path}.{attr}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![expect(
+        clippy::expect_used,
+        clippy::indexing_slicing,
+        clippy::panic,
+        reason = "test assertions"
+    )]
+    use super::*;
+    use cedar_policy_core::extensions::Extensions;
+
+    fn schema_stub() -> Fragment<RawName> {
+        let schema = r#"namespace Test {
+    @mcp_principal("User")
+    entity user;
+
+    @mcp_resource("McpServer")
+    entity resource;
+}"#;
+        Fragment::from_cedarschema_str(schema, Extensions::all_available())
+            .expect("Failed to parse schema")
+            .0
+    }
+
+    fn tools(json: &str) -> ServerDescription {
+        ServerDescription::from_json_str(json).expect("Failed to parse tool descriptions")
+    }
+
+    const OLD_TOOLS: &str = r#"[
+    {
+        "name": "read_file",
+        "inputSchema": {
+            "properties": {
+                "path": { "type": "string" },
+                "encoding": { "type": "string" },
+                "options": { "type": "object", "properties": { "follow": { "type": "boolean" } } }
+            },
+            "required": ["path"]
+        }
+    },
+    {
+        "name": "delete_file",
+        "inputSchema": { "properties": { "path": { "type": "string" } }, "required": ["path"] }
+    }
+]"#;
+
+    const NEW_TOOLS: &str = r#"[
+    {
+        "name": "read_file",
+        "inputSchema": {
+            "properties": {
+                "path": { "type": "string" },
+                "encoding": { "type": "integer" },
+                "offset": { "type": "integer" }
+            },
+            "required": ["path"]
+        }
+    },
+    {
+        "name": "write_file",
+        "inputSchema": { "properties": { "path": { "type": "string" } }, "required": ["path"] }
+    }
+]"#;
+
+    fn report(policies: &str) -> PolicyImpactReport {
+        let policies =
+            cedar_policy_core::parser::parse_policyset(policies).expect("Failed to parse policies");
+        PolicyImpactReport::new(
+            schema_stub(),
+            SchemaGeneratorConfig::default(),
+            &tools(OLD_TOOLS),
+            &tools(NEW_TOOLS),
+            &policies,
+        )
+        .expect("Failed to generate report")
+    }
+
+    #[test]
+    fn test_schema_changes() {
+        let report = report("");
+        assert_eq!(report.added_actions, vec!["write_file"]);
+        assert_eq!(report.removed_actions, vec!["delete_file"]);
+        assert_eq!(
+            report.added_attributes,
+            vec![AttributeChange {
+                action: "read_file".into(),
+                path: "input.offset".into(),
+                old_type: None,
+                new_type: Some("Long (optional)".into()),
+            }]
+        );
+        // Attributes of removed objects are not reported separately
+        assert_eq!(
+            report.removed_attributes,
+            vec![AttributeChange {
+                action: "read_file".into(),
+                path: "input.options".into(),
+                old_type: Some("Test::read_file::Input::options (optional)".into()),
+                new_type: None,
+            }]
+        );
+        assert_eq!(
+            report.retyped_attributes,
+            vec![AttributeChange {
+                action: "read_file".into(),
+                path: "input.encoding".into(),
+                old_type: Some("String (optional)".into()),
+                new_type: Some("Long (optional)".into()),
+            }]
+        );
+        assert!(report.has_changes());
+        assert!(report.policies.is_empty());
+    }
+
+    #[test]
+    fn test_impacted_policies() {
+        let report = report(
+            r#"
+permit(principal, action == Test::Action::"read_file", resource)
+when { context.input.path like "/public/*" };
+permit(principal, action == Test::Action::"read_file", resource)
+when { context.input has encoding && context.input.encoding == "utf-8" };
+permit(principal, action, resource)
+when { context.input has options && context.input.options has follow && context.input.options.follow };
+forbid(principal, action == Test::Action::"delete_file", resource);
+"#,
+        );
+        let ids = report
+            .policies
+            .iter()
+            .map(|policy| policy.policy_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["policy1", "policy2", "policy3"]);
+
+        let [encoding, options, delete] = report.policies.as_slice() else {
+            panic!("Expected three impacted policies");
+        };
+        assert!(!encoding.validation_errors.is_empty());
+        assert!(encoding.removed_attributes.is_empty());
+        assert_eq!(encoding.retyped_attributes.len(), 1);
+        assert_eq!(encoding.retyped_attributes[0].path, "input.encoding");
+
+        assert_eq!(options.removed_attributes.len(), 1);
+        assert_eq!(options.removed_attributes[0].path, "input.options");
+        assert!(options.retyped_attributes.is_empty());
+
+        // The removed action no longer validates
+        assert!(!delete.validation_errors.is_empty());
+        assert!(delete.removed_attributes.is_empty());
+    }
+
+    #[test]
+    fn test_unchanged_tools() {
+        let policies = cedar_policy_core::parser::parse_policyset(
+            r#"permit(principal, action, resource) when { context.input.path == "/" };"#,
+        )
+        .expect("Failed to parse policies");
+        let report = PolicyImpactReport::new(
+            schema_stub(),
+            SchemaGeneratorConfig::default(),
+            &tools(OLD_TOOLS),
+            &tools(OLD_TOOLS),
+            &policies,
+        )
+        .expect("Failed to generate report");
+        assert!(!report.has_changes());
+        assert!(report.policies.is_empty());
+    }
+}
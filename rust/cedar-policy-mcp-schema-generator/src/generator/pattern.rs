@@ -0,0 +1,103 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Patterns matching the names of MCP tools in schema stub annotations.
+
+use smol_str::SmolStr;
+
+/// A `|` separated list of tool name globs, e.g., `fs_*|read_file`, in which `*`
+/// matches any (possibly empty) sequence of characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ToolPattern {
+    globs: Vec<SmolStr>,
+}
+
+impl ToolPattern {
+    /// Parse a tool pattern. Returns `None` if one of its globs is empty.
+    pub(super) fn parse(pattern: &str) -> Option<Self> {
+        let globs = pattern
+            .split('|')
+            .map(|glob| SmolStr::from(glob.trim()))
+            .collect::<Vec<_>>();
+        if globs.iter().any(SmolStr::is_empty) {
+            return None;
+        }
+        Some(Self { globs })
+    }
+
+    /// Does one of the pattern's globs match the whole of `name`
+    pub(super) fn matches(&self, name: &str) -> bool {
+        self.globs.iter().any(|glob| glob_matches(glob, name))
+    }
+}
+
+fn glob_matches(glob: &str, name: &str) -> bool {
+    let mut parts = glob.split('*');
+    #[expect(clippy::unwrap_used, reason = "Split always yields a first part.")]
+    let first = parts.next().unwrap();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard
+        return rest.is_empty();
+    };
+    // Matching each part at its first occurrence leaves the most room for the next ones
+    for part in middle {
+        match rest.split_once(part) {
+            Some((_, after)) => rest = after,
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod test {
+    #![expect(clippy::unwrap_used, reason = "test assertions")]
+    use super::*;
+
+    #[test]
+    fn test_tool_pattern() {
+        let pattern = ToolPattern::parse("fs_* | read_file|*_repo*").unwrap();
+        for name in [
+            "fs_",
+            "fs_list",
+            "read_file",
+            "get_repo",
+            "get_repos",
+            "_repo",
+        ] {
+            assert!(pattern.matches(name), "{name} should match");
+        }
+        for name in ["read_files", "my_read_file", "fs", "repo", "write_file"] {
+            assert!(!pattern.matches(name), "{name} should not match");
+        }
+
+        let pattern = ToolPattern::parse("a*b*a").unwrap();
+        assert!(pattern.matches("aba"));
+        assert!(pattern.matches("abba"));
+        assert!(pattern.matches("abab_a"));
+        assert!(!pattern.matches("ab"));
+        assert!(!pattern.matches("aab"));
+
+        assert!(ToolPattern::parse("*").unwrap().matches(""));
+        assert_eq!(ToolPattern::parse(""), None);
+        assert_eq!(ToolPattern::parse("a||b"), None);
+        assert_eq!(ToolPattern::parse("a| "), None);
+    }
+}
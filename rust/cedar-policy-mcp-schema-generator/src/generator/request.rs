@@ -14,26 +14,35 @@
  * limitations under the License.
  */
 
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use cedar_policy_core::ast::{
-    Context, Eid, Entity, EntityType, EntityUID, InternalName, Name, Request, RestrictedExpr,
-    UnreservedId,
+    Context, Eid, Entity, EntityType, EntityUID, EntityUIDEntry, InternalName, Name, PolicySet,
+    Request, RestrictedExpr, Unknown, UnreservedId,
 };
-use cedar_policy_core::entities::Entities;
+use cedar_policy_core::authorizer::{AuthorizationError, Authorizer, Decision};
+use cedar_policy_core::entities::{Dereference, Entities};
 use cedar_policy_core::parser::err::ParseErrors;
 use cedar_policy_core::validator::ValidatorSchema;
 
 use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
 
+use super::entity_id;
 use super::identifiers;
-use super::schema::{DeduplicatedEntityType, EntityTypeFingerprint};
+use super::schema::{
+    redaction_action_name, union_choice_names, DeduplicatedEntityType, EntityTypeFingerprint,
+    ResourceArgument, ServerTools,
+};
 use crate::{RequestGeneratorError, SchemaGeneratorConfig};
 
-use mcp_tools_sdk::data::{Input, Output, TypedValue};
-use mcp_tools_sdk::description::{PropertyTypeDef, ServerDescription};
+use mcp_tools_sdk::data::{Input, Output, PromptGet, ResourceRead, TypedInput, TypedValue};
+use mcp_tools_sdk::description::{
+    PromptList, PropertyTypeDef, ResourceTemplateDescription, ServerDescription, ToolDescription,
+};
 use smol_str::{SmolStr, ToSmolStr};
+#[cfg(feature = "uuid")]
 use uuid::Uuid;
 
 /// Authorization request components as JSON-serializable strings, ready for
@@ -41,7 +50,8 @@ use uuid::Uuid;
 ///
 /// All entity UIDs are formatted as Cedar EntityUID strings
 /// (e.g., `MyServer::User::"alice"`). Entities are serialized as a JSON
-/// string (array of entity objects).
+/// string (array of entity objects), and the context as a JSON string
+/// (a record in Cedar's JSON format, using `__entity` and `__extn` escapes).
 #[derive(Clone, Debug)]
 pub struct AuthorizationComponents {
     /// The principal EntityUID as a Cedar string.
@@ -52,6 +62,48 @@ pub struct AuthorizationComponents {
     pub resource: String,
     /// The entities serialized as a JSON array string.
     pub entities_json: String,
+    /// The context serialized as a JSON object string.
+    pub context_json: String,
+}
+
+/// The tools of an MCP server that a principal may be authorized to call
+/// (see [`RequestGenerator::authorized_tools`]).
+#[derive(Clone, Debug)]
+pub struct AuthorizedTools {
+    /// The id the server was added with, if any
+    /// (see `SchemaGenerator::add_actions_from_server_description_with_id`).
+    pub server_id: Option<SmolStr>,
+    /// The kept tools of the server, along with all of its type definitions.
+    pub tools: ServerDescription,
+}
+
+/// The result of authorizing an MCP tool call with [`RequestGenerator::is_authorized`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthorizationResponse {
+    /// The authorization decision.
+    pub decision: Decision,
+    /// The IDs of the policies that determined the decision, in sorted order.
+    /// Empty if no policy applied to the request.
+    pub determining_policies: Vec<String>,
+    /// The errors encountered while evaluating policies. Policies that produce
+    /// an error are ignored when making the decision.
+    pub errors: Vec<PolicyEvaluationError>,
+}
+
+impl AuthorizationResponse {
+    /// Whether the tool call is allowed.
+    pub fn is_allowed(&self) -> bool {
+        self.decision == Decision::Allow
+    }
+}
+
+/// An error encountered while evaluating a policy during authorization.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PolicyEvaluationError {
+    /// The ID of the policy that produced the error.
+    pub policy_id: String,
+    /// The error message.
+    pub message: String,
 }
 
 #[derive(Clone, Debug)]
@@ -60,7 +112,14 @@ pub struct AuthorizationComponents {
 /// `SchemaGenerator`.
 pub struct RequestGenerator {
     config: SchemaGeneratorConfig,
-    tools: ServerDescription,
+    /// The MCP servers whose tools were added to the `SchemaGenerator`. Never empty.
+    servers: Vec<ServerTools>,
+    /// The MCP resource templates whose read actions were added to the `SchemaGenerator`.
+    resource_templates: Vec<ResourceTemplateDescription>,
+    /// The MCP prompts whose actions were added to the `SchemaGenerator`.
+    prompts: PromptList,
+    /// The arguments identifying the resource of each tool's requests, by tool name.
+    resource_arguments: HashMap<SmolStr, ResourceArgument>,
     root_namespace: Option<Name>,
     schema: ValidatorSchema,
     /// Resolved deduplication decisions from the schema generator.
@@ -103,17 +162,105 @@ impl TypeDefsInfo {
     }
 }
 
+/// The redaction of the output of a tool call (see `RequestGenerator::redact_output`)
+struct Redaction<'a> {
+    generator: &'a RequestGenerator,
+    tool: &'a str,
+    /// The request generated for the tool call, whose principal, resource and context are
+    /// shared by the requests for redaction actions
+    request: Request,
+    entities: &'a Entities,
+    policies: &'a PolicySet,
+    authorizer: Authorizer,
+}
+
+impl Redaction<'_> {
+    /// Remove the value at `path` of `output` if it is not disclosed. Otherwise, redact the
+    /// nested properties of its typed value `value`.
+    fn redact<'v>(&self, output: &mut Output, path: &mut Vec<&'v str>, value: &'v TypedValue) {
+        if !self.is_disclosed(path) {
+            output.remove_result_at(path);
+            return;
+        }
+        let mut value = value;
+        while let TypedValue::Ref { val, .. } = value {
+            value = val;
+        }
+        if let TypedValue::Object { properties, .. } = value {
+            for (name, value) in properties {
+                path.push(name);
+                self.redact(output, path, value);
+                path.pop();
+            }
+        }
+    }
+
+    /// Returns `true` if the value at `path` has no redaction action or the request for its
+    /// redaction action is allowed
+    fn is_disclosed(&self, path: &[&str]) -> bool {
+        let action = EntityUID::from_components(
+            identifiers::ACTION.qualify_with(self.generator.root_namespace.as_ref()),
+            Eid::new(redaction_action_name(self.tool, path)),
+            None,
+        );
+        if self.generator.schema.get_action_id(&action).is_none() {
+            return true;
+        }
+        let request = Request::new_unchecked(
+            self.request.principal().clone(),
+            EntityUIDEntry::known(action, None),
+            self.request.resource().clone(),
+            self.request.context().cloned(),
+        );
+        self.authorizer
+            .is_authorized(request, self.policies, self.entities)
+            .decision
+            == Decision::Allow
+    }
+}
+
+/// The EUIDs of the entities named after the value of their key property (see
+/// `SchemaGeneratorConfig::entity_id_key`) while converting MCP values to Cedar.
+#[derive(Debug, Default)]
+struct KeyedEntityIds {
+    /// The EUIDs shared by distinct objects, whose entities are named after their content instead
+    shared: HashSet<EntityUID>,
+    /// The content hashes of the objects named after each EUID
+    named: RefCell<HashMap<EntityUID, HashSet<SmolStr>>>,
+}
+
+impl KeyedEntityIds {
+    /// The EUIDs found to be shared by distinct objects during the conversion
+    fn newly_shared(&self) -> impl Iterator<Item = EntityUID> + '_ {
+        self.named
+            .take()
+            .into_iter()
+            .filter(|(_, hashes)| hashes.len() > 1)
+            .map(|(uid, _)| uid)
+    }
+}
+
 impl RequestGenerator {
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "Mirrors the state of the SchemaGenerator"
+    )]
     pub(crate) fn new(
         config: SchemaGeneratorConfig,
-        tools: ServerDescription,
+        servers: Vec<ServerTools>,
+        resource_templates: Vec<ResourceTemplateDescription>,
+        prompts: PromptList,
+        resource_arguments: HashMap<SmolStr, ResourceArgument>,
         root_namespace: Option<Name>,
         schema: ValidatorSchema,
         resolved_dedup: Option<HashMap<EntityTypeFingerprint, DeduplicatedEntityType>>,
     ) -> Self {
         Self {
             config,
-            tools,
+            servers,
+            resource_templates,
+            prompts,
+            resource_arguments,
             root_namespace,
             schema,
             resolved_dedup,
@@ -128,7 +275,13 @@ impl RequestGenerator {
     /// (4) the MCP tool input request, and (5) optionally the MCP tool output response
     ///
     /// The function will then return a Cedar Request and entities necessary to determine if the principal is
-    /// authorized to use (or receive the oputout of) the requested tool.
+    /// authorized to use (or receive the oputout of) the requested tool. The returned entities include
+    /// the schema's action entities, so that policies can refer to action groups (e.g., the action group
+    /// of an MCP server's tools) whatever authorizer evaluates the request.
+    ///
+    /// If the resource of the tool's requests is identified by one of its arguments (see the
+    /// `@mcp_resource_from` annotation), then `resource` is ignored and the request's resource
+    /// is the entity of the annotated type whose id is the value of that argument.
     pub fn generate_request(
         &self,
         principal: EntityUID,
@@ -138,26 +291,31 @@ impl RequestGenerator {
         input: &Input,
         output: Option<&Output>,
     ) -> Result<(Request, Entities), RequestGeneratorError> {
-        let input = self.tools.validate_input(input)?;
+        let server = self.server_of(input.name());
+        let input = server.description.validate_input(input)?;
+        let resource = self.argument_resource(&input)?.unwrap_or(resource);
         #[expect(
             clippy::unwrap_used,
             reason = "Validation ensures there is a tool in with the same name that the input validates against"
         )]
-        let tool = self
-            .tools
+        let tool = server
+            .description
             .tool_descriptions()
             .find(|t| t.name() == input.name())
             .unwrap();
 
         let output = output
-            .map(|output| self.tools.validate_output(tool.name(), output))
+            .map(|output| server.description.validate_output(tool.name(), output))
             .transpose()?;
 
         let mut type_defs = TypeDefsInfo::new();
-        type_defs.extend(self.tools.type_definitions(), self.root_namespace.clone());
+        type_defs.extend(
+            server.description.type_definitions(),
+            server.namespace.clone(),
+        );
 
         let tool_ns: Name = tool.name().parse()?;
-        let tool_ns = tool_ns.qualify_with_name(self.root_namespace.as_ref());
+        let tool_ns = tool_ns.qualify_with_name(server.namespace.as_ref());
 
         // Extend with tool specific type definitions
         type_defs.extend(tool.type_definitions(), Some(tool_ns.clone()));
@@ -222,7 +380,114 @@ impl RequestGenerator {
             cedar_policy_core::extensions::Extensions::all_available(),
         )?;
 
-        Ok((request, entities))
+        Ok((request, self.with_action_entities(entities)?))
+    }
+
+    /// Generate an authorization request for an MCP `resources/read` request, given its
+    /// principal and resource, any common context elements and entity information (see
+    /// [`Self::generate_request`]).
+    ///
+    /// The read URI is matched against the resource templates added to the `SchemaGenerator`
+    /// (see `SchemaGenerator::add_actions_from_resource_templates`), in order. The request's
+    /// action is the read action of the first matching template, and its context holds the
+    /// URI (`context.uri`) and the values of the template's variables (`context.input`).
+    pub fn generate_resource_read_request(
+        &self,
+        principal: EntityUID,
+        resource: EntityUID,
+        context: impl IntoIterator<Item = (SmolStr, RestrictedExpr)>,
+        entities: Entities,
+        read: &ResourceRead,
+    ) -> Result<(Request, Entities), RequestGeneratorError> {
+        let (template, values) = self
+            .resource_templates
+            .iter()
+            .find_map(|template| {
+                template
+                    .uri_template()
+                    .match_uri(read.uri())
+                    .map(|values| (template, values))
+            })
+            .ok_or_else(|| RequestGeneratorError::NoMatchingResourceTemplate(read.uri().into()))?;
+
+        let values = values
+            .into_iter()
+            .map(|(name, value)| (name, RestrictedExpr::val(value)));
+        let context = context.into_iter().chain(vec![
+            ("uri".to_smolstr(), RestrictedExpr::val(read.uri())),
+            ("input".to_smolstr(), RestrictedExpr::record(values)?),
+        ]);
+        let context = Context::from_pairs(
+            context,
+            cedar_policy_core::extensions::Extensions::all_available(),
+        )?;
+
+        let action = EntityUID::from_components(
+            identifiers::ACTION.qualify_with(self.root_namespace.as_ref()),
+            Eid::new(template.name()),
+            None,
+        );
+        // Validate the request against the schema as in `generate_request`
+        let request = Request::new(
+            (principal, None),
+            (action, None),
+            (resource, None),
+            context,
+            Some(&self.schema),
+            cedar_policy_core::extensions::Extensions::all_available(),
+        )?;
+
+        Ok((request, self.with_action_entities(entities)?))
+    }
+
+    /// Generate an authorization request for an MCP `prompts/get` request, given its
+    /// principal and resource, any common context elements and entity information (see
+    /// [`Self::generate_request`]).
+    ///
+    /// The arguments of the request are validated against the prompts added to the
+    /// `SchemaGenerator` (see `SchemaGenerator::add_actions_from_prompts`). The request's
+    /// action is the action of the requested prompt, and its context holds the arguments
+    /// of the request (`context.input`).
+    pub fn generate_prompt_request(
+        &self,
+        principal: EntityUID,
+        resource: EntityUID,
+        context: impl IntoIterator<Item = (SmolStr, RestrictedExpr)>,
+        entities: Entities,
+        get: &PromptGet,
+    ) -> Result<(Request, Entities), RequestGeneratorError> {
+        let input = self.prompts.validate_get(get)?;
+
+        // Prompt arguments are strings, so no entities are created for them
+        let (inputs, _) = self.values_to_cedar(
+            input.get_args(),
+            &TypeDefsInfo::new(),
+            self.root_namespace.as_ref(),
+        )?;
+        let context = context
+            .into_iter()
+            .chain(vec![("input".to_smolstr(), inputs)]);
+        let context = Context::from_pairs(
+            context,
+            cedar_policy_core::extensions::Extensions::all_available(),
+        )?;
+
+        let action = EntityUID::from_components(
+            identifiers::ACTION.qualify_with(self.root_namespace.as_ref()),
+            Eid::new(get.name()),
+            None,
+        );
+        // Validate the request against the schema as in `generate_request`
+        let request = Request::new(
+            (principal, None),
+            (action, None),
+            (resource, None),
+            context,
+            Some(&self.schema),
+            cedar_policy_core::extensions::Extensions::all_available(),
+        )?;
+
+        Ok((request, self.with_action_entities(entities)?))
     }
 
     /// Generate authorization request components as JSON-serializable values.
@@ -232,8 +497,8 @@ impl RequestGenerator {
     /// types directly — for example, WASM bindings or REST API bridges.
     ///
     /// Returns the principal, action, and resource as Cedar EntityUID strings,
-    /// and the entities as a JSON array. The caller is responsible for
-    /// constructing context from the MCP tool input.
+    /// the entities as a JSON array, and the context (including the encoded
+    /// MCP tool input and output) as a JSON object.
     pub fn generate_request_components(
         &self,
         input: &Input,
@@ -272,11 +537,18 @@ impl RequestGenerator {
         result_entities.write_to_json(&mut entities_buf)?;
         let entities_json_str = String::from_utf8_lossy(&entities_buf).into_owned();
 
+        // `generate_request` always attaches a context to the request
+        let context_json_str = match request.context() {
+            Some(context) => context.to_json_value()?.to_string(),
+            None => "{}".to_string(),
+        };
+
         Ok(AuthorizationComponents {
             principal: principal_str,
             action: action_str,
             resource: resource_str,
             entities_json: entities_json_str,
+            context_json: context_json_str,
         })
     }
 
@@ -297,10 +569,11 @@ impl RequestGenerator {
     /// * `resource_id` — e.g. `"server1"`
     ///
     /// # Returns
-    /// [`AuthorizationComponents`] — the four Cedar strings ready for JSON
+    /// [`AuthorizationComponents`] — the five Cedar strings ready for JSON
     /// serialization. `entities_json` is the real entity set produced by the
     /// generator from the input (including any entities derived from nulls,
-    /// floats, or nested objects per the schema config).
+    /// floats, or nested objects per the schema config), and `context_json`
+    /// holds the encoded input under `input`.
     pub fn generate_request_components_from_strings(
         &self,
         input: &Input,
@@ -309,18 +582,8 @@ impl RequestGenerator {
         resource_type: &str,
         resource_id: &str,
     ) -> Result<AuthorizationComponents, RequestGeneratorError> {
-        let principal_ns: Name = principal_type.parse()?;
-        let principal_ns = principal_ns.qualify_with_name(self.root_namespace.as_ref());
-        let principal = EntityUID::from_components(
-            EntityType::from(principal_ns),
-            Eid::new(principal_id),
-            None,
-        );
-
-        let resource_ns: Name = resource_type.parse()?;
-        let resource_ns = resource_ns.qualify_with_name(self.root_namespace.as_ref());
-        let resource =
-            EntityUID::from_components(EntityType::from(resource_ns), Eid::new(resource_id), None);
+        let principal = self.qualified_uid(principal_type, principal_id)?;
+        let resource = self.qualified_uid(resource_type, resource_id)?;
 
         self.generate_request_components(
             input,
@@ -332,16 +595,396 @@ impl RequestGenerator {
         )
     }
 
+    /// JSON-in / string-out convenience wrapper around
+    /// [`generate_request_components`] for FFI consumers (WASM, Python, C)
+    /// that supply their own context and entities.
+    ///
+    /// # Arguments
+    /// * `input` — the MCP tool-call input
+    /// * `output` — the MCP tool-call output, if any
+    /// * `principal` — a Cedar EntityUID string, e.g. `MyServer::User::"alice"`
+    /// * `resource` — a Cedar EntityUID string, e.g. `MyServer::McpServer::"server1"`
+    /// * `context_json` — a JSON object of additional context attributes in Cedar's JSON format
+    /// * `entities_json` — a JSON array of entities in Cedar's JSON entity format
+    ///
+    /// # Returns
+    /// [`AuthorizationComponents`] as for [`generate_request_components`], where
+    /// `entities_json` includes the provided entities.
+    pub fn generate_request_components_from_json(
+        &self,
+        input: &Input,
+        output: Option<&Output>,
+        principal: &str,
+        resource: &str,
+        context_json: &str,
+        entities_json: &str,
+    ) -> Result<AuthorizationComponents, RequestGeneratorError> {
+        let principal = principal
+            .parse::<EntityUID>()
+            .map_err(RequestGeneratorError::MalformedEntityUid)?;
+        let resource = resource
+            .parse::<EntityUID>()
+            .map_err(RequestGeneratorError::MalformedEntityUid)?;
+        let context = Context::from_json_str(context_json)
+            .map_err(RequestGeneratorError::MalformedContext)?;
+        let entities = parse_entities(entities_json)?;
+
+        self.generate_request_components(input, principal, resource, context, entities, output)
+    }
+
+    /// Authorize an MCP tool call against a set of Cedar policies.
+    ///
+    /// Generates the Cedar request for the tool call (see [`Self::generate_request`])
+    /// and evaluates `policies` against it, returning the decision along with the
+    /// policies that determined it and any errors encountered while evaluating policies.
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "Mirrors the arguments of generate_request plus the policies to evaluate"
+    )]
+    pub fn is_authorized(
+        &self,
+        principal: EntityUID,
+        resource: EntityUID,
+        context: impl IntoIterator<Item = (SmolStr, RestrictedExpr)>,
+        entities: Entities,
+        input: &Input,
+        output: Option<&Output>,
+        policies: &PolicySet,
+    ) -> Result<AuthorizationResponse, RequestGeneratorError> {
+        let (request, entities) =
+            self.generate_request(principal, resource, context, entities, input, output)?;
+        let response = Authorizer::new().is_authorized(request, policies, &entities);
+
+        let mut determining_policies = response
+            .diagnostics
+            .reason
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        determining_policies.sort();
+        let errors = response
+            .diagnostics
+            .errors
+            .into_iter()
+            .map(|err| match err {
+                AuthorizationError::PolicyEvaluationError { id, error } => PolicyEvaluationError {
+                    policy_id: id.to_string(),
+                    message: error.to_string(),
+                },
+            })
+            .collect();
+
+        Ok(AuthorizationResponse {
+            decision: response.decision,
+            determining_policies,
+            errors,
+        })
+    }
+
+    /// String-in convenience wrapper around [`Self::is_authorized`] for FFI consumers
+    /// (WASM, Python, C).
+    ///
+    /// Principal and resource [`EntityUID`]s are built from plain strings as in
+    /// [`Self::generate_request_components_from_strings`], `policies` is parsed from Cedar
+    /// policy syntax and `entities_json` from Cedar's JSON entity format. The context is empty.
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "FFI consumers require flat parameter lists"
+    )]
+    pub fn is_authorized_from_strings(
+        &self,
+        input: &Input,
+        output: Option<&Output>,
+        principal_type: &str,
+        principal_id: &str,
+        resource_type: &str,
+        resource_id: &str,
+        policies: &str,
+        entities_json: &str,
+    ) -> Result<AuthorizationResponse, RequestGeneratorError> {
+        let principal = self.qualified_uid(principal_type, principal_id)?;
+        let resource = self.qualified_uid(resource_type, resource_id)?;
+        let policies = cedar_policy_core::parser::parse_policyset(policies)
+            .map_err(RequestGeneratorError::MalformedPolicies)?;
+        let entities = parse_entities(entities_json)?;
+
+        self.is_authorized(
+            principal,
+            resource,
+            std::iter::empty::<(SmolStr, RestrictedExpr)>(),
+            entities,
+            input,
+            output,
+            &policies,
+        )
+    }
+
+    /// Redact the `output` of an MCP `tools/call` response to `input` according to `policies`.
+    ///
+    /// Each returned result of `output`, and each nested property of a returned object, that
+    /// has a redaction action (see `SchemaGeneratorConfig::redaction_actions`) is kept only if
+    /// the request for its redaction action is allowed. These requests share the principal,
+    /// resource and context of the request generated by [`Self::generate_request`] for `input`
+    /// and `output`. Required properties have no redaction action, so the redacted `Output`
+    /// still validates against the tool's `outputSchema`.
+    ///
+    /// This does not authorize the tool call itself (see [`Self::is_authorized`]).
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "Mirrors the arguments of is_authorized"
+    )]
+    pub fn redact_output(
+        &self,
+        principal: EntityUID,
+        resource: EntityUID,
+        context: impl IntoIterator<Item = (SmolStr, RestrictedExpr)>,
+        entities: Entities,
+        input: &Input,
+        output: &Output,
+        policies: &PolicySet,
+    ) -> Result<Output, RequestGeneratorError> {
+        let (request, entities) =
+            self.generate_request(principal, resource, context, entities, input, Some(output))?;
+        let output_type = self
+            .server_of(input.name())
+            .description
+            .validate_output(input.name(), output)?;
+
+        let redaction = Redaction {
+            generator: self,
+            tool: input.name(),
+            request,
+            entities: &entities,
+            policies,
+            authorizer: Authorizer::new(),
+        };
+        let mut redacted = output.clone();
+        for (name, value) in output_type.get_results() {
+            redaction.redact(&mut redacted, &mut vec![name], value);
+        }
+        Ok(redacted)
+    }
+
+    /// Build the `EntityUID` with id `id` of the entity type `ty` qualified with the
+    /// schema's namespace.
+    fn qualified_uid(&self, ty: &str, id: &str) -> Result<EntityUID, RequestGeneratorError> {
+        let ty: Name = ty.parse()?;
+        let ty = ty.qualify_with_name(self.root_namespace.as_ref());
+        Ok(EntityUID::from_components(
+            EntityType::from(ty),
+            Eid::new(id),
+            None,
+        ))
+    }
+
+    /// Determine the tools `principal` could possibly be authorized to call on `resource`.
+    ///
+    /// For each tool, the policies are partially evaluated against a request whose
+    /// `context.input` (and `context.output`, if outputs are included in the schema) is
+    /// unknown. A tool is kept unless the request is denied whatever the tool's input is,
+    /// e.g., because no policy can permit the call or a policy forbids it unconditionally.
+    /// Tools whose action does not apply to the types of `principal` and `resource` (see the
+    /// `@mcp_tools` annotation) are removed.
+    ///
+    /// Returns, for each added MCP server (in the order they were added), a `ServerDescription`
+    /// containing only its kept tools and all of its type definitions, e.g., to be advertised
+    /// as the server's `tools/list` response to `principal`. Type definitions are kept per
+    /// server, since several servers may define types of the same name.
+    pub fn authorized_tools(
+        &self,
+        principal: &EntityUID,
+        resource: &EntityUID,
+        context: impl IntoIterator<Item = (SmolStr, RestrictedExpr)>,
+        entities: &Entities,
+        policies: &PolicySet,
+    ) -> Result<Vec<AuthorizedTools>, RequestGeneratorError> {
+        let context = context.into_iter().collect::<Vec<_>>();
+        let authorizer = Authorizer::new();
+        let entities = self.with_action_entities(entities.clone())?;
+        let mut servers = Vec::new();
+        for server in &self.servers {
+            let mut tools = Vec::new();
+            for tool in server.description.tool_descriptions() {
+                // Calls to tools whose action does not apply to the principal's (or the
+                // resource's) type are invalid
+                let action = EntityUID::from_components(
+                    identifiers::ACTION.qualify_with(self.root_namespace.as_ref()),
+                    Eid::new(tool.name()),
+                    None,
+                );
+                let applies = self.schema.get_action_id(&action).is_some_and(|action| {
+                    action.is_applicable_principal_type(principal.entity_type())
+                        && (self.resource_arguments.contains_key(tool.name())
+                            || action.is_applicable_resource_type(resource.entity_type()))
+                });
+                if !applies {
+                    continue;
+                }
+                let request = self.partial_request(
+                    tool,
+                    principal.clone(),
+                    resource.clone(),
+                    context.clone(),
+                )?;
+                let response = authorizer.is_authorized_core(request, policies, &entities);
+                if response.decision() != Some(Decision::Deny) {
+                    tools.push(tool.clone());
+                }
+            }
+            let type_defs = server
+                .description
+                .type_definitions()
+                .map(|ty_def| (ty_def.name().to_smolstr(), ty_def.clone()))
+                .collect();
+            servers.push(AuthorizedTools {
+                server_id: server.id.clone(),
+                tools: ServerDescription::new(tools.into_iter(), type_defs),
+            });
+        }
+        Ok(servers)
+    }
+
+    /// Get the id of the MCP server providing the tool named `tool_name`.
+    ///
+    /// Returns `None` if no server provides the tool, or if the tool was added without a
+    /// server id (see `SchemaGenerator::add_actions_from_server_description_with_id`).
+    pub fn server_for_tool(&self, tool_name: &str) -> Option<&str> {
+        self.servers
+            .iter()
+            .find(|server| server.provides(tool_name))
+            .and_then(|server| server.id.as_deref())
+    }
+
+    /// Add the schema's action entities missing from `entities`, so that policies can
+    /// refer to action groups (e.g., the action group of an MCP server's tools).
+    fn with_action_entities(&self, entities: Entities) -> Result<Entities, RequestGeneratorError> {
+        let actions = self
+            .schema
+            .action_entities()?
+            .iter()
+            .filter(|action| matches!(entities.entity(action.uid()), Dereference::NoSuchEntity))
+            .map(|action| Arc::new(action.clone()))
+            .collect::<Vec<_>>();
+        Ok(entities.add_entities(
+            actions,
+            None::<&cedar_policy_core::validator::CoreSchema<'_>>,
+            cedar_policy_core::entities::TCComputation::AssumeAlreadyComputed,
+            cedar_policy_core::extensions::Extensions::all_available(),
+        )?)
+    }
+
+    /// The server providing the tool named `tool_name`. Falls back to the first server
+    /// if no server provides the tool, so that validating against it reports the unknown tool.
+    fn server_of(&self, tool_name: &str) -> &ServerTools {
+        #[expect(
+            clippy::unwrap_used,
+            reason = "A SchemaGenerator always holds at least one server"
+        )]
+        let first = self.servers.first().unwrap();
+        self.servers
+            .iter()
+            .find(|server| server.provides(tool_name))
+            .unwrap_or(first)
+    }
+
+    /// Build the request to call `tool` in which the tool's input (and output) is unknown.
+    fn partial_request(
+        &self,
+        tool: &ToolDescription,
+        principal: EntityUID,
+        resource: EntityUID,
+        context: Vec<(SmolStr, RestrictedExpr)>,
+    ) -> Result<Request, RequestGeneratorError> {
+        let mut context = context;
+        context.push((
+            "input".to_smolstr(),
+            RestrictedExpr::unknown(Unknown::new_untyped("input")),
+        ));
+        if self.config.include_outputs {
+            context.push((
+                "output".to_smolstr(),
+                RestrictedExpr::unknown(Unknown::new_untyped("output")),
+            ));
+        }
+        let context = Context::from_pairs(
+            context,
+            cedar_policy_core::extensions::Extensions::all_available(),
+        )?;
+
+        let action = EntityUID::from_components(
+            identifiers::ACTION.qualify_with(self.root_namespace.as_ref()),
+            Eid::new(tool.name()),
+            None,
+        );
+        // The request is not validated against the schema, since the validator rejects
+        // contexts containing unknowns
+        // The resource is unknown as well if it is identified by one of the tool's arguments
+        let resource = if self.resource_arguments.contains_key(tool.name()) {
+            EntityUIDEntry::unknown()
+        } else {
+            EntityUIDEntry::known(resource, None)
+        };
+        Ok(Request::new_unchecked(
+            EntityUIDEntry::known(principal, None),
+            EntityUIDEntry::known(action, None),
+            resource,
+            Some(context),
+        ))
+    }
+
+    /// The resource identified by an argument of `input`, if the resource of the tool's
+    /// requests is identified by one of its arguments. Fails if the argument does not
+    /// identify a resource, rather than falling back to the supplied resource.
+    fn argument_resource(
+        &self,
+        input: &TypedInput,
+    ) -> Result<Option<EntityUID>, RequestGeneratorError> {
+        let Some(resource) = self.resource_arguments.get(input.name()) else {
+            return Ok(None);
+        };
+        let id = input
+            .get_arg(&resource.argument)
+            .and_then(entity_id::key_value)
+            .ok_or_else(|| RequestGeneratorError::MissingResourceArgument {
+                tool: input.name().into(),
+                argument: resource.argument.clone(),
+            })?;
+        let ty = EntityType::from(Name::from(resource.entity_type.clone()))
+            .qualify_with(self.root_namespace.as_ref());
+        Ok(Some(EntityUID::from_components(ty, Eid::new(id), None)))
+    }
+
     fn values_to_cedar<'a>(
         &self,
         vals: impl Iterator<Item = (&'a str, &'a TypedValue)>,
         type_defs: &TypeDefsInfo,
         namespace: Option<&Name>,
+    ) -> Result<(RestrictedExpr, Entities), RequestGeneratorError> {
+        let vals = vals.collect::<Vec<_>>();
+        let mut ids = KeyedEntityIds::default();
+        // Distinct objects sharing a key value would be represented by the same entity, so the
+        // values are converted again with their entities named after their content instead
+        loop {
+            let result = self.values_to_cedar_with_ids(&vals, type_defs, namespace, &ids);
+            let shared = ids.newly_shared().collect::<Vec<_>>();
+            if shared.is_empty() {
+                return result;
+            }
+            ids.shared.extend(shared);
+        }
+    }
+
+    fn values_to_cedar_with_ids(
+        &self,
+        vals: &[(&str, &TypedValue)],
+        type_defs: &TypeDefsInfo,
+        namespace: Option<&Name>,
+        ids: &KeyedEntityIds,
     ) -> Result<(RestrictedExpr, Entities), RequestGeneratorError> {
         let mut entities = Entities::new();
         let mut exprs = HashMap::new();
         for (name, arg) in vals {
-            let (expr, new_entities) = self.val_to_cedar(arg, type_defs, namespace, name)?;
+            let (expr, new_entities) = self.val_to_cedar(arg, type_defs, namespace, name, ids)?;
             entities = entities.add_entities(
                 new_entities.into_iter().map(Arc::from),
                 None::<&cedar_policy_core::validator::CoreSchema<'_>>,
@@ -359,6 +1002,7 @@ impl RequestGenerator {
         type_defs: &TypeDefsInfo,
         namespace: Option<&Name>,
         ty_name: &str,
+        ids: &KeyedEntityIds,
     ) -> Result<(RestrictedExpr, Entities), RequestGeneratorError> {
         match val {
             TypedValue::Null => {
@@ -452,10 +1096,10 @@ impl RequestGenerator {
             TypedValue::Unknown(_) => {
                 let ty = EntityType::from(Name::from(identifiers::UNKNOWN_TYPE.clone()));
                 let ty = ty.qualify_with(self.root_namespace.as_ref());
-                // Generate a unique EID for all Unknown values
+                // Unless EIDs are deterministic, generate a unique EID for all Unknown values
                 // with the implication being that any unkonwn value is different from any other
                 // unknown value even if they were the same JSON value.
-                let eid = Eid::new(Uuid::new_v4().to_smolstr());
+                let eid = self.entity_id(&ty, val, ids);
                 let euid = EntityUID::from_components(ty, eid, None);
                 Ok((RestrictedExpr::val(euid), Entities::new()))
             }
@@ -475,7 +1119,7 @@ impl RequestGenerator {
                 let mut entities = Entities::new();
                 for val in vals {
                     let (expr, new_entities) =
-                        self.val_to_cedar(val, type_defs, namespace, ty_name)?;
+                        self.val_to_cedar(val, type_defs, namespace, ty_name, ids)?;
                     entities = entities.add_entities(
                         new_entities.into_iter().map(Arc::from),
                         None::<&cedar_policy_core::validator::CoreSchema<'_>>,
@@ -495,7 +1139,7 @@ impl RequestGenerator {
                     let sub_namespace: Name = ty_name.parse::<UnreservedId>()?.into();
                     let sub_namespace = sub_namespace.qualify_with_name(namespace);
                     let (expr, new_entities) =
-                        self.val_to_cedar(val, type_defs, Some(&sub_namespace), &sub_ty_name)?;
+                        self.val_to_cedar(val, type_defs, Some(&sub_namespace), &sub_ty_name, ids)?;
                     entities = entities.add_entities(
                         new_entities.into_iter().map(Arc::from),
                         None::<&cedar_policy_core::validator::CoreSchema<'_>>,
@@ -506,13 +1150,12 @@ impl RequestGenerator {
                 }
                 Ok((RestrictedExpr::record(pairs)?, entities))
             }
-            TypedValue::Union { index, value } => {
-                let sub_ty_name = format!("TypeChoice{}", index);
-                let name = format!("typeChoice{}", index).to_smolstr();
+            TypedValue::Union { index, tag, value } => {
+                let (name, sub_ty_name) = union_choice_names(*index, tag.as_ref());
                 let sub_namespace: Name = ty_name.parse::<UnreservedId>()?.into();
                 let sub_namespace = sub_namespace.qualify_with_name(namespace);
                 let (expr, entities) =
-                    self.val_to_cedar(value, type_defs, Some(&sub_namespace), &sub_ty_name)?;
+                    self.val_to_cedar(value, type_defs, Some(&sub_namespace), &sub_ty_name, ids)?;
                 Ok((RestrictedExpr::record([(name, expr)])?, entities))
             }
             TypedValue::Object {
@@ -531,8 +1174,13 @@ impl RequestGenerator {
                 > {
                     let mut pairs = HashMap::new();
                     for (name, val) in props.iter() {
-                        let (expr, new_entities) =
-                            self.val_to_cedar(val, type_defs, Some(&sub_namespace), name.as_ref())?;
+                        let (expr, new_entities) = self.val_to_cedar(
+                            val,
+                            type_defs,
+                            Some(&sub_namespace),
+                            name.as_ref(),
+                            ids,
+                        )?;
                         let old_entities = std::mem::replace(entities, Entities::new());
                         *entities = old_entities.add_entities(
                             new_entities.into_iter().map(Arc::from),
@@ -549,16 +1197,15 @@ impl RequestGenerator {
                 let tags = into_pairs(additional_properties, &mut entities)?;
 
                 if tags.is_empty() && self.config.objects_as_records {
-                    Ok((RestrictedExpr::record(pairs.into_iter())?, entities))
+                    Ok((RestrictedExpr::record(pairs)?, entities))
                 } else {
                     // Check if this object was deduplicated to a different namespace.
                     let qualified_ty = self.resolved_ty(ty_name, namespace)?;
-                    // Generate a unique EID for each object's entity representation
-                    // This means that all entities are different from all other entities
-                    // even if the entities are structurally equivalent.
-                    // Perhaps we could generate EIDs in a way that result in equal EIDs for
-                    // structurally equivalent entities.
-                    let eid = Eid::new(Uuid::new_v4().to_smolstr());
+                    // Unless EIDs are deterministic or taken from a key property, generate a
+                    // unique EID for each object's entity representation. This means that all
+                    // entities are different from all other entities even if the entities are
+                    // structurally equivalent.
+                    let eid = self.entity_id(&qualified_ty, val, ids);
                     let euid = EntityUID::from_components(qualified_ty, eid, None);
                     let euid = if self.config.flatten_namespaces {
                         flatten_name(euid)
@@ -583,9 +1230,37 @@ impl RequestGenerator {
                 }
             }
             TypedValue::Ref { name, val } => {
-                self.val_to_cedar(val, type_defs, type_defs.get(name), name.as_str())
+                self.val_to_cedar(val, type_defs, type_defs.get(name), name.as_str(), ids)
+            }
+        }
+    }
+
+    /// Returns the EID of the entity of type `ty` representing `val` (see
+    /// `SchemaGeneratorConfig::deterministic_entity_ids` and `SchemaGeneratorConfig::entity_id_key`).
+    /// Objects sharing their key value with a distinct object of the same type are named after
+    /// their content.
+    fn entity_id(&self, ty: &EntityType, val: &TypedValue, ids: &KeyedEntityIds) -> Eid {
+        if let (Some(key), TypedValue::Object { properties, .. }) =
+            (&self.config.entity_id_key, val)
+        {
+            if let Some(id) = properties.get(key).and_then(entity_id::key_value) {
+                let uid = EntityUID::from_components(ty.clone(), Eid::new(id.clone()), None);
+                if ids.shared.contains(&uid) {
+                    return Eid::new(entity_id::content_hash(ty, val));
+                }
+                ids.named
+                    .borrow_mut()
+                    .entry(uid)
+                    .or_default()
+                    .insert(entity_id::content_hash(ty, val));
+                return Eid::new(id);
             }
         }
+        #[cfg(feature = "uuid")]
+        if !self.config.deterministic_entity_ids {
+            return Eid::new(Uuid::new_v4().to_smolstr());
+        }
+        Eid::new(entity_id::content_hash(ty, val))
     }
 
     /// Checks if the type name was deduplicated in another namespace.
@@ -822,10 +1497,20 @@ fn flatten_name(euid: EntityUID) -> EntityUID {
     EntityUID::from_components(entity_type, eid, None)
 }
 
+/// Parse entities in Cedar's JSON entity format
+fn parse_entities(entities_json: &str) -> Result<Entities, RequestGeneratorError> {
+    cedar_policy_core::entities::EntityJsonParser::new(
+        None::<&cedar_policy_core::validator::CoreSchema<'_>>,
+        cedar_policy_core::extensions::Extensions::all_available(),
+        cedar_policy_core::entities::TCComputation::ComputeNow,
+    )
+    .from_json_str(entities_json)
+    .map_err(RequestGeneratorError::MalformedEntities)
+}
+
 #[cfg(test)]
 mod test {
     use cedar_policy_core::ast::{Literal, PartialValue, Value, ValueKind};
-    use cedar_policy_core::entities::Dereference;
     use cool_asserts::assert_matches;
     use std::str::FromStr;
 
@@ -1250,7 +1935,13 @@ mod test {
         let type_defs = TypeDefsInfo::new();
         let namespace = Some("Test".parse().unwrap());
         let (expr, entities) = request_generator
-            .val_to_cedar(val, &type_defs, namespace.as_ref(), "test_type")
+            .val_to_cedar(
+                val,
+                &type_defs,
+                namespace.as_ref(),
+                "test_type",
+                &KeyedEntityIds::default(),
+            )
             .expect(&format!(
                 "Failed to convert {:?} to Cedar expression and entities",
                 val
@@ -1379,6 +2070,7 @@ mod test {
                     &type_defs,
                     namespace.as_ref(),
                     "test_type",
+                    &KeyedEntityIds::default(),
                 )
                 .unwrap();
             let (expr_b, _) = request_generator
@@ -1387,6 +2079,7 @@ mod test {
                     &type_defs,
                     namespace.as_ref(),
                     "test_type",
+                    &KeyedEntityIds::default(),
                 )
                 .unwrap();
             assert_ne!(expr_a, expr_b, "Adjacent floats must produce distinct EIDs");
@@ -1499,6 +2192,19 @@ mod test {
             .expect("Failed to construct request generator")
     }
 
+    /// The entities of a generated request, without the schema's action entities
+    fn without_actions(entities: Entities) -> Entities {
+        Entities::from_entities(
+            entities
+                .into_iter()
+                .filter(|entity| !entity.uid().is_action()),
+            None::<&cedar_policy_core::validator::CoreSchema<'_>>,
+            cedar_policy_core::entities::TCComputation::AssumeAlreadyComputed,
+            cedar_policy_core::extensions::Extensions::all_available(),
+        )
+        .unwrap()
+    }
+
     #[test]
     fn test_generate_request_default_config_empty_input() {
         let request_generator = get_request_generator(
@@ -1546,6 +2252,7 @@ mod test {
                 Some(&output),
             )
             .expect("Failed to generate request");
+        let entities = without_actions(entities);
 
         assert_eq!(request.principal().uid().unwrap(), &principal);
         assert_eq!(
@@ -1616,6 +2323,7 @@ mod test {
                 None,
             )
             .expect("Failed to generate request");
+        let entities = without_actions(entities);
 
         assert_eq!(request.principal().uid().unwrap(), &principal);
         assert_eq!(
@@ -1668,7 +2376,13 @@ mod test {
             let num = str_to_number(input);
             let val = TypedValue::Number(num);
             let (expr, _) = request_gen
-                .val_to_cedar(&val, &type_defs, namespace.as_ref(), "test_type")
+                .val_to_cedar(
+                    &val,
+                    &type_defs,
+                    namespace.as_ref(),
+                    "test_type",
+                    &KeyedEntityIds::default(),
+                )
                 .unwrap_or_else(|_| panic!("Failed to convert Number({}) to Cedar", input));
             let expected_expr = RestrictedExpr::from_str(expected).unwrap();
             assert_eq!(
@@ -1722,6 +2436,7 @@ mod test {
                 None,
             )
             .expect("Failed to generate request");
+        let entities = without_actions(entities);
 
         assert_eq!(request.principal().uid().unwrap(), &principal);
         assert_eq!(
@@ -1789,6 +2504,7 @@ mod test {
                 None,
             )
             .expect("Failed to generate request");
+        let entities = without_actions(entities);
 
         assert_eq!(request.principal().uid().unwrap(), &principal);
         assert_eq!(
@@ -1844,6 +2560,7 @@ mod test {
                     None,
                 )
                 .expect("Failed to generate request");
+            let entities = without_actions(entities);
 
             assert_eq!(request.principal().uid().unwrap(), &principal);
             assert_eq!(
@@ -1941,6 +2658,7 @@ mod test {
                 None,
             )
             .expect("Failed to generate request");
+        let entities = without_actions(entities);
 
         assert_eq!(request.principal().uid().unwrap(), &principal);
         assert_eq!(
@@ -1965,9 +2683,67 @@ mod test {
     }
 
     #[test]
-    fn test_generate_request_default_config_ref_of_object() {
+    fn test_generate_request_discriminated_union() {
         let request_generator = get_request_generator(
-            SchemaGeneratorConfig::default(),
+            SchemaGeneratorConfig::default().objects_as_records(true),
+            r#"{
+    "name": "test_tool",
+    "description": "test_description",
+    "parameters": {
+        "properties": {
+            "shape": {
+                "oneOf": [
+                    { "type": "object", "properties": { "kind": { "const": "circle" }, "radius": { "type": "integer" } } },
+                    { "type": "object", "properties": { "kind": { "const": "square" }, "side": { "type": "integer" } } }
+                ],
+                "discriminator": { "propertyName": "kind" }
+            }
+        }
+    }
+}"#,
+        );
+
+        let input = Input::from_json_str(
+            r#"{
+    "params": {
+        "tool": "test_tool",
+        "args": {
+            "shape": { "kind": "square", "side": 2 }
+        }
+    }
+}"#,
+        )
+        .expect("Failed to parse input");
+
+        let principal = r#"Test::user::"""#.parse::<EntityUID>().unwrap();
+        let resource = r#"Test::resource::"""#.parse::<EntityUID>().unwrap();
+        let (request, _) = request_generator
+            .generate_request(
+                principal,
+                resource,
+                Context::empty(),
+                Entities::new(),
+                &input,
+                None,
+            )
+            .expect("Failed to generate request");
+
+        assert_matches!(request.context(), Some(Context::Value(kvs)) if {
+            matches!(kvs.get("input").map(Value::value_kind), Some(ValueKind::Record(ikvs)) if {
+                matches!(ikvs.get("shape").map(Value::value_kind), Some(ValueKind::Record(iikvs)) if {
+                    iikvs.len() == 1 &&
+                    matches!(iikvs.get("square").map(Value::value_kind), Some(ValueKind::Record(square)) if {
+                        matches!(square.get("side").map(Value::value_kind), Some(ValueKind::Lit(Literal::Long(2))))
+                    })
+                })
+            })
+        });
+    }
+
+    #[test]
+    fn test_generate_request_default_config_ref_of_object() {
+        let request_generator = get_request_generator(
+            SchemaGeneratorConfig::default(),
             r##"{
     "name": "test_tool",
     "description": "test_description",
@@ -2019,6 +2795,7 @@ mod test {
                 None,
             )
             .expect("Failed to generate request");
+        let entities = without_actions(entities);
 
         assert_eq!(request.principal().uid().unwrap(), &principal);
         assert_eq!(
@@ -2108,6 +2885,7 @@ mod test {
                 None,
             )
             .expect("Failed to generate request");
+        let entities = without_actions(entities);
 
         assert_eq!(request.principal().uid().unwrap(), &principal);
         assert_eq!(
@@ -2199,6 +2977,7 @@ mod test {
                 None,
             )
             .expect("Failed to generate request");
+        let entities = without_actions(entities);
 
         assert_eq!(request.principal().uid().unwrap(), &principal);
         assert_eq!(
@@ -2284,6 +3063,7 @@ mod test {
                 Some(&output),
             )
             .expect("Failed to generate request");
+        let entities = without_actions(entities);
 
         assert_eq!(request.principal().uid().unwrap(), &principal);
         assert_eq!(
@@ -2321,12 +3101,14 @@ mod test {
             action: "NS::Action::\"read\"".to_string(),
             resource: "NS::McpServer::\"s1\"".to_string(),
             entities_json: "[]".to_string(),
+            context_json: "{}".to_string(),
         };
         let cloned = comp.clone();
         assert_eq!(cloned.principal, "NS::User::\"alice\"");
         assert_eq!(cloned.action, "NS::Action::\"read\"");
         assert_eq!(cloned.resource, "NS::McpServer::\"s1\"");
         assert_eq!(cloned.entities_json, "[]");
+        assert_eq!(cloned.context_json, "{}");
         // Debug should be derivable
         let debug_str = format!("{:?}", comp);
         assert!(debug_str.contains("AuthorizationComponents"));
@@ -2465,6 +3247,71 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_generate_request_components_context_serialized() {
+        let request_generator = get_request_generator(
+            SchemaGeneratorConfig::default(),
+            r#"{
+                "name": "schedule",
+                "description": "test",
+                "parameters": {
+                    "properties": {
+                        "title": { "type": "string" },
+                        "at": { "type": "string", "format": "date-time" },
+                        "location": {
+                            "type": "object",
+                            "properties": { "room": { "type": "string" } }
+                        }
+                    },
+                    "required": ["title", "at", "location"]
+                }
+            }"#,
+        );
+
+        let input = Input::from_json_str(
+            r#"{"params": {"tool": "schedule", "args": {
+                "title": "standup",
+                "at": "2025-12-16T09:00:00Z",
+                "location": { "room": "101" }
+            }}}"#,
+        )
+        .expect("Failed to parse input");
+
+        let principal = r#"Test::user::"alice""#.parse::<EntityUID>().unwrap();
+        let resource = r#"Test::resource::"s1""#.parse::<EntityUID>().unwrap();
+
+        let components = request_generator
+            .generate_request_components(
+                &input,
+                principal,
+                resource,
+                Context::empty(),
+                Entities::new(),
+                None,
+            )
+            .expect("Failed to generate components");
+
+        // Extension values and entity references use Cedar's JSON escapes
+        assert!(
+            components
+                .context_json
+                .contains(r#""__extn":{"fn":"datetime""#),
+            "context_json should escape datetimes, got: {}",
+            components.context_json
+        );
+        assert!(
+            components
+                .context_json
+                .contains(r#""location":{"__entity":"#),
+            "context_json should escape entities, got: {}",
+            components.context_json
+        );
+        // and can be parsed back into a Cedar context
+        let context =
+            Context::from_json_str(&components.context_json).expect("Failed to parse context");
+        assert_eq!(context.num_keys(), 1);
+    }
+
     #[test]
     fn test_generate_request_components_from_strings_basic() {
         let request_generator = get_request_generator(
@@ -2534,6 +3381,64 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_generate_request_components_from_json() {
+        let request_generator = get_request_generator(
+            SchemaGeneratorConfig::default(),
+            r#"{
+                "name": "tool1",
+                "description": "test",
+                "parameters": { "properties": {}, "required": [] }
+            }"#,
+        );
+        let input = Input::from_json_str(r#"{"params": {"tool": "tool1", "args": {}}}"#)
+            .expect("Failed to parse input");
+        let entities =
+            r#"[{"uid": {"type": "Test::user", "id": "alice"}, "attrs": {}, "parents": []}]"#;
+
+        let components = request_generator
+            .generate_request_components_from_json(
+                &input,
+                None,
+                r#"Test::user::"alice""#,
+                r#"Test::resource::"s1""#,
+                "{}",
+                entities,
+            )
+            .expect("Failed to generate components from json");
+        assert_eq!(components.principal, r#"Test::user::"alice""#);
+        assert_eq!(components.resource, r#"Test::resource::"s1""#);
+        // The provided entities are included in the output entities
+        assert!(
+            components.entities_json.contains(r#""alice""#),
+            "entities_json should contain the provided entities, got: {}",
+            components.entities_json
+        );
+
+        assert_matches!(
+            request_generator.generate_request_components_from_json(
+                &input,
+                None,
+                "alice",
+                r#"Test::resource::"s1""#,
+                "{}",
+                "[]",
+            ),
+            Err(RequestGeneratorError::MalformedEntityUid(_))
+        );
+        assert_matches!(
+            request_generator.generate_request_components_from_json(
+                &input,
+                None,
+                r#"Test::user::"alice""#,
+                r#"Test::resource::"s1""#,
+                "[]",
+                "[]",
+            ),
+            Err(RequestGeneratorError::MalformedContext(_))
+        );
+    }
+
     #[test]
     fn test_generate_request_dedup_enum_resolves_to_lca() {
         // Two tools share `format` enum ["markdown", "text"]. With dedup enabled,
@@ -2858,12 +3763,994 @@ mod test {
 
         let val = TypedValue::Tuple(vec![TypedValue::Bool(true), TypedValue::Integer(42)]);
         let (expr, _entities) = request_generator
-            .val_to_cedar(&val, &type_defs, namespace.as_ref(), "myTuple")
+            .val_to_cedar(
+                &val,
+                &type_defs,
+                namespace.as_ref(),
+                "myTuple",
+                &KeyedEntityIds::default(),
+            )
             .expect("Failed to convert Tuple to Cedar");
 
         assert_eq!(expr.to_string(), "{proj0: true, proj1: 42}");
     }
 
+    fn authorized_tool_names(
+        config: SchemaGeneratorConfig,
+        user: &str,
+        policies: &str,
+    ) -> Vec<String> {
+        let mut schema_generator = get_schema_generator(config);
+        let server_description = ServerDescription::from_json_str(
+            r#"{
+    "result": {
+        "tools": [
+        {
+            "name": "read_file",
+            "inputSchema": {
+                "properties": { "path": { "type": "string" } },
+                "required": ["path"]
+            }
+        },
+        {
+            "name": "write_file",
+            "inputSchema": {
+                "properties": { "path": { "type": "string" } },
+                "required": ["path"]
+            }
+        },
+        {
+            "name": "delete_file",
+            "inputSchema": {
+                "properties": { "path": { "type": "string" } },
+                "required": ["path"]
+            }
+        },
+        {
+            "name": "list_files",
+            "inputSchema": { "properties": {} }
+        }
+    ]
+    }
+}"#,
+        )
+        .expect("Failed to parse server description");
+        schema_generator
+            .add_actions_from_server_description(&server_description)
+            .expect("Failed to add tools to schema generator");
+        let request_generator = schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+
+        let policies =
+            cedar_policy_core::parser::parse_policyset(policies).expect("Failed to parse policies");
+        let principal = format!(r#"Test::user::"{user}""#)
+            .parse::<EntityUID>()
+            .unwrap();
+        let resource = r#"Test::resource::"""#.parse::<EntityUID>().unwrap();
+        let tools = request_generator
+            .authorized_tools(
+                &principal,
+                &resource,
+                Context::empty(),
+                &Entities::new(),
+                &policies,
+            )
+            .expect("Failed to filter tools");
+        let mut names = tools
+            .iter()
+            .flat_map(|server| server.tools.tool_descriptions())
+            .map(|tool| tool.name().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_authorized_tools() {
+        let policies = r#"
+permit(principal, action == Test::Action::"list_files", resource);
+permit(principal, action == Test::Action::"read_file", resource)
+when { context.input.path like "/public/*" };
+permit(principal == Test::user::"admin", action, resource);
+forbid(principal, action == Test::Action::"delete_file", resource)
+unless { principal == Test::user::"root" };
+"#;
+
+        // Unconditional permits and permits depending on the input are kept
+        assert_eq!(
+            authorized_tool_names(SchemaGeneratorConfig::default(), "alice", policies),
+            vec!["list_files", "read_file"]
+        );
+        // Forbid policies applying regardless of the input remove tools
+        assert_eq!(
+            authorized_tool_names(SchemaGeneratorConfig::default(), "admin", policies),
+            vec!["list_files", "read_file", "write_file"]
+        );
+        assert_eq!(
+            authorized_tool_names(SchemaGeneratorConfig::default(), "root", policies),
+            vec!["list_files", "read_file"]
+        );
+        // No policies, no tools
+        assert_eq!(
+            authorized_tool_names(SchemaGeneratorConfig::default(), "alice", ""),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_authorized_tools_forbid_on_input_or_output() {
+        let policies = r#"
+permit(principal, action, resource);
+forbid(principal, action == Test::Action::"write_file", resource)
+when { context.input.path like "/etc/*" };
+forbid(principal, action == Test::Action::"read_file", resource)
+when { context has output };
+"#;
+
+        // Forbid policies depending on the input may not apply, so tools are kept
+        assert_eq!(
+            authorized_tool_names(SchemaGeneratorConfig::default(), "alice", policies),
+            vec!["delete_file", "list_files", "read_file", "write_file"]
+        );
+        // With outputs, `context has output` is known to hold
+        assert_eq!(
+            authorized_tool_names(
+                SchemaGeneratorConfig::default().include_outputs(true),
+                "alice",
+                policies
+            ),
+            vec!["delete_file", "list_files", "write_file"]
+        );
+    }
+
+    #[test]
+    fn test_is_authorized_from_strings() {
+        let mut schema_generator = get_schema_generator(SchemaGeneratorConfig::default());
+        let tool = ToolDescription::from_json_str(
+            r#"{
+    "name": "read_file",
+    "inputSchema": {
+        "properties": { "path": { "type": "string" } },
+        "required": ["path"]
+    }
+}"#,
+        )
+        .expect("Failed to parse tool description");
+        schema_generator
+            .add_action_from_tool_description(&tool)
+            .expect("Failed to add tool to schema generator");
+        let request_generator = schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+        let input = Input::from_json_str(
+            r#"{"params": {"tool": "read_file", "args": {"path": "/public/index.html"}}}"#,
+        )
+        .expect("Failed to parse input");
+        let policies = r#"
+permit(principal, action, resource) when { context.input.path like "/public/*" };
+permit(principal, action, resource) when { principal.admin };
+forbid(principal == Test::user::"mallory", action, resource);
+"#;
+        let entities =
+            r#"[{"uid": {"type": "Test::user", "id": "alice"}, "attrs": {}, "parents": []}]"#;
+
+        let response = request_generator
+            .is_authorized_from_strings(
+                &input, None, "user", "alice", "resource", "", policies, entities,
+            )
+            .expect("Failed to authorize");
+        assert!(response.is_allowed());
+        assert_eq!(response.determining_policies, vec!["policy0"]);
+        assert_eq!(
+            response
+                .errors
+                .iter()
+                .map(|err| err.policy_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["policy1"]
+        );
+
+        let response = request_generator
+            .is_authorized_from_strings(
+                &input, None, "user", "mallory", "resource", "", policies, "[]",
+            )
+            .expect("Failed to authorize");
+        assert_eq!(response.decision, Decision::Deny);
+        assert_eq!(response.determining_policies, vec!["policy2"]);
+
+        assert_matches!(
+            request_generator.is_authorized_from_strings(
+                &input, None, "user", "alice", "resource", "", "permit(", "[]",
+            ),
+            Err(RequestGeneratorError::MalformedPolicies(_))
+        );
+        assert_matches!(
+            request_generator.is_authorized_from_strings(
+                &input, None, "user", "alice", "resource", "", policies, "{",
+            ),
+            Err(RequestGeneratorError::MalformedEntities(_))
+        );
+    }
+
+    /// A request generator for two servers, `github` and `jira`, which both define a type
+    /// `Query` with a different shape
+    fn github_and_jira_request_generator() -> RequestGenerator {
+        let github = ServerDescription::from_json_str(
+            r##"{"result": {
+    "tools": [{
+        "name": "search",
+        "inputSchema": {
+            "properties": { "query": { "$ref": "#/$defs/Query" } },
+            "required": ["query"]
+        }
+    }],
+    "$defs": { "Query": { "type": "object", "properties": { "text": { "type": "string" } } } }
+}}"##,
+        )
+        .expect("Failed to parse server description");
+        let jira = ServerDescription::from_json_str(
+            r##"{"result": {
+    "tools": [{
+        "name": "create_issue",
+        "inputSchema": {
+            "properties": { "summary": { "$ref": "#/$defs/Query" } },
+            "required": ["summary"]
+        }
+    }],
+    "$defs": { "Query": { "type": "string" } }
+}}"##,
+        )
+        .expect("Failed to parse server description");
+
+        let mut schema_generator = get_schema_generator(SchemaGeneratorConfig::default());
+        schema_generator
+            .add_actions_from_server_description_with_id("github", &github)
+            .expect("Failed to add github tools");
+        schema_generator
+            .add_actions_from_server_description_with_id("jira", &jira)
+            .expect("Failed to add jira tools");
+        schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator")
+    }
+
+    #[test]
+    fn test_generate_request_routes_to_server() {
+        let request_generator = github_and_jira_request_generator();
+
+        assert_eq!(request_generator.server_for_tool("search"), Some("github"));
+        assert_eq!(
+            request_generator.server_for_tool("create_issue"),
+            Some("jira")
+        );
+        assert_eq!(request_generator.server_for_tool("delete_repo"), None);
+
+        let policies = r#"permit(principal, action in Test::Action::"github", resource);"#;
+        let authorize = |input: &str| {
+            let input = Input::from_json_str(input).expect("Failed to parse input");
+            request_generator.is_authorized_from_strings(
+                &input, None, "user", "alice", "resource", "", policies, "[]",
+            )
+        };
+
+        // Each input is encoded using the type definitions of the server providing the tool
+        let response =
+            authorize(r#"{"params": {"tool": "search", "args": {"query": {"text": "cedar"}}}}"#)
+                .expect("Failed to authorize search");
+        assert!(response.is_allowed());
+        let response =
+            authorize(r#"{"params": {"tool": "create_issue", "args": {"summary": "bug"}}}"#)
+                .expect("Failed to authorize create_issue");
+        assert_eq!(response.decision, Decision::Deny);
+        assert_matches!(
+            authorize(r#"{"params": {"tool": "delete_repo", "args": {}}}"#),
+            Err(RequestGeneratorError::MCPValidationError(_))
+        );
+
+        // The generated entities include the action groups of the servers, whatever
+        // authorizer evaluates the request
+        let policies =
+            cedar_policy_core::parser::parse_policyset(policies).expect("Failed to parse policies");
+        let input = Input::from_json_str(
+            r#"{"params": {"tool": "search", "args": {"query": {"text": "cedar"}}}}"#,
+        )
+        .expect("Failed to parse input");
+        let (request, entities) = request_generator
+            .generate_request(
+                r#"Test::user::"alice""#.parse().unwrap(),
+                r#"Test::resource::"""#.parse().unwrap(),
+                Context::empty(),
+                Entities::new(),
+                &input,
+                None,
+            )
+            .expect("Failed to generate request");
+        assert_eq!(
+            Authorizer::new()
+                .is_authorized(request, &policies, &entities)
+                .decision,
+            Decision::Allow
+        );
+    }
+
+    #[test]
+    fn test_authorized_tools_keeps_type_definitions_per_server() {
+        let request_generator = github_and_jira_request_generator();
+        let policies = cedar_policy_core::parser::parse_policyset(
+            r#"permit(principal, action in Test::Action::"github", resource);"#,
+        )
+        .expect("Failed to parse policies");
+        let servers = request_generator
+            .authorized_tools(
+                &r#"Test::user::"alice""#.parse().unwrap(),
+                &r#"Test::resource::"""#.parse().unwrap(),
+                Context::empty(),
+                &Entities::new(),
+                &policies,
+            )
+            .expect("Failed to filter tools");
+
+        let [github, jira] = servers.as_slice() else {
+            panic!("Expected one entry per server: {servers:?}");
+        };
+        assert_eq!(github.server_id.as_deref(), Some("github"));
+        assert_eq!(jira.server_id.as_deref(), Some("jira"));
+        assert_eq!(
+            github
+                .tools
+                .tool_descriptions()
+                .map(ToolDescription::name)
+                .collect::<Vec<_>>(),
+            vec!["search"]
+        );
+        assert_eq!(jira.tools.tool_descriptions().count(), 0);
+
+        // Each server keeps its own `Query` type definition
+        let query = |server: &AuthorizedTools| {
+            let defs = server.tools.type_definitions().collect::<Vec<_>>();
+            let [def] = defs.as_slice() else {
+                panic!("Expected a single type definition: {defs:?}");
+            };
+            assert_eq!(def.name(), "Query");
+            def.property_type().clone()
+        };
+        assert_matches!(
+            query(github),
+            mcp_tools_sdk::description::PropertyType::Object { .. }
+        );
+        assert_matches!(
+            query(jira),
+            mcp_tools_sdk::description::PropertyType::String
+        );
+    }
+
+    #[test]
+    fn test_generate_resource_read_request() {
+        let resources = mcp_tools_sdk::description::ResourceList::from_json_str(
+            r#"{"result": {"resourceTemplates": [
+    {"uriTemplate": "file:///{+path}{?encoding}", "name": "read_file"},
+    {"uriTemplate": "users://{id}/profile", "name": "user_profile"}
+]}}"#,
+        )
+        .expect("Failed to parse resource templates");
+        let mut schema_generator = get_schema_generator(SchemaGeneratorConfig::default());
+        schema_generator
+            .add_actions_from_resource_templates(&resources)
+            .expect("Failed to add resource templates");
+        let request_generator = schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+
+        let policies = cedar_policy_core::parser::parse_policyset(
+            r#"permit(principal, action == Test::Action::"read_file", resource)
+when { context.input.path like "src/*" && !(context.input has encoding) };
+permit(principal, action == Test::Action::"user_profile", resource)
+when { context.uri == "users://alice%20b/profile" && context.input.id == "alice b" };"#,
+        )
+        .expect("Failed to parse policies");
+        let authorize = |uri: &str| {
+            let (request, entities) = request_generator.generate_resource_read_request(
+                r#"Test::user::"alice""#.parse().unwrap(),
+                r#"Test::resource::"server""#.parse().unwrap(),
+                std::iter::empty(),
+                Entities::new(),
+                &ResourceRead::new(uri.into()),
+            )?;
+            Ok::<_, RequestGeneratorError>(
+                Authorizer::new()
+                    .is_authorized(request, &policies, &entities)
+                    .decision,
+            )
+        };
+
+        assert_matches!(authorize("file:///src/main.rs"), Ok(Decision::Allow));
+        assert_matches!(
+            authorize("file:///src/main.rs?encoding=utf-8"),
+            Ok(Decision::Deny)
+        );
+        assert_matches!(authorize("file:///README.md"), Ok(Decision::Deny));
+        assert_matches!(authorize("users://alice%20b/profile"), Ok(Decision::Allow));
+        assert_matches!(
+            authorize("https://example.com"),
+            Err(RequestGeneratorError::NoMatchingResourceTemplate(_))
+        );
+        // Paths escaping `src/` through dot-segments match no template, rather than
+        // satisfying `like "src/*"`
+        for uri in [
+            "file:///src/../secrets/key.pem",
+            "file:///src/%2e%2e/secrets/key.pem",
+            "users://..%2Fadmin/profile",
+        ] {
+            assert_matches!(
+                authorize(uri),
+                Err(RequestGeneratorError::NoMatchingResourceTemplate(_)),
+                "{uri} should not match"
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_prompt_request() {
+        let prompts = mcp_tools_sdk::description::PromptList::from_json_str(
+            r#"[{"name": "code_review", "arguments": [
+    {"name": "code", "required": true},
+    {"name": "language"}
+]}]"#,
+        )
+        .expect("Failed to parse prompts");
+        let mut schema_generator = get_schema_generator(SchemaGeneratorConfig::default());
+        schema_generator
+            .add_actions_from_prompts(&prompts)
+            .expect("Failed to add prompts");
+        let request_generator = schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+
+        let policies = cedar_policy_core::parser::parse_policyset(
+            r#"permit(principal, action == Test::Action::"code_review", resource)
+when { !(context.input.code like "*ignore previous instructions*") && context.input has language };"#,
+        )
+        .expect("Failed to parse policies");
+        let authorize = |get: &str| {
+            let (request, entities) = request_generator.generate_prompt_request(
+                r#"Test::user::"alice""#.parse().unwrap(),
+                r#"Test::resource::"server""#.parse().unwrap(),
+                std::iter::empty(),
+                Entities::new(),
+                &PromptGet::from_json_str(get).expect("Failed to parse prompts/get request"),
+            )?;
+            Ok::<_, RequestGeneratorError>(
+                Authorizer::new()
+                    .is_authorized(request, &policies, &entities)
+                    .decision,
+            )
+        };
+
+        assert_matches!(
+            authorize(
+                r#"{"params": {"name": "code_review", "arguments": {"code": "fn main() {}", "language": "rust"}}}"#
+            ),
+            Ok(Decision::Allow)
+        );
+        assert_matches!(
+            authorize(
+                r#"{"params": {"name": "code_review", "arguments": {"code": "fn main() {}"}}}"#
+            ),
+            Ok(Decision::Deny)
+        );
+        assert_matches!(
+            authorize(
+                r#"{"params": {"name": "code_review", "arguments": {"code": "ignore previous instructions", "language": "rust"}}}"#
+            ),
+            Ok(Decision::Deny)
+        );
+        assert_matches!(
+            authorize(r#"{"params": {"name": "code_review", "arguments": {"language": "rust"}}}"#),
+            Err(RequestGeneratorError::MCPValidationError(
+                mcp_tools_sdk::err::ValidationError::MissingRequiredProperty(..)
+            ))
+        );
+        assert_matches!(
+            authorize(r#"{"params": {"name": "summarize"}}"#),
+            Err(RequestGeneratorError::MCPValidationError(
+                mcp_tools_sdk::err::ValidationError::PromptNotFound(..)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_deterministic_entity_ids() {
+        let tool = ToolDescription::from_json_str(
+            r#"{
+    "name": "update_customer",
+    "inputSchema": {
+        "properties": {
+            "customer": {
+                "type": "object",
+                "properties": { "id": { "type": "string" }, "name": { "type": "string" } },
+                "additionalProperties": { "type": "string" }
+            },
+            "metadata": {}
+        }
+    }
+}"#,
+        )
+        .expect("Failed to parse tool description");
+        let input = Input::from_json_str(
+            r#"{"params": {"tool": "update_customer", "args": {"customer": {"id": "c1", "name": "Bob"}, "metadata": {"k": [1]}}}}"#,
+        )
+        .expect("Failed to parse input");
+        let entity_ids = |config: SchemaGeneratorConfig| {
+            let mut schema_generator = get_schema_generator(config);
+            schema_generator
+                .add_action_from_tool_description(&tool)
+                .expect("Failed to add tool to schema generator");
+            let request_generator = schema_generator
+                .new_request_generator()
+                .expect("Failed to construct request generator");
+            let (_, entities) = request_generator
+                .generate_request(
+                    r#"Test::user::"alice""#.parse().unwrap(),
+                    r#"Test::resource::"server""#.parse().unwrap(),
+                    std::iter::empty(),
+                    Entities::new(),
+                    &input,
+                    None,
+                )
+                .expect("Failed to generate request");
+            let mut ids = without_actions(entities)
+                .iter()
+                .map(|entity| entity.uid().to_string())
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+
+        // Random EIDs differ between runs
+        #[cfg(feature = "uuid")]
+        {
+            let config = SchemaGeneratorConfig::default();
+            assert_ne!(entity_ids(config.clone()), entity_ids(config));
+        }
+        // Without the `uuid` feature, EIDs are always deterministic
+        #[cfg(not(feature = "uuid"))]
+        {
+            let config = SchemaGeneratorConfig::default();
+            assert_eq!(entity_ids(config.clone()), entity_ids(config));
+        }
+
+        // Content hashes do not
+        let config = SchemaGeneratorConfig::default().deterministic_entity_ids(true);
+        let ids = entity_ids(config.clone());
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids, entity_ids(config));
+
+        // Objects with a key property are named after it
+        let config = SchemaGeneratorConfig::default().entity_id_key(Some("id".into()));
+        assert_eq!(
+            entity_ids(config),
+            vec![r#"Test::update_customer::Input::customer::"c1""#]
+        );
+    }
+
+    #[test]
+    fn test_entity_id_key_shared_by_distinct_objects() {
+        let tool = ToolDescription::from_json_str(
+            r#"{
+    "name": "update_customers",
+    "inputSchema": {
+        "properties": {
+            "customers": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": { "id": { "type": "string" }, "name": { "type": "string" } }
+                }
+            }
+        }
+    }
+}"#,
+        )
+        .expect("Failed to parse tool description");
+        let input = Input::from_json_str(
+            r#"{"params": {"tool": "update_customers", "args": {"customers": [
+    {"id": "c1", "name": "Bob"},
+    {"id": "c1", "name": "Alice"},
+    {"id": "c2", "name": "Eve"},
+    {"id": "c2", "name": "Eve"}
+]}}}"#,
+        )
+        .expect("Failed to parse input");
+        let mut schema_generator =
+            get_schema_generator(SchemaGeneratorConfig::default().entity_id_key(Some("id".into())));
+        schema_generator
+            .add_action_from_tool_description(&tool)
+            .expect("Failed to add tool to schema generator");
+        let request_generator = schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+        let entity_ids = || {
+            let (_, entities) = request_generator
+                .generate_request(
+                    r#"Test::user::"alice""#.parse().unwrap(),
+                    r#"Test::resource::"server""#.parse().unwrap(),
+                    std::iter::empty(),
+                    Entities::new(),
+                    &input,
+                    None,
+                )
+                .expect("Failed to generate request");
+            let mut ids = without_actions(entities)
+                .iter()
+                .map(|entity| entity.uid().eid().escaped().to_string())
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+
+        // Equal objects share their entity, while distinct objects sharing a key value
+        // are named after their content
+        let ids = entity_ids();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.contains(&"c2".to_string()));
+        assert!(ids
+            .iter()
+            .filter(|id| *id != "c2")
+            .all(|id| id.len() == 32 && id.bytes().all(|b| b.is_ascii_hexdigit())));
+        assert_eq!(ids, entity_ids());
+    }
+
+    #[test]
+    fn test_resource_from_argument() {
+        let schema_stub = r#"namespace Test {
+    @mcp_principal("User")
+    entity user;
+
+    @mcp_resource("McpServer")
+    entity resource;
+
+    entity Folder in [Folder];
+
+    @mcp_resource_from("read_file.path")
+    entity File in [Folder];
+}"#;
+        let schema_stub =
+            cedar_policy_core::validator::json_schema::Fragment::from_cedarschema_str(
+                schema_stub,
+                cedar_policy_core::extensions::Extensions::all_available(),
+            )
+            .expect("Failed to parse schema")
+            .0;
+        let mut schema_generator =
+            SchemaGenerator::new(schema_stub).expect("Failed to create schema generator");
+        let server_description = ServerDescription::from_json_str(
+            r#"[
+    {"name": "read_file", "inputSchema": {"properties": {"path": {"type": "string"}}, "required": ["path"]}},
+    {"name": "list_files", "inputSchema": {"properties": {}}}
+]"#,
+        )
+        .expect("Failed to parse server description");
+        schema_generator
+            .add_actions_from_server_description(&server_description)
+            .expect("Failed to add tools to schema generator");
+        let request_generator = schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+
+        let principal: EntityUID = r#"Test::user::"alice""#.parse().unwrap();
+        let server: EntityUID = r#"Test::resource::"server""#.parse().unwrap();
+        let input = Input::from_json_str(
+            r#"{"params": {"tool": "read_file", "args": {"path": "/home/alice/notes.txt"}}}"#,
+        )
+        .expect("Failed to parse input");
+        let (request, _) = request_generator
+            .generate_request(
+                principal.clone(),
+                server.clone(),
+                std::iter::empty(),
+                Entities::new(),
+                &input,
+                None,
+            )
+            .expect("Failed to generate request");
+        assert_eq!(
+            request.resource().uid().unwrap().to_string(),
+            r#"Test::File::"/home/alice/notes.txt""#
+        );
+
+        // Tools without a mapped argument keep the supplied resource
+        let list_input = Input::from_json_str(r#"{"params": {"tool": "list_files", "args": {}}}"#)
+            .expect("Failed to parse input");
+        let (request, _) = request_generator
+            .generate_request(
+                principal.clone(),
+                server.clone(),
+                std::iter::empty(),
+                Entities::new(),
+                &list_input,
+                None,
+            )
+            .expect("Failed to generate request");
+        assert_eq!(request.resource().uid().unwrap(), &server);
+
+        // Policies can rely on the hierarchy of the supplied resource entities
+        let policies = cedar_policy_core::parser::parse_policyset(
+            r#"permit(principal, action == Test::Action::"read_file", resource in Test::Folder::"/home/alice");"#,
+        )
+        .expect("Failed to parse policies");
+        let entities = parse_entities(
+            r#"[
+    {"uid": {"type": "Test::File", "id": "/home/alice/notes.txt"}, "attrs": {}, "parents": [{"type": "Test::Folder", "id": "/home/alice"}]},
+    {"uid": {"type": "Test::Folder", "id": "/home/alice"}, "attrs": {}, "parents": []}
+]"#,
+        )
+        .expect("Failed to parse entities");
+        let response = request_generator
+            .is_authorized(
+                principal.clone(),
+                server.clone(),
+                std::iter::empty(),
+                entities,
+                &input,
+                None,
+                &policies,
+            )
+            .expect("Failed to authorize");
+        assert!(response.is_allowed());
+        let other_input = Input::from_json_str(
+            r#"{"params": {"tool": "read_file", "args": {"path": "/home/bob/notes.txt"}}}"#,
+        )
+        .expect("Failed to parse input");
+        let response = request_generator
+            .is_authorized(
+                principal.clone(),
+                server.clone(),
+                std::iter::empty(),
+                Entities::new(),
+                &other_input,
+                None,
+                &policies,
+            )
+            .expect("Failed to authorize");
+        assert!(!response.is_allowed());
+
+        // The resource is unknown until the tool is called
+        let tools = request_generator
+            .authorized_tools(
+                &principal,
+                &server,
+                Context::empty(),
+                &Entities::new(),
+                &policies,
+            )
+            .expect("Failed to filter tools");
+        assert_eq!(
+            tools
+                .iter()
+                .flat_map(|server| server.tools.tool_descriptions())
+                .map(|tool| tool.name().to_string())
+                .collect::<Vec<_>>(),
+            vec!["read_file"]
+        );
+    }
+
+    #[test]
+    fn test_tool_applicability() {
+        let schema_stub = r#"namespace Test {
+    @mcp_principal
+    entity Agent;
+
+    @mcp_principal
+    @mcp_tools("github_*")
+    entity User;
+
+    @mcp_resource
+    entity Server;
+}"#;
+        let schema_stub =
+            cedar_policy_core::validator::json_schema::Fragment::from_cedarschema_str(
+                schema_stub,
+                cedar_policy_core::extensions::Extensions::all_available(),
+            )
+            .expect("Failed to parse schema")
+            .0;
+        let mut schema_generator =
+            SchemaGenerator::new(schema_stub).expect("Failed to create schema generator");
+        let server_description = ServerDescription::from_json_str(
+            r#"[
+    {"name": "github_list_repos", "inputSchema": {"properties": {}}},
+    {"name": "search", "inputSchema": {"properties": {}}}
+]"#,
+        )
+        .expect("Failed to parse server description");
+        schema_generator
+            .add_actions_from_server_description(&server_description)
+            .expect("Failed to add tools to schema generator");
+        let request_generator = schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+
+        let policies =
+            cedar_policy_core::parser::parse_policyset("permit(principal, action, resource);")
+                .expect("Failed to parse policies");
+        let server: EntityUID = r#"Test::Server::"github""#.parse().unwrap();
+        let tool_names = |principal: &str| {
+            request_generator
+                .authorized_tools(
+                    &principal.parse().unwrap(),
+                    &server,
+                    Context::empty(),
+                    &Entities::new(),
+                    &policies,
+                )
+                .expect("Failed to filter tools")
+                .iter()
+                .flat_map(|server| server.tools.tool_descriptions())
+                .map(|tool| tool.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            tool_names(r#"Test::User::"alice""#),
+            vec!["github_list_repos"]
+        );
+        assert_eq!(
+            tool_names(r#"Test::Agent::"bot""#).len(),
+            2,
+            "Agents may call every tool"
+        );
+
+        // Requests pairing a tool with a principal type it does not apply to are invalid
+        let input = Input::from_json_str(r#"{"params": {"tool": "search", "args": {}}}"#)
+            .expect("Failed to parse input");
+        assert_matches!(
+            request_generator.generate_request(
+                r#"Test::User::"alice""#.parse().unwrap(),
+                server.clone(),
+                std::iter::empty(),
+                Entities::new(),
+                &input,
+                None,
+            ),
+            Err(RequestGeneratorError::MalformedRequest(_))
+        );
+        request_generator
+            .generate_request(
+                r#"Test::Agent::"bot""#.parse().unwrap(),
+                server.clone(),
+                std::iter::empty(),
+                Entities::new(),
+                &input,
+                None,
+            )
+            .expect("Failed to generate request");
+    }
+
+    #[test]
+    fn test_redact_output() {
+        let config = SchemaGeneratorConfig::default()
+            .include_outputs(true)
+            .redaction_actions(true)
+            .annotation_action_groups(true);
+        let mut schema_generator = get_schema_generator(config);
+        let tool = ToolDescription::from_json_str(
+            r##"{
+    "name": "get_customer",
+    "annotations": { "readOnlyHint": true },
+    "inputSchema": {
+        "properties": { "id": { "type": "string" } },
+        "required": ["id"]
+    },
+    "outputSchema": {
+        "properties": {
+            "name": { "type": "string" },
+            "ssn": { "type": "string" },
+            "email": { "type": "string" },
+            "address": { "$ref": "#/$defs/address" }
+        },
+        "required": ["name"],
+        "$defs": {
+            "address": {
+                "type": "object",
+                "properties": {
+                    "city": { "type": "string" },
+                    "zip": { "type": "string" }
+                },
+                "required": ["city"]
+            }
+        }
+    }
+}"##,
+        )
+        .expect("Failed to parse tool description");
+        schema_generator
+            .add_action_from_tool_description(&tool)
+            .expect("Failed to add tool to schema generator");
+        let request_generator = schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+
+        let input =
+            Input::from_json_str(r#"{"params": {"tool": "get_customer", "args": {"id": "c1"}}}"#)
+                .expect("Failed to parse input");
+        let output = Output::from_json_str(
+            r#"{"result": {"structuredContent": {"name": "Bob", "ssn": "123-45-6789", "email": "bob@example.com", "address": {"city": "Paris", "zip": "75001"}}}}"#,
+        )
+        .expect("Failed to parse output");
+        let policies = cedar_policy_core::parser::parse_policyset(
+            r#"
+permit(principal, action in Test::Action::"readOnlyTools", resource);
+permit(
+    principal,
+    action in [Test::Action::"get_customer.email", Test::Action::"get_customer.address"],
+    resource
+);
+permit(
+    principal == Test::user::"admin",
+    action in [Test::Action::"get_customer.ssn", Test::Action::"get_customer.address.zip"],
+    resource
+)
+when { context.output has ssn && context.input.id == "c1" };
+"#,
+        )
+        .expect("Failed to parse policies");
+        let redact = |principal: &str| {
+            request_generator
+                .redact_output(
+                    principal.parse().unwrap(),
+                    r#"Test::resource::"server""#.parse().unwrap(),
+                    std::iter::empty(),
+                    Entities::new(),
+                    &input,
+                    &output,
+                    &policies,
+                )
+                .expect("Failed to redact output")
+        };
+        let results = |output: &Output| {
+            let mut names = output
+                .get_results()
+                .map(|(name, _)| name.to_string())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+
+        let has_zip = |output: &Output| {
+            output
+                .get_result("address")
+                .expect("address should be disclosed")
+                .get_map()
+                .expect("address should be an object")
+                .contains_key("zip")
+        };
+
+        let redacted = redact(r#"Test::user::"admin""#);
+        assert_eq!(results(&redacted), vec!["address", "email", "name", "ssn"]);
+        assert!(has_zip(&redacted));
+        // Permitting the tool's action group does not disclose its redacted properties
+        let redacted = redact(r#"Test::user::"alice""#);
+        assert_eq!(results(&redacted), vec!["address", "email", "name"]);
+        assert!(!has_zip(&redacted));
+        // The redacted output still validates against the tool's output schema
+        tool.validate_output(&redacted, HashMap::new())
+            .expect("Redacted output should validate");
+
+        // Without a permit, every optional result is redacted
+        let redacted = request_generator
+            .redact_output(
+                r#"Test::user::"admin""#.parse().unwrap(),
+                r#"Test::resource::"server""#.parse().unwrap(),
+                std::iter::empty(),
+                Entities::new(),
+                &input,
+                &output,
+                &PolicySet::new(),
+            )
+            .expect("Failed to redact output");
+        assert_eq!(results(&redacted), vec!["name"]);
+    }
+
     /// Regression test: property names containing `::` must be rejected to prevent
     /// namespace injection. Previously these parsed as multi-component Cedar Names.
     #[test]
@@ -2884,6 +4771,7 @@ mod test {
                 "Union",
                 TypedValue::Union {
                     index: 0,
+                    tag: None,
                     value: Box::new(TypedValue::Bool(true)),
                 },
             ),
@@ -2898,8 +4786,13 @@ mod test {
         ];
 
         for (label, val) in vals {
-            let result =
-                request_generator.val_to_cedar(val, &type_defs, namespace.as_ref(), "foo::bar");
+            let result = request_generator.val_to_cedar(
+                val,
+                &type_defs,
+                namespace.as_ref(),
+                "foo::bar",
+                &KeyedEntityIds::default(),
+            );
             assert!(
                 result.is_err(),
                 "ty_name with `::` should be rejected in {label}"
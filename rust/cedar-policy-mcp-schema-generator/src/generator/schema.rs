@@ -15,9 +15,10 @@
  */
 
 use super::identifiers;
+use super::pattern::ToolPattern;
 use crate::{RequestGenerator, SchemaGeneratorError};
 
-use cedar_policy_core::ast::{Eid, Id, InternalName, Name, UnreservedId};
+use cedar_policy_core::ast::{Annotation, AnyId, Eid, Id, InternalName, Name, UnreservedId};
 use cedar_policy_core::est::Annotations;
 use cedar_policy_core::validator::{
     json_schema::{
@@ -28,7 +29,8 @@ use cedar_policy_core::validator::{
     RawName,
 };
 use mcp_tools_sdk::description::{
-    Parameters, Property, PropertyType, ServerDescription, ToolDescription,
+    Parameters, PromptDescription, PromptList, Property, PropertyType, PropertyTypeDef,
+    ResourceList, ResourceTemplateDescription, ServerDescription, ToolAnnotations, ToolDescription,
 };
 
 use nonempty::NonEmpty;
@@ -46,6 +48,18 @@ pub struct SchemaGeneratorConfig {
     pub(crate) flatten_namespaces: bool,
     pub(crate) numbers_as_decimal: bool,
     pub(crate) deduplicate_entity_types: bool,
+    pub(crate) annotation_action_groups: bool,
+    pub(crate) doc_annotations: bool,
+    pub(crate) redaction_actions: bool,
+    #[cfg_attr(
+        not(feature = "uuid"),
+        expect(
+            dead_code,
+            reason = "EIDs are always deterministic without the `uuid` feature."
+        )
+    )]
+    pub(crate) deterministic_entity_ids: bool,
+    pub(crate) entity_id_key: Option<SmolStr>,
 }
 
 impl SchemaGeneratorConfig {
@@ -138,6 +152,80 @@ impl SchemaGeneratorConfig {
             ..self
         }
     }
+
+    /// Updates config to set `annotation_action_groups` to `val` (default: false)
+    ///
+    /// If `annotation_action_groups` is set to `true`, then the schema generator adds
+    /// the action groups `readOnlyTools`, `destructiveTools`, `idempotentTools` and
+    /// `openWorldTools` to the schema, and the action of each tool is a member of the
+    /// groups matching the tool's MCP annotations (e.g., `readOnlyHint`). Absent hints
+    /// take the defaults of the MCP specification, so a tool without annotations is a
+    /// member of `destructiveTools` and `openWorldTools`. This allows writing policies
+    /// such as `forbid(principal, action in Action::"destructiveTools", resource);`.
+    pub fn annotation_action_groups(self, val: bool) -> Self {
+        Self {
+            annotation_action_groups: val,
+            ..self
+        }
+    }
+
+    /// Updates config to set `doc_annotations` to `val` (default: false)
+    ///
+    /// If `doc_annotations` is set to `true`, then the descriptions of MCP tools, of their
+    /// properties and of their type definitions are added as `@doc` annotations to the
+    /// corresponding actions, record attributes, and common or entity types of the schema.
+    pub fn doc_annotations(self, val: bool) -> Self {
+        Self {
+            doc_annotations: val,
+            ..self
+        }
+    }
+
+    /// Updates config to set `redaction_actions` to `val` (default: false)
+    ///
+    /// If `redaction_actions` and `include_outputs` are set to `true`, then an action
+    /// `"{tool}.{property}"` is generated for each optional property of a tool's output, and
+    /// an action `"{tool}.{property}.{nested}"` for each optional property of an object held
+    /// by an output property (recursively, but not through arrays, tuples or unions).
+    /// These actions share the context of the tool's action, are not members of its action
+    /// groups, and decide whether the property is disclosed in the tool's response
+    /// (see `RequestGenerator::redact_output`).
+    pub fn redaction_actions(self, val: bool) -> Self {
+        Self {
+            redaction_actions: val,
+            ..self
+        }
+    }
+
+    /// Updates config to set `deterministic_entity_ids` to `val` (default: false)
+    ///
+    /// The `RequestGenerator` represents objects (and values of unknown type) as entities.
+    /// By default, each of these entities is given a random (UUID v4) EID, so that the same
+    /// MCP request generates different entities each time. If `deterministic_entity_ids` is
+    /// set to `true`, then the EID of each entity is instead a hash of its entity type (which
+    /// is determined by where the value occurs in the tool's input or output) and of its value.
+    ///
+    /// Without the `uuid` feature, EIDs are always deterministic.
+    pub fn deterministic_entity_ids(self, val: bool) -> Self {
+        Self {
+            deterministic_entity_ids: val,
+            ..self
+        }
+    }
+
+    /// Updates config to set `entity_id_key` to `key` (default: None)
+    ///
+    /// If `entity_id_key` is set, then the entity representing an object whose property `key`
+    /// is a string or an integer takes the value of that property as its EID (e.g., the
+    /// `"id"` of a record returned by a tool). Other entities are named as configured by
+    /// `deterministic_entity_ids`. Distinct objects of the same type sharing a key are named
+    /// after a hash of their content instead, as with `deterministic_entity_ids`.
+    pub fn entity_id_key(self, key: Option<SmolStr>) -> Self {
+        Self {
+            entity_id_key: key,
+            ..self
+        }
+    }
 }
 
 impl Default for SchemaGeneratorConfig {
@@ -149,6 +237,80 @@ impl Default for SchemaGeneratorConfig {
             flatten_namespaces: false,
             numbers_as_decimal: false,
             deduplicate_entity_types: false,
+            annotation_action_groups: false,
+            doc_annotations: false,
+            redaction_actions: false,
+            deterministic_entity_ids: false,
+            entity_id_key: None,
+        }
+    }
+}
+
+/// Returns the name of the action deciding whether the output property at `path` of `tool` is
+/// disclosed, e.g., `"get_customer.address.zip"` for the path `["address", "zip"]`
+pub(super) fn redaction_action_name(tool: &str, path: &[&str]) -> SmolStr {
+    format!("{tool}.{}", path.join(".")).into()
+}
+
+/// Returns the paths of the output properties of `tool` that have a redaction action: its optional
+/// output properties and, recursively, the optional properties of the objects held by its output
+/// properties (but not by their arrays, tuples or unions). A path starts with the name of an output
+/// property followed by the names of nested object properties, and is paired with its property.
+fn redactable_output_paths<'a>(
+    tool: &'a ToolDescription,
+    server_type_defs: &[&'a PropertyTypeDef],
+) -> Vec<(Vec<&'a str>, &'a Property)> {
+    // Type definitions are resolved as when validating the tool's output
+    let type_defs = server_type_defs
+        .iter()
+        .copied()
+        .chain(tool.type_definitions())
+        .chain(tool.outputs().type_definitions())
+        .map(|type_def| (type_def.name(), type_def.property_type()))
+        .collect();
+    let mut collector = RedactablePaths {
+        type_defs,
+        path: Vec::new(),
+        expanding: Vec::new(),
+        paths: Vec::new(),
+    };
+    collector.add_properties(tool.outputs().properties());
+    collector.paths
+}
+
+/// Collects the paths of redactable output properties (see [`redactable_output_paths`])
+struct RedactablePaths<'a> {
+    type_defs: HashMap<&'a str, &'a PropertyType>,
+    /// The path of the object whose properties are visited
+    path: Vec<&'a str>,
+    /// The type definitions being expanded, which are not expanded again within themselves
+    expanding: Vec<&'a str>,
+    paths: Vec<(Vec<&'a str>, &'a Property)>,
+}
+
+impl<'a> RedactablePaths<'a> {
+    fn add_properties(&mut self, properties: impl Iterator<Item = &'a Property>) {
+        for property in properties {
+            self.path.push(property.name());
+            if !property.is_required() {
+                self.paths.push((self.path.clone(), property));
+            }
+            self.add_nested_properties(property.property_type());
+            self.path.pop();
+        }
+    }
+
+    fn add_nested_properties(&mut self, ty: &'a PropertyType) {
+        match ty.base_type() {
+            PropertyType::Object { properties, .. } => self.add_properties(properties.iter()),
+            PropertyType::Ref { name } if !self.expanding.contains(&name.as_str()) => {
+                if let Some(ty) = self.type_defs.get(name.as_str()).copied() {
+                    self.expanding.push(name);
+                    self.add_nested_properties(ty);
+                    self.expanding.pop();
+                }
+            }
+            _ => (),
         }
     }
 }
@@ -156,21 +318,90 @@ impl Default for SchemaGeneratorConfig {
 /// Returns `true` if the `PropertyType` is a primitive (leaf) type.
 fn is_primitive(pt: &PropertyType) -> bool {
     matches!(
-        pt,
+        pt.base_type(),
         PropertyType::Bool
             | PropertyType::Integer
             | PropertyType::Float
             | PropertyType::Number
             | PropertyType::String
             | PropertyType::Decimal
-            | PropertyType::Datetime
+            | PropertyType::Datetime { .. }
             | PropertyType::Duration
-            | PropertyType::IpAddr
+            | PropertyType::IpAddr { .. }
             | PropertyType::Null
             | PropertyType::Unknown
     )
 }
 
+/// Returns the attribute name and type name encoding the `index`th type of a union.
+///
+/// The types of a discriminated union are named after their discriminator value (e.g., `circle`)
+/// when it is a valid identifier. Otherwise they are named `typeChoice{index}` / `TypeChoice{index}`.
+/// Discriminator values of that form (e.g., `typeChoice1`) are not used as names either, so that
+/// they cannot collide with the name of another type of the union.
+pub(super) fn union_choice_names(index: usize, tag: Option<&SmolStr>) -> (SmolStr, SmolStr) {
+    let is_choice_name = |tag: &str| {
+        tag.strip_prefix("typeChoice")
+            .or_else(|| tag.strip_prefix("TypeChoice"))
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+    };
+    match tag {
+        Some(tag) if tag.parse::<UnreservedId>().is_ok() && !is_choice_name(tag) => {
+            (tag.clone(), tag.clone())
+        }
+        _ => (
+            format!("typeChoice{index}").to_smolstr(),
+            format!("TypeChoice{index}").to_smolstr(),
+        ),
+    }
+}
+
+/// Returns the annotations of an attribute of the given `PropertyType`.
+///
+/// Integer and boolean enums are encoded as `Long` and `Bool`, so their variants are recorded in
+/// an `@mcp_enum` annotation, e.g., `@mcp_enum("[1, 2, 3]")`. The annotation of an array of
+/// such enums (encoded as a `Set`) records the variants of its elements.
+fn annotations_of_property_type(pt: &PropertyType) -> Annotations {
+    let variants = match pt.base_type() {
+        PropertyType::Array { element_ty } => return annotations_of_property_type(element_ty),
+        PropertyType::IntegerEnum { variants } => {
+            variants.iter().map(ToString::to_string).collect::<Vec<_>>()
+        }
+        PropertyType::BoolEnum { variants } => {
+            variants.iter().map(ToString::to_string).collect::<Vec<_>>()
+        }
+        _ => return Annotations::new(),
+    };
+    let annotation =
+        Annotation::with_optional_value(Some(format!("[{}]", variants.join(", ")).into()), None);
+    Annotations(BTreeMap::from([(
+        identifiers::MCP_ENUM.clone(),
+        Some(annotation),
+    )]))
+}
+
+/// Returns whether a property of type `pt` is encoded as a common or entity type named after the
+/// property (i.e., whether it is an object or enum, or an array of these).
+fn defines_named_type(pt: &PropertyType) -> bool {
+    match pt.base_type() {
+        PropertyType::Object { .. } | PropertyType::Enum { .. } => true,
+        PropertyType::Array { element_ty } => defines_named_type(element_ty),
+        _ => false,
+    }
+}
+
+/// Returns the annotation action groups the action of a tool with the given annotations is a member of.
+fn annotation_action_groups(annotations: &ToolAnnotations) -> impl Iterator<Item = &'static str> {
+    [
+        (identifiers::READ_ONLY_TOOLS, annotations.is_read_only()),
+        (identifiers::DESTRUCTIVE_TOOLS, annotations.is_destructive()),
+        (identifiers::IDEMPOTENT_TOOLS, annotations.is_idempotent()),
+        (identifiers::OPEN_WORLD_TOOLS, annotations.is_open_world()),
+    ]
+    .into_iter()
+    .filter_map(|(group, member)| member.then_some(group))
+}
+
 // Returns `true` if the record is a "leaf" record, i.e. all its properties are of
 // primitive type and it doesn't have any `additionalProperties`
 fn is_leaf_record(p: &PropertyType) -> bool {
@@ -228,7 +459,7 @@ impl EntityTypeFingerprint {
             .map(|prop| {
                 (
                     prop.name().to_smolstr(),
-                    prop.property_type().clone(),
+                    prop.property_type().base_type().clone(),
                     prop.is_required(),
                 )
             })
@@ -264,6 +495,10 @@ impl DeduplicationMap {
 
     /// Resolve all duplicates: returns a map from fingerprint to placement info
     /// for entity types that appear in more than one namespace.
+    #[expect(
+        clippy::mutable_key_type,
+        reason = "Fingerprints only hold the base types of properties, without the compiled regexes of their `pattern`s."
+    )]
     fn resolve_duplicates(&self) -> HashMap<EntityTypeFingerprint, DeduplicatedEntityType> {
         self.occurrences
             .iter()
@@ -341,23 +576,116 @@ fn compute_lca(namespaces: &[Option<Name>]) -> Option<Name> {
     Name::try_from(InternalName::new(basename, path, None)).ok()
 }
 
+/// The input argument of a tool whose value is the id of the resource of the tool's requests
+/// (see the `@mcp_resource_from` annotation).
+#[derive(Debug, Clone)]
+pub(crate) struct ResourceArgument {
+    /// The (unqualified) entity type of the resource
+    pub(crate) entity_type: UnreservedId,
+    /// The name of the input argument
+    pub(crate) argument: SmolStr,
+}
+
+/// The MCP principal (or resource) entity types of the schema stub, along with the
+/// tools each of them applies to (see the `@mcp_tools` annotation).
+#[derive(Debug, Clone)]
+struct McpTypes(Vec<(RawName, Option<ToolPattern>)>);
+
+impl McpTypes {
+    /// Collect the entity types of `ns` annotated with `anno`
+    fn new(ns: &NamespaceDefinition<RawName>, anno: &AnyId) -> Result<Self, SchemaGeneratorError> {
+        let types = ns
+            .entity_types
+            .iter()
+            .filter(|(_, ty)| ty.annotations.0.contains_key(anno))
+            .map(|(tyname, ty)| {
+                let tools = ty
+                    .annotations
+                    .0
+                    .get(&*identifiers::MCP_TOOLS)
+                    .map(|anno| tool_pattern(anno.as_ref()))
+                    .transpose()?;
+                Ok((
+                    RawName::from_name(InternalName::unqualified_name(tyname.clone().into(), None)),
+                    tools,
+                ))
+            })
+            .collect::<Result<Vec<_>, SchemaGeneratorError>>()?;
+        Ok(Self(types))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// All the entity types, whatever the tools they apply to
+    fn all(&self) -> Vec<RawName> {
+        self.0.iter().map(|(ty, _)| ty.clone()).collect()
+    }
+
+    /// The entity types applying to the tool named `tool`
+    fn for_tool(&self, tool: &str) -> Vec<RawName> {
+        self.0
+            .iter()
+            .filter(|(_, tools)| tools.as_ref().is_none_or(|tools| tools.matches(tool)))
+            .map(|(ty, _)| ty.clone())
+            .collect()
+    }
+}
+
+/// The tools of an MCP server that were added to a `SchemaGenerator`.
+#[derive(Debug, Clone)]
+pub(crate) struct ServerTools {
+    /// The id the server was added with, if any.
+    pub(crate) id: Option<SmolStr>,
+    /// The namespace holding the server's shared type definitions and the namespaces of its tools.
+    pub(crate) namespace: Option<Name>,
+    pub(crate) description: ServerDescription,
+}
+
+impl ServerTools {
+    /// Whether this server provides a tool named `tool_name`
+    pub(crate) fn provides(&self, tool_name: &str) -> bool {
+        self.description
+            .tool_descriptions()
+            .any(|tool| tool.name() == tool_name)
+    }
+
+    /// Whether this server provides no tools
+    pub(crate) fn is_empty(&self) -> bool {
+        self.description.tool_descriptions().next().is_none()
+    }
+}
+
 /// A type that allows constructing a Cedar Schema (Fragment)
 /// from an input Cedar Schema Stub that defines the Cedar Type of
 /// MCP principals, MCP Resources, and common MCP Contects.
 ///
 /// The Generator can then be populated with a number of tool / server
 /// descriptions to auto-generate Cedar actions corresponding one-to-one
-/// with each tool description.
+/// with each tool description. Tools of several MCP servers can be combined
+/// in a single schema by adding each server's description under its own
+/// server id (see `add_actions_from_server_description_with_id`).
 #[derive(Debug, Clone)]
 pub struct SchemaGenerator {
     fragment: Fragment<RawName>,
     namespace: Option<Name>,
-    users: Vec<RawName>,
-    resources: Vec<RawName>,
+    users: McpTypes,
+    resources: McpTypes,
     contexts: BTreeMap<SmolStr, RawName>,
     actions: Option<Vec<ActionEntityUID<RawName>>>,
+    /// The action groups of the tools whose names match a pattern (see the `@mcp_action_group` annotation)
+    action_groups: Vec<(ActionEntityUID<RawName>, ToolPattern)>,
+    /// The arguments identifying the resource of each tool's requests, by tool name
+    resource_arguments: HashMap<SmolStr, ResourceArgument>,
     config: SchemaGeneratorConfig,
-    tools: ServerDescription,
+    /// The MCP servers whose tools have been added. Never empty: initially holds
+    /// a single server without an id and without tools.
+    servers: Vec<ServerTools>,
+    /// The MCP resource templates whose read actions have been added.
+    resource_templates: Vec<ResourceTemplateDescription>,
+    /// The MCP prompts whose actions have been added.
+    prompts: Vec<PromptDescription>,
     /// Resolved deduplication decisions, populated during pass 1
     /// (only when deduplicate_entity_types is true).
     /// Maps fingerprint → placement info for entity types that appear in multiple tools.
@@ -408,28 +736,12 @@ impl SchemaGenerator {
             return Err(SchemaGeneratorError::WrongNumberOfNamespaces);
         }
 
-        let users = ns
-            .entity_types
-            .iter()
-            .filter_map(|(tyname, ty)| {
-                ty.annotations.0.get(&*identifiers::MCP_PRINCIPAL).map(|_| {
-                    RawName::from_name(InternalName::unqualified_name(tyname.clone().into(), None))
-                })
-            })
-            .collect::<Vec<_>>();
+        let users = McpTypes::new(ns, &identifiers::MCP_PRINCIPAL)?;
         if users.is_empty() {
             return Err(SchemaGeneratorError::NoPrincipalTypes);
         }
 
-        let resources = ns
-            .entity_types
-            .iter()
-            .filter_map(|(tyname, ty)| {
-                ty.annotations.0.get(&*identifiers::MCP_RESOURCE).map(|_| {
-                    RawName::from_name(InternalName::unqualified_name(tyname.clone().into(), None))
-                })
-            })
-            .collect::<Vec<_>>();
+        let resources = McpTypes::new(ns, &identifiers::MCP_RESOURCE)?;
         if resources.is_empty() {
             return Err(SchemaGeneratorError::NoResourceTypes);
         }
@@ -469,6 +781,8 @@ impl SchemaGenerator {
             }))
             .collect();
 
+        let resource_arguments = resource_arguments(ns)?;
+
         let actions = ns
             .actions
             .iter()
@@ -486,21 +800,66 @@ impl SchemaGenerator {
             Some(actions)
         };
 
-        let fragment = if config.erase_annotations {
+        let action_groups = ns
+            .actions
+            .iter()
+            .filter_map(|(name, action)| {
+                action
+                    .annotations
+                    .0
+                    .get(&*identifiers::MCP_ACTION_GROUP)
+                    .map(|anno| {
+                        Ok((
+                            ActionEntityUID::new(None, name.clone()),
+                            tool_pattern(anno.as_ref())?,
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, SchemaGeneratorError>>()?;
+
+        let mut fragment = if config.erase_annotations {
             erase_mcp_annotations(schema_stub)
         } else {
             schema_stub
         };
 
+        if config.annotation_action_groups {
+            // Action groups defined by the stub are kept as is
+            #[expect(clippy::unwrap_used, reason = "Namespace exists by construction.")]
+            let ns = fragment.0.get_mut(&Some(namespace.clone())).unwrap();
+            for group in identifiers::ANNOTATION_ACTION_GROUPS {
+                ns.actions
+                    .entry(group.into())
+                    .or_insert_with(|| ActionType {
+                        attributes: None,
+                        applies_to: None,
+                        member_of: None,
+                        annotations: Annotations::new(),
+                        loc: None,
+                    });
+            }
+        }
+
+        let namespace = Some(namespace);
+        let servers = vec![ServerTools {
+            id: None,
+            namespace: namespace.clone(),
+            description: ServerDescription::new(Vec::new().into_iter(), HashMap::new()),
+        }];
+
         Ok(Self {
             fragment,
-            namespace: Some(namespace),
+            namespace,
             users,
             resources,
             contexts,
             actions,
+            action_groups,
+            resource_arguments,
             config,
-            tools: ServerDescription::new(Vec::new().into_iter(), HashMap::new()),
+            servers,
+            resource_templates: Vec::new(),
+            prompts: Vec::new(),
             resolved_dedup: None,
         })
     }
@@ -523,7 +882,10 @@ impl SchemaGenerator {
             cedar_policy_core::validator::ValidatorSchema::try_from(self.fragment.clone())?;
         Ok(RequestGenerator::new(
             self.config.clone(),
-            self.tools.clone(),
+            self.servers.clone(),
+            self.resource_templates.clone(),
+            PromptList::new(self.prompts.iter().cloned()),
+            self.resource_arguments.clone(),
             self.namespace.clone(),
             schema,
             self.resolved_dedup.clone(),
@@ -596,9 +958,9 @@ impl SchemaGenerator {
                     PropertyType::Integer => &identifiers::LONG_TYPE,
                     PropertyType::String => &identifiers::STRING_TYPE,
                     PropertyType::Decimal => &identifiers::DECIMAL_TYPE,
-                    PropertyType::Datetime => &identifiers::DATETIME_TYPE,
+                    PropertyType::Datetime { .. } => &identifiers::DATETIME_TYPE,
                     PropertyType::Duration => &identifiers::DURATION_TYPE,
-                    PropertyType::IpAddr => &identifiers::IPADDR_TYPE,
+                    PropertyType::IpAddr { .. } => &identifiers::IPADDR_TYPE,
                     _ => return false,
                 };
                 type_name == &**expected
@@ -683,15 +1045,13 @@ impl SchemaGenerator {
         dedup_map: &mut DeduplicationMap,
     ) {
         match property_type {
-            PropertyType::Enum { variants } => {
-                if !variants.is_empty() {
-                    if let Ok(base_name) = name.parse::<UnreservedId>() {
-                        let fingerprint = EntityTypeFingerprint::Enum {
-                            base_name,
-                            variants: variants.clone().into_iter().map(Eid::new).collect(),
-                        };
-                        dedup_map.record(fingerprint, namespace.clone());
-                    }
+            PropertyType::Enum { variants } if !variants.is_empty() => {
+                if let Ok(base_name) = name.parse::<UnreservedId>() {
+                    let fingerprint = EntityTypeFingerprint::Enum {
+                        base_name,
+                        variants: variants.clone().into_iter().map(Eid::new).collect(),
+                    };
+                    dedup_map.record(fingerprint, namespace.clone());
                 }
             }
             PropertyType::Object {
@@ -736,12 +1096,17 @@ impl SchemaGenerator {
                     dedup_map,
                 );
             }
-            PropertyType::Union { types } => {
+            PropertyType::Union {
+                types,
+                discriminator,
+                ..
+            } => {
                 if let Ok(union_name) = name.parse::<UnreservedId>() {
                     let child_ns: Name = union_name.into();
                     let child_ns = Some(child_ns.qualify_with_name(namespace.as_ref()));
                     for (i, ty) in types.iter().enumerate() {
-                        let variant_name = format!("TypeChoice{i}");
+                        let tag = discriminator.as_ref().and_then(|d| d.values().get(i));
+                        let (_, variant_name) = union_choice_names(i, tag);
                         Self::collect_enum_fingerprints_from_property_type(
                             &variant_name,
                             ty,
@@ -751,6 +1116,14 @@ impl SchemaGenerator {
                     }
                 }
             }
+            PropertyType::Constrained { ty, .. } => {
+                Self::collect_enum_fingerprints_from_property_type(
+                    name,
+                    ty.as_ref(),
+                    namespace,
+                    dedup_map,
+                );
+            }
             PropertyType::Tuple { types } => {
                 if let Ok(tuple_name) = name.parse::<UnreservedId>() {
                     let child_ns: Name = tuple_name.into();
@@ -773,20 +1146,35 @@ impl SchemaGenerator {
         &mut self,
         description: &ToolDescription,
     ) -> Result<(), SchemaGeneratorError> {
-        if self.tools.tool_descriptions().count() != 0 {
+        if self.has_tools() || self.has_server_ids() {
             return Err(SchemaGeneratorError::ServerDescriptionMerge);
         }
         // Keep a copy of state (schema, tools and dedup) in case we have an error
         let fragment = self.fragment.clone();
-        let tools = self.tools.clone();
+        let servers = self.servers.clone();
         let resolved_dedup = self.resolved_dedup.clone();
-        self.tools = ServerDescription::new(vec![description.clone()].into_iter(), HashMap::new());
-        match self.add_action_from_tool_description_inner(description, BTreeMap::new()) {
+        let namespace = self.namespace.clone();
+        self.servers = vec![ServerTools {
+            id: None,
+            namespace: namespace.clone(),
+            description: ServerDescription::new(
+                vec![description.clone()].into_iter(),
+                HashMap::new(),
+            ),
+        }];
+        let member_of = self.actions.clone();
+        match self.add_action_from_tool_description_inner(
+            description,
+            BTreeMap::new(),
+            &namespace,
+            &[],
+            member_of,
+        ) {
             Ok(_) => Ok(()),
             Err(e) => {
                 // Restore the values if adding the new tool failed.
                 self.fragment = fragment;
-                self.tools = tools;
+                self.servers = servers;
                 self.resolved_dedup = resolved_dedup;
                 Err(e)
             }
@@ -801,104 +1189,536 @@ impl SchemaGenerator {
     ) -> Result<(), SchemaGeneratorError> {
         // Keep a copy of generatore state (schema, tools, dedup map) in case we have an error
         let fragment = self.fragment.clone();
-        let tools = self.tools.clone();
+        let servers = self.servers.clone();
         let resolved_dedup = self.resolved_dedup.clone();
         match self.add_actions_from_server_description_inner(description) {
             Ok(_) => Ok(()),
             Err(e) => {
                 // Restore the cloned values
                 self.fragment = fragment;
-                self.tools = tools;
+                self.servers = servers;
                 self.resolved_dedup = resolved_dedup;
                 Err(e)
             }
         }
     }
 
-    fn add_actions_from_server_description_inner(
+    /// Add a new action to the generated Cedar Schema for each tool description
+    /// within the `ServerDescription` of the MCP server identified by `server_id`.
+    ///
+    /// Unlike `add_actions_from_server_description`, this can be called once per MCP server
+    /// to generate a single schema covering the tools of several servers. The server's shared
+    /// type definitions and the namespaces of its tools are placed in the sub-namespace
+    /// `server_id` of the schema's namespace, and each of its tools' actions is a member of an
+    /// action group `server_id` (e.g., `MyNamespace::Action::"github"`), allowing policies to
+    /// refer to all tools of a server at once.
+    ///
+    /// Fails if `server_id` is not a valid Cedar identifier, if a server with the same id was
+    /// already added, or if one of the server's tools has the same name as a tool of an already
+    /// added server (as each tool corresponds to a single Cedar action). Cannot be combined with
+    /// tools added without a server id.
+    pub fn add_actions_from_server_description_with_id(
         &mut self,
+        server_id: &str,
         description: &ServerDescription,
     ) -> Result<(), SchemaGeneratorError> {
-        if self.tools.tool_descriptions().count() != 0 {
-            return Err(SchemaGeneratorError::ServerDescriptionMerge);
+        // Keep a copy of generator state (schema, tools, dedup map) in case we have an error
+        let fragment = self.fragment.clone();
+        let servers = self.servers.clone();
+        let resolved_dedup = self.resolved_dedup.clone();
+        match self.add_actions_from_server_description_with_id_inner(server_id, description) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                // Restore the cloned values
+                self.fragment = fragment;
+                self.servers = servers;
+                self.resolved_dedup = resolved_dedup;
+                Err(e)
+            }
         }
-        self.tools = description.clone();
-
-        // Clone once and reuse to avoid borrow issues
-        let namespace = self.namespace.clone();
+    }
 
-        // Populate a map from type ref names to fully qualified type name
-        // This makes type resolution simpler and will allow for mutually recursive type defs
-        let mut common_types = BTreeMap::new();
-        for type_def in description.type_definitions() {
-            let type_name = CommonTypeId::new(type_def.name().parse()?)?;
-            let type_name = get_refname(&namespace, &type_name);
-            let ref_name = type_def.name().to_smolstr();
-            common_types.insert(ref_name, type_name);
+    /// Add a new action to the generated Cedar Schema for each resource template within
+    /// the `ResourceList`, corresponding to `resources/read` requests of the resources
+    /// matching the template. Concrete resources (i.e., `resources/list` entries) are ignored.
+    ///
+    /// Each action is named after its template, and its context holds the URI of the read
+    /// resource as `context.uri` and the values of the template's variables as the `String`
+    /// attributes of `context.input` (e.g., `context.input.path` for `file:///{+path}`).
+    /// Variables that some matching URIs do not define (e.g., those of `{?var}` expressions)
+    /// are optional attributes.
+    ///
+    /// Fails if a resource template has the same name as an action of the schema (e.g., the
+    /// action of a tool or of another resource template).
+    pub fn add_actions_from_resource_templates(
+        &mut self,
+        resources: &ResourceList,
+    ) -> Result<(), SchemaGeneratorError> {
+        // Keep a copy of generator state (schema, resource templates) in case we have an error
+        let fragment = self.fragment.clone();
+        let resource_templates = self.resource_templates.clone();
+        match resources
+            .resource_templates()
+            .try_for_each(|template| self.add_action_from_resource_template(template))
+        {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                // Restore the cloned values
+                self.fragment = fragment;
+                self.resource_templates = resource_templates;
+                Err(e)
+            }
         }
+    }
 
-        // Preemptively add all typedefs as commontypes
-        for type_def in description.type_definitions() {
-            let ty_name = type_def.name().parse::<UnreservedId>()?;
-            let ty = self.cedar_type_from_property_type(
-                &namespace,
-                ty_name.clone(),
-                type_def.property_type(),
-                &common_types,
-            )?;
-            self.add_commontype(&namespace, ty, ty_name, true)?;
+    /// Add a new action to the generated Cedar Schema for each prompt within the
+    /// `PromptList`, corresponding to `prompts/get` requests of the prompt.
+    ///
+    /// Each action is named after its prompt, and its context holds the arguments of the
+    /// prompt as the `String` attributes of `context.input`, whose type is the common type
+    /// `{prompt}Input` (as for the inputs of a tool). Arguments that are not required are
+    /// optional attributes.
+    ///
+    /// Fails if a prompt has the same name as an action of the schema (e.g., the action of
+    /// a tool, of a resource template or of another prompt).
+    pub fn add_actions_from_prompts(
+        &mut self,
+        prompts: &PromptList,
+    ) -> Result<(), SchemaGeneratorError> {
+        // Keep a copy of generator state (schema, prompts) in case we have an error
+        let fragment = self.fragment.clone();
+        let added_prompts = self.prompts.clone();
+        match prompts
+            .prompts()
+            .try_for_each(|prompt| self.add_action_from_prompt(prompt))
+        {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                // Restore the cloned values
+                self.fragment = fragment;
+                self.prompts = added_prompts;
+                Err(e)
+            }
         }
+    }
 
-        self.deduplicate_entities(description)?;
+    /// Whether any tools have been added to this generator
+    fn has_tools(&self) -> bool {
+        self.servers.iter().any(|server| !server.is_empty())
+    }
 
-        for tool_description in description.tool_descriptions() {
-            self.add_action_from_tool_description_inner(tool_description, common_types.clone())?
-        }
-        Ok(())
+    /// Whether any server descriptions have been added with a server id
+    fn has_server_ids(&self) -> bool {
+        self.servers.iter().any(|server| server.id.is_some())
     }
 
-    /// Scans all tool descriptions for equivalent enum entity types and places
-    /// shared definitions in the lowest common ancestor namespace.
-    /// Must be called before individual tool actions are processed.
-    fn deduplicate_entities(
+    fn add_actions_from_server_description_inner(
         &mut self,
         description: &ServerDescription,
     ) -> Result<(), SchemaGeneratorError> {
-        if !self.config.deduplicate_entity_types {
-            return Ok(());
+        if self.has_tools() || self.has_server_ids() {
+            return Err(SchemaGeneratorError::ServerDescriptionMerge);
         }
+        let namespace = self.namespace.clone();
+        self.servers = vec![ServerTools {
+            id: None,
+            namespace: namespace.clone(),
+            description: description.clone(),
+        }];
+        let member_of = self.actions.clone();
+        self.add_server_actions(description, &namespace, member_of.as_deref())
+    }
 
-        let mut dedup_map = DeduplicationMap::default();
-
-        for tool_description in description.tool_descriptions() {
-            let tool_ns: Name = tool_description.name().parse()?;
-            let tool_ns = tool_ns.qualify_with_name(self.namespace.as_ref());
-            let input_ns = Some(identifiers::INPUT_NAME.qualify_with_name(Some(&tool_ns)));
-
-            Self::collect_enum_fingerprints(tool_description.inputs(), &input_ns, &mut dedup_map);
+    fn add_actions_from_server_description_with_id_inner(
+        &mut self,
+        server_id: &str,
+        description: &ServerDescription,
+    ) -> Result<(), SchemaGeneratorError> {
+        let server_name: UnreservedId = server_id.parse()?;
+        if self
+            .servers
+            .iter()
+            .any(|server| server.id.is_none() && !server.is_empty())
+        {
+            return Err(SchemaGeneratorError::ServerDescriptionMerge);
+        }
+        // Drop the (empty) server without an id the generator starts with
+        self.servers.retain(|server| server.id.is_some());
 
-            if self.config.include_outputs {
-                let output_ns = Some(identifiers::OUTPUT_NAME.qualify_with_name(Some(&tool_ns)));
-                Self::collect_enum_fingerprints(
-                    tool_description.outputs(),
-                    &output_ns,
-                    &mut dedup_map,
-                );
+        if self
+            .servers
+            .iter()
+            .any(|server| server.id.as_deref() == Some(server_id))
+        {
+            return Err(SchemaGeneratorError::DuplicateServerId(server_id.into()));
+        }
+        for tool in description.tool_descriptions() {
+            if let Some(other) = self
+                .servers
+                .iter()
+                .find(|server| server.provides(tool.name()))
+            {
+                return Err(SchemaGeneratorError::tool_name_collision(
+                    tool.name().into(),
+                    server_id.into(),
+                    other.id.clone().unwrap_or_default(),
+                ));
             }
+        }
 
-            for type_def in tool_description.type_definitions() {
-                if let PropertyType::Enum { variants } = type_def.property_type() {
-                    if !variants.is_empty() {
-                        if let Ok(base_name) = type_def.name().parse::<UnreservedId>() {
-                            let fingerprint = EntityTypeFingerprint::Enum {
-                                base_name,
-                                variants: variants.clone().into_iter().map(Eid::new).collect(),
-                            };
-                            dedup_map.record(fingerprint, Some(tool_ns.clone()));
-                        }
-                    }
-                }
-            }
+        // The server's action group shares the action namespace with the actions of all tools
+        let action_names = self
+            .fragment
+            .0
+            .get(&self.namespace)
+            .map(|nsdef| nsdef.actions.keys().cloned().collect::<HashSet<_>>())
+            .unwrap_or_default();
+        if action_names.contains(server_id) {
+            return Err(SchemaGeneratorError::conflicting_name(server_id.into()));
+        }
+        if let Some(tool) = description
+            .tool_descriptions()
+            .find(|tool| tool.name() == server_id || self.is_server_id(tool.name()))
+        {
+            return Err(SchemaGeneratorError::conflicting_name(tool.name().into()));
+        }
+
+        let action_group = ActionType {
+            attributes: None,
+            applies_to: None,
+            member_of: None,
+            annotations: Annotations::new(),
+            loc: None,
+        };
+        #[expect(clippy::unwrap_used, reason = "Namespace exists by construction.")]
+        self.fragment
+            .0
+            .get_mut(&self.namespace)
+            .unwrap()
+            .actions
+            .insert(server_id.into(), action_group);
+
+        let server_ns: Name = server_name.into();
+        let namespace = Some(server_ns.qualify_with_name(self.namespace.as_ref()));
+        self.servers.push(ServerTools {
+            id: Some(server_id.into()),
+            namespace: namespace.clone(),
+            description: description.clone(),
+        });
+
+        let member_of = self
+            .actions
+            .iter()
+            .flatten()
+            .cloned()
+            .chain(std::iter::once(ActionEntityUID::new(
+                None,
+                server_id.into(),
+            )))
+            .collect::<Vec<_>>();
+        self.add_server_actions(description, &namespace, Some(&member_of))
+    }
+
+    /// Whether a server description has been added with the id `name`
+    fn is_server_id(&self, name: &str) -> bool {
+        self.servers
+            .iter()
+            .any(|server| server.id.as_deref() == Some(name))
+    }
+
+    /// Add the read action of the resource template `template`
+    fn add_action_from_resource_template(
+        &mut self,
+        template: &ResourceTemplateDescription,
+    ) -> Result<(), SchemaGeneratorError> {
+        let action_name = template.name().to_smolstr();
+        if self
+            .fragment
+            .0
+            .get(&self.namespace)
+            .is_some_and(|nsdef| nsdef.actions.contains_key(&action_name))
+        {
+            return Err(SchemaGeneratorError::conflicting_name(action_name));
+        }
+
+        let string_attribute = |required| TypeOfAttribute {
+            ty: Type::Type {
+                ty: TypeVariant::EntityOrCommon {
+                    type_name: identifiers::STRING_TYPE.clone(),
+                },
+                loc: None,
+            },
+            annotations: Annotations::new(),
+            required,
+        };
+        let variables = template
+            .uri_template()
+            .variables()
+            .map(|var| (var.name().to_smolstr(), string_attribute(var.is_required())))
+            .collect();
+
+        let mut ctx_attrs = self
+            .contexts
+            .iter()
+            .map(|(key, ty_name)| {
+                (
+                    key.clone(),
+                    TypeOfAttribute {
+                        ty: Type::Type {
+                            ty: TypeVariant::EntityOrCommon {
+                                type_name: ty_name.clone(),
+                            },
+                            loc: None,
+                        },
+                        annotations: Annotations::new(),
+                        required: true,
+                    },
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        ctx_attrs.insert("uri".to_smolstr(), string_attribute(true));
+        ctx_attrs.insert(
+            "input".to_smolstr(),
+            TypeOfAttribute {
+                ty: Type::Type {
+                    ty: TypeVariant::Record(RecordType {
+                        attributes: variables,
+                        additional_attributes: false,
+                    }),
+                    loc: None,
+                },
+                annotations: Annotations::new(),
+                required: true,
+            },
+        );
+
+        let action = ActionType {
+            attributes: None,
+            applies_to: Some(ApplySpec {
+                resource_types: self.resources.all(),
+                principal_types: self.users.all(),
+                context: AttributesOrContext(Type::Type {
+                    ty: TypeVariant::Record(RecordType {
+                        attributes: ctx_attrs,
+                        additional_attributes: false,
+                    }),
+                    loc: None,
+                }),
+            }),
+            member_of: self.actions.clone(),
+            annotations: self.doc_annotation(template.description()),
+            loc: None,
+        };
+
+        #[expect(clippy::unwrap_used, reason = "Namespace exists by construction.")]
+        self.fragment
+            .0
+            .get_mut(&self.namespace)
+            .unwrap()
+            .actions
+            .insert(action_name, action);
+        self.resource_templates.push(template.clone());
+        Ok(())
+    }
+
+    /// Add the action of the prompt `prompt`
+    fn add_action_from_prompt(
+        &mut self,
+        prompt: &PromptDescription,
+    ) -> Result<(), SchemaGeneratorError> {
+        let action_name = prompt.name().to_smolstr();
+        if self
+            .fragment
+            .0
+            .get(&self.namespace)
+            .is_some_and(|nsdef| nsdef.actions.contains_key(&action_name))
+        {
+            return Err(SchemaGeneratorError::conflicting_name(action_name));
+        }
+
+        let mut ctx_attrs = self
+            .contexts
+            .iter()
+            .map(|(key, ty_name)| {
+                (
+                    key.clone(),
+                    TypeOfAttribute {
+                        ty: Type::Type {
+                            ty: TypeVariant::EntityOrCommon {
+                                type_name: ty_name.clone(),
+                            },
+                            loc: None,
+                        },
+                        annotations: Annotations::new(),
+                        required: true,
+                    },
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        // The arguments are encoded like the inputs of a tool, as a `promptnameInput` type
+        let parent_namespace = self.namespace.clone();
+        let arguments =
+            self.record_from_parameters(&prompt.inputs(), &parent_namespace, BTreeMap::new())?;
+        let input_type = Type::Type {
+            ty: TypeVariant::Record(arguments),
+            loc: None,
+        };
+        let prompt_input_ty_name: UnreservedId =
+            format!("{}Input", prompt.name()).as_str().parse()?;
+        self.add_commontype(
+            &parent_namespace,
+            input_type,
+            prompt_input_ty_name.clone(),
+            true,
+        )?;
+        ctx_attrs.insert(
+            "input".to_smolstr(),
+            TypeOfAttribute {
+                ty: Type::CommonTypeRef {
+                    type_name: RawName::new_from_unreserved(prompt_input_ty_name, None),
+                    loc: None,
+                },
+                annotations: Annotations::new(),
+                required: true,
+            },
+        );
+
+        let action = ActionType {
+            attributes: None,
+            applies_to: Some(ApplySpec {
+                resource_types: self.resources.all(),
+                principal_types: self.users.all(),
+                context: AttributesOrContext(Type::Type {
+                    ty: TypeVariant::Record(RecordType {
+                        attributes: ctx_attrs,
+                        additional_attributes: false,
+                    }),
+                    loc: None,
+                }),
+            }),
+            member_of: self.actions.clone(),
+            annotations: self.doc_annotation(prompt.description()),
+            loc: None,
+        };
+
+        #[expect(clippy::unwrap_used, reason = "Namespace exists by construction.")]
+        self.fragment
+            .0
+            .get_mut(&self.namespace)
+            .unwrap()
+            .actions
+            .insert(action_name, action);
+        self.prompts.push(prompt.clone());
+        Ok(())
+    }
+
+    /// Add the actions (and types) for the tools of `description`, placing the server's
+    /// shared type definitions and the namespaces of its tools in `namespace`.
+    /// Each action is a member of the action groups `member_of`.
+    #[expect(
+        clippy::ref_option,
+        reason = "Consistent with the rest of the codebase's namespace parameter style."
+    )]
+    fn add_server_actions(
+        &mut self,
+        description: &ServerDescription,
+        namespace: &Option<Name>,
+        member_of: Option<&[ActionEntityUID<RawName>]>,
+    ) -> Result<(), SchemaGeneratorError> {
+        self.add_namespace(namespace.clone());
+
+        // Populate a map from type ref names to fully qualified type name
+        // This makes type resolution simpler and will allow for mutually recursive type defs
+        let mut common_types = BTreeMap::new();
+        for type_def in description.type_definitions() {
+            let type_name = CommonTypeId::new(type_def.name().parse()?)?;
+            let type_name = get_refname(namespace, &type_name);
+            let ref_name = type_def.name().to_smolstr();
+            common_types.insert(ref_name, type_name);
+        }
+
+        // Preemptively add all typedefs as commontypes
+        for type_def in description.type_definitions() {
+            let ty_name = type_def.name().parse::<UnreservedId>()?;
+            let ty = self.cedar_type_from_property_type(
+                namespace,
+                ty_name.clone(),
+                type_def.property_type(),
+                &common_types,
+            )?;
+            self.add_commontype(namespace, ty, ty_name.clone(), true)?;
+            self.add_doc_annotation(namespace, ty_name, type_def.description());
+        }
+
+        self.deduplicate_entities(description, namespace)?;
+
+        let server_type_defs = description.type_definitions().collect::<Vec<_>>();
+        for tool_description in description.tool_descriptions() {
+            self.add_action_from_tool_description_inner(
+                tool_description,
+                common_types.clone(),
+                namespace,
+                &server_type_defs,
+                member_of.map(<[_]>::to_vec),
+            )?
+        }
+        self.drop_namespace_if_empty(namespace);
+        Ok(())
+    }
+
+    /// Scans all tool descriptions for equivalent enum entity types and places
+    /// shared definitions in the lowest common ancestor namespace.
+    /// Must be called before individual tool actions are processed.
+    /// `namespace` is the namespace containing the namespaces of the server's tools.
+    #[expect(
+        clippy::ref_option,
+        reason = "Consistent with the rest of the codebase's namespace parameter style."
+    )]
+    #[expect(
+        clippy::mutable_key_type,
+        reason = "Fingerprints only hold the base types of properties, without the compiled regexes of their `pattern`s."
+    )]
+    fn deduplicate_entities(
+        &mut self,
+        description: &ServerDescription,
+        namespace: &Option<Name>,
+    ) -> Result<(), SchemaGeneratorError> {
+        if !self.config.deduplicate_entity_types {
+            return Ok(());
+        }
+
+        let mut dedup_map = DeduplicationMap::default();
+
+        for tool_description in description.tool_descriptions() {
+            let tool_ns: Name = tool_description.name().parse()?;
+            let tool_ns = tool_ns.qualify_with_name(namespace.as_ref());
+            let input_ns = Some(identifiers::INPUT_NAME.qualify_with_name(Some(&tool_ns)));
+
+            Self::collect_enum_fingerprints(tool_description.inputs(), &input_ns, &mut dedup_map);
+
+            if self.config.include_outputs {
+                let output_ns = Some(identifiers::OUTPUT_NAME.qualify_with_name(Some(&tool_ns)));
+                Self::collect_enum_fingerprints(
+                    tool_description.outputs(),
+                    &output_ns,
+                    &mut dedup_map,
+                );
+            }
+
+            for type_def in tool_description.type_definitions() {
+                if let PropertyType::Enum { variants } = type_def.property_type().base_type() {
+                    if !variants.is_empty() {
+                        if let Ok(base_name) = type_def.name().parse::<UnreservedId>() {
+                            let fingerprint = EntityTypeFingerprint::Enum {
+                                base_name,
+                                variants: variants.clone().into_iter().map(Eid::new).collect(),
+                            };
+                            dedup_map.record(fingerprint, Some(tool_ns.clone()));
+                        }
+                    }
+                }
+            }
         }
 
         let mut resolved = dedup_map.resolve_duplicates();
@@ -1023,17 +1843,79 @@ impl SchemaGenerator {
             placed.insert(fingerprint.clone(), dedup_info.clone());
         }
 
-        self.resolved_dedup = Some(placed);
+        // Servers are deduplicated separately, so keep the decisions made for earlier servers
+        self.resolved_dedup
+            .get_or_insert_with(HashMap::new)
+            .extend(placed);
         Ok(())
     }
 
+    #[expect(
+        clippy::ref_option,
+        reason = "Consistent with the rest of the codebase's namespace parameter style."
+    )]
     fn add_action_from_tool_description_inner(
         &mut self,
         description: &ToolDescription,
         mut common_types: BTreeMap<SmolStr, RawName>,
+        server_namespace: &Option<Name>,
+        server_type_defs: &[&PropertyTypeDef],
+        member_of: Option<Vec<ActionEntityUID<RawName>>>,
     ) -> Result<(), SchemaGeneratorError> {
         let namespace: Name = description.name().parse()?;
-        let namespace = Some(namespace.qualify_with_name(self.namespace.as_ref()));
+        let namespace = Some(namespace.qualify_with_name(server_namespace.as_ref()));
+        if self
+            .resource_templates
+            .iter()
+            .any(|template| template.name() == description.name())
+            || self
+                .prompts
+                .iter()
+                .any(|prompt| prompt.name() == description.name())
+        {
+            return Err(SchemaGeneratorError::conflicting_name(
+                description.name().into(),
+            ));
+        }
+        let member_of = if self.config.annotation_action_groups {
+            if identifiers::ANNOTATION_ACTION_GROUPS.contains(&description.name()) {
+                return Err(SchemaGeneratorError::conflicting_name(
+                    description.name().into(),
+                ));
+            }
+            Some(
+                member_of
+                    .into_iter()
+                    .flatten()
+                    .chain(
+                        annotation_action_groups(description.annotations())
+                            .map(|group| ActionEntityUID::new(None, group.into())),
+                    )
+                    .collect(),
+            )
+        } else {
+            member_of
+        };
+        let pattern_groups = self
+            .action_groups
+            .iter()
+            .filter(|(group, pattern)| {
+                pattern.matches(description.name())
+                    && !member_of.iter().flatten().any(|action| action == group)
+            })
+            .map(|(group, _)| group.clone())
+            .collect::<Vec<_>>();
+        let member_of = if pattern_groups.is_empty() {
+            member_of
+        } else {
+            Some(
+                member_of
+                    .into_iter()
+                    .flatten()
+                    .chain(pattern_groups)
+                    .collect(),
+            )
+        };
         self.add_namespace(namespace.clone());
 
         // Populate a map from type ref names to fully qualified type name
@@ -1056,7 +1938,8 @@ impl SchemaGenerator {
                 type_def.property_type(),
                 &common_types,
             )?;
-            self.add_commontype(&namespace, ty, ty_name, true)?;
+            self.add_commontype(&namespace, ty, ty_name.clone(), true)?;
+            self.add_doc_annotation(&namespace, ty_name, type_def.description());
         }
 
         // Shared Common (input Context Types)
@@ -1150,11 +2033,49 @@ impl SchemaGenerator {
             );
         }
 
+        // The resource of the tool's requests may be identified by one of its arguments
+        let resource_types = match self.resource_arguments.get(description.name()) {
+            Some(resource) => {
+                let is_valid = description.inputs().properties().any(|property| {
+                    property.name() == resource.argument
+                        && property.is_required()
+                        && matches!(
+                            property.property_type().base_type(),
+                            PropertyType::String
+                                | PropertyType::Integer
+                                | PropertyType::Enum { .. }
+                        )
+                });
+                if !is_valid {
+                    return Err(SchemaGeneratorError::invalid_resource_argument(
+                        description.name().into(),
+                        resource.argument.clone(),
+                    ));
+                }
+                vec![RawName::new_from_unreserved(
+                    resource.entity_type.clone(),
+                    None,
+                )]
+            }
+            None => self.resources.for_tool(description.name()),
+        };
+        if resource_types.is_empty() {
+            return Err(SchemaGeneratorError::NoResourceTypesForTool(
+                description.name().into(),
+            ));
+        }
+        let principal_types = self.users.for_tool(description.name());
+        if principal_types.is_empty() {
+            return Err(SchemaGeneratorError::NoPrincipalTypesForTool(
+                description.name().into(),
+            ));
+        }
+
         let action = ActionType {
             attributes: None,
             applies_to: Some(ApplySpec {
-                resource_types: self.resources.clone(),
-                principal_types: self.users.clone(),
+                resource_types,
+                principal_types,
                 context: AttributesOrContext(Type::Type {
                     ty: TypeVariant::Record(RecordType {
                         attributes: ctx_attrs,
@@ -1163,18 +2084,44 @@ impl SchemaGenerator {
                     loc: None,
                 }),
             }),
-            member_of: self.actions.clone(),
-            annotations: Annotations::new(),
+            member_of,
+            annotations: self.doc_annotation(description.description()),
             loc: None,
         };
 
+        // One action per (nested) optional output property to decide whether it is redacted.
+        // Redaction actions are not members of the tool's action groups, so that permitting
+        // a group of tools does not disclose their redacted properties.
+        let redaction_actions = if self.config.include_outputs && self.config.redaction_actions {
+            redactable_output_paths(description, server_type_defs)
+                .into_iter()
+                .map(|(path, property)| {
+                    let action = ActionType {
+                        attributes: None,
+                        applies_to: action.applies_to.clone(),
+                        member_of: None,
+                        annotations: self.doc_annotation(property.description()),
+                        loc: None,
+                    };
+                    (redaction_action_name(description.name(), &path), action)
+                })
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
         #[expect(clippy::unwrap_used, reason = "Namespace exists by construction.")]
-        self.fragment
-            .0
-            .get_mut(&self.namespace)
-            .unwrap()
-            .actions
-            .insert(description.name().to_smolstr(), action);
+        let actions = &mut self.fragment.0.get_mut(&self.namespace).unwrap().actions;
+        // Property names containing `.` may give two redaction actions the same name
+        let mut redaction_names = HashSet::new();
+        if let Some((name, _)) = redaction_actions
+            .iter()
+            .find(|(name, _)| actions.contains_key(name) || !redaction_names.insert(name))
+        {
+            return Err(SchemaGeneratorError::conflicting_name(name.clone()));
+        }
+        actions.insert(description.name().to_smolstr(), action);
+        actions.extend(redaction_actions);
 
         self.drop_namespace_if_empty(&namespace);
 
@@ -1326,6 +2273,71 @@ impl SchemaGenerator {
         }
     }
 
+    /// Returns a `@doc` annotation with `description` (if `doc_annotations` is set)
+    fn doc_annotation(&self, description: Option<&str>) -> Annotations {
+        match description {
+            Some(description) if self.config.doc_annotations => Annotations(BTreeMap::from([(
+                identifiers::DOC.clone(),
+                Some(Annotation::with_optional_value(
+                    Some(description.into()),
+                    None,
+                )),
+            )])),
+            _ => Annotations::new(),
+        }
+    }
+
+    /// Returns the annotations of the record attribute encoding `property`
+    fn attribute_annotations(&self, property: &Property) -> Annotations {
+        let mut annotations = annotations_of_property_type(property.property_type());
+        annotations
+            .0
+            .extend(self.doc_annotation(property.description()).0);
+        annotations
+    }
+
+    /// Add a `@doc` annotation with `description` (if `doc_annotations` is set) to the
+    /// common or entity type `ty_name` of `namespace`, unless it already has one.
+    #[expect(
+        clippy::ref_option,
+        reason = "More ergnomic for indexing into fragment."
+    )]
+    fn add_doc_annotation(
+        &mut self,
+        namespace: &Option<Name>,
+        ty_name: UnreservedId,
+        description: Option<&str>,
+    ) {
+        let doc = self.doc_annotation(description);
+        if doc.0.is_empty() {
+            return;
+        }
+        let (namespace, ty_name) = if self.config.flatten_namespaces {
+            (
+                &self.namespace,
+                self.flatten_unreserved_id(ty_name, namespace),
+            )
+        } else {
+            (namespace, ty_name)
+        };
+        let Some(nsdef) = self.fragment.0.get_mut(namespace) else {
+            return;
+        };
+        let annotations = match CommonTypeId::new(ty_name.clone())
+            .ok()
+            .and_then(|id| nsdef.common_types.get_mut(&id))
+        {
+            Some(common_type) => &mut common_type.annotations,
+            None => match nsdef.entity_types.get_mut(&ty_name) {
+                Some(entity_type) => &mut entity_type.annotations,
+                None => return,
+            },
+        };
+        for (key, value) in doc.0 {
+            annotations.0.entry(key).or_insert(value);
+        }
+    }
+
     #[expect(
         clippy::ref_option,
         reason = "More ergnomic for indexing into fragment."
@@ -1415,24 +2427,28 @@ impl SchemaGenerator {
                 type_def.property_type(),
                 &common_types,
             )?;
-            self.add_commontype(namespace, ty, ty_name, true)?;
+            self.add_commontype(namespace, ty, ty_name.clone(), true)?;
+            self.add_doc_annotation(namespace, ty_name, type_def.description());
         }
 
         let mut attributes = BTreeMap::new();
 
         for property in parameters.properties() {
             let attr_name = property.name().to_smolstr();
-            let ty_name = property.name().parse()?;
+            let ty_name: UnreservedId = property.name().parse()?;
 
             let ty = self.cedar_type_from_property_type(
                 namespace,
-                ty_name,
+                ty_name.clone(),
                 property.property_type(),
                 &common_types,
             )?;
+            if defines_named_type(property.property_type()) {
+                self.add_doc_annotation(namespace, ty_name, property.description());
+            }
             let ty = TypeOfAttribute {
                 ty,
-                annotations: Annotations::new(),
+                annotations: self.attribute_annotations(property),
                 required: property.is_required(),
             };
 
@@ -1479,8 +2495,8 @@ impl SchemaGenerator {
         };
 
         let variant = match property_type {
-            PropertyType::Bool => bool,
-            PropertyType::Integer => long,
+            PropertyType::Bool | PropertyType::BoolEnum { .. } => bool,
+            PropertyType::Integer | PropertyType::IntegerEnum { .. } => long,
             PropertyType::Float => {
                 if self.config.numbers_as_decimal {
                     decimal
@@ -1509,9 +2525,9 @@ impl SchemaGenerator {
             }
             PropertyType::String => string,
             PropertyType::Decimal => decimal,
-            PropertyType::Datetime => datetime,
+            PropertyType::Datetime { .. } => datetime,
             PropertyType::Duration => duration,
-            PropertyType::IpAddr => ipaddr,
+            PropertyType::IpAddr { .. } => ipaddr,
             PropertyType::Null => {
                 self.add_opaque_entity_type(
                     &self.namespace.clone(),
@@ -1608,7 +2624,11 @@ impl SchemaGenerator {
                     additional_attributes: false,
                 })
             }
-            PropertyType::Union { types } => {
+            PropertyType::Union {
+                types,
+                discriminator,
+                ..
+            } => {
                 let ns: Name = ty_name.into();
                 let ns = Some(ns.qualify_with_name(namespace.as_ref()));
                 self.add_namespace(ns.clone());
@@ -1616,13 +2636,9 @@ impl SchemaGenerator {
                     .iter()
                     .enumerate()
                     .map(|(i, ptype)| {
-                        #[expect(
-                            clippy::unwrap_used,
-                            reason = "The string `TypeChoice{i}` is a valid UnreservedId."
-                        )]
-                        let proj_tyname: UnreservedId =
-                            format!("TypeChoice{i}").as_str().parse().unwrap();
-                        let proj = format!("typeChoice{i}").to_smolstr();
+                        let tag = discriminator.as_ref().and_then(|d| d.values().get(i));
+                        let (proj, proj_tyname) = union_choice_names(i, tag);
+                        let proj_tyname: UnreservedId = proj_tyname.parse()?;
                         let ty = self.cedar_type_from_property_type(
                             &ns,
                             proj_tyname,
@@ -1683,17 +2699,20 @@ impl SchemaGenerator {
 
                 for property in properties {
                     let attr_name = property.name().to_smolstr();
-                    let ty_name = property.name().parse()?;
+                    let ty_name: UnreservedId = property.name().parse()?;
 
                     let ty = self.cedar_type_from_property_type(
                         &ns,
-                        ty_name,
+                        ty_name.clone(),
                         property.property_type(),
                         common_types,
                     )?;
+                    if defines_named_type(property.property_type()) {
+                        self.add_doc_annotation(&ns, ty_name, property.description());
+                    }
                     let ty = TypeOfAttribute {
                         ty: unqualify_type(namespace, ty),
-                        annotations: Annotations::new(),
+                        annotations: self.attribute_annotations(property),
                         required: property.is_required(),
                     };
 
@@ -1744,6 +2763,10 @@ impl SchemaGenerator {
                 self.drop_namespace_if_empty(&ns);
                 type_reference
             }
+            // Value constraints have no Cedar schema counterpart
+            PropertyType::Constrained { ty, .. } => {
+                return self.cedar_type_from_property_type(namespace, ty_name, ty, common_types)
+            }
             PropertyType::Ref { name } => match common_types.get(name) {
                 None => {
                     let ns = match namespace {
@@ -1875,6 +2898,45 @@ fn unqualify_name(namespace: &Option<Name>, name: RawName) -> RawName {
     }
 }
 
+/// Collect the tool arguments identifying resources from the `@mcp_resource_from("tool.argument, ...")`
+/// annotations of the entity types of the schema stub. Tool names may contain `.`s, so the argument
+/// is the part of each pair after its last `.`.
+fn resource_arguments(
+    ns: &NamespaceDefinition<RawName>,
+) -> Result<HashMap<SmolStr, ResourceArgument>, SchemaGeneratorError> {
+    let mut resource_arguments = HashMap::new();
+    for (tyname, ty) in &ns.entity_types {
+        let Some(anno) = ty.annotations.0.get(&*identifiers::MCP_RESOURCE_FROM) else {
+            continue;
+        };
+        let val = anno
+            .as_ref()
+            .map(|anno| anno.val.clone())
+            .unwrap_or_default();
+        for pair in val.split(',') {
+            let (tool, argument) = pair
+                .trim()
+                .rsplit_once('.')
+                .filter(|(tool, argument)| !tool.is_empty() && !argument.is_empty())
+                .ok_or_else(|| SchemaGeneratorError::MalformedResourceFrom(val.clone()))?;
+            let resource = ResourceArgument {
+                entity_type: tyname.clone(),
+                argument: argument.into(),
+            };
+            if resource_arguments.insert(tool.into(), resource).is_some() {
+                return Err(SchemaGeneratorError::MalformedResourceFrom(val));
+            }
+        }
+    }
+    Ok(resource_arguments)
+}
+
+/// Parse the tool name pattern of an `@mcp_tools` or `@mcp_action_group` annotation
+fn tool_pattern(anno: Option<&Annotation>) -> Result<ToolPattern, SchemaGeneratorError> {
+    let val = anno.map(|anno| anno.val.clone()).unwrap_or_default();
+    ToolPattern::parse(&val).ok_or(SchemaGeneratorError::MalformedToolPattern(val))
+}
+
 fn erase_mcp_annotations(schema_stub: Fragment<RawName>) -> Fragment<RawName> {
     let ns = schema_stub
         .0
@@ -1909,6 +2971,8 @@ fn erase_mcp_annotations(schema_stub: Fragment<RawName>) -> Fragment<RawName> {
                                 .filter(|(anno, _)| {
                                     anno.as_ref() != "mcp_context"
                                         && anno.as_ref() != "mcp_resource"
+                                        && anno.as_ref() != "mcp_resource_from"
+                                        && anno.as_ref() != "mcp_tools"
                                         && anno.as_ref() != "mcp_principal"
                                 })
                                 .collect(),
@@ -1927,7 +2991,10 @@ fn erase_mcp_annotations(schema_stub: Fragment<RawName>) -> Fragment<RawName> {
                             act.annotations
                                 .0
                                 .into_iter()
-                                .filter(|(anno, _)| anno.as_ref() != "mcp_action")
+                                .filter(|(anno, _)| {
+                                    anno.as_ref() != "mcp_action"
+                                        && anno.as_ref() != "mcp_action_group"
+                                })
                                 .collect(),
                         ),
                         ..act
@@ -1952,9 +3019,11 @@ fn erase_mcp_annotations(schema_stub: Fragment<RawName>) -> Fragment<RawName> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use cedar_policy_core::entities::Entities;
     use cedar_policy_core::extensions::Extensions;
     use cool_asserts::assert_matches;
-    use mcp_tools_sdk::description::Property;
+    use mcp_tools_sdk::data::Input;
+    use mcp_tools_sdk::description::{DatetimeFormat, IpAddrFormat, Property, UnionKind};
 
     use std::collections::HashMap;
 
@@ -2390,7 +3459,518 @@ namespace Test2 {
     }
 
     #[test]
-    fn test_adding_tool_action_errors() {
+    fn test_adding_tool_action_errors() {
+        let schema = r#"namespace Test {
+    @mcp_principal("User")
+    entity user;
+
+    @mcp_resource("McpServer")
+    entity resource;
+
+    @mcp_context("foo")
+    entity Foo;
+
+    type test_toolInput = {};
+}"#;
+
+        let schema_stub = Fragment::from_cedarschema_str(schema, Extensions::all_available())
+            .expect("Failed to parse schema")
+            .0;
+
+        let tool = r#"{
+    "name": "test_tool",
+    "description": "A tool for testing purposes",
+    "parameters": {
+        "type": "object",
+        "properties": {
+            "test_dt": {"type": "string", "format": "date-time"},
+            "test_obj": {
+                "type": "object",
+                "properties": {
+                    "test1": {"type": "float"},
+                    "test2": {"type": "string"}
+                }
+            },
+            "test_obj2": { "type": "object", "additionalProperties": {"type": "string"} }
+        },
+        "required": ["test_dt"]
+    }
+}"#;
+
+        let tool = ToolDescription::from_json_str(tool).expect("Failed to parse tool description");
+        let config = SchemaGeneratorConfig::default().erase_annotations(false);
+        let mut schema_generator = SchemaGenerator::new_with_config(schema_stub.clone(), config)
+            .expect("Failed to create schema generator");
+
+        assert_matches!(
+            schema_generator.add_action_from_tool_description(&tool),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(..))
+        );
+        assert_eq!(&schema_stub, schema_generator.get_schema());
+    }
+
+    #[test]
+    fn test_adding_server_actions_errors() {
+        let schema = r#"namespace Test {
+    @mcp_principal("User")
+    entity user;
+
+    @mcp_resource("McpServer")
+    entity resource;
+
+    @mcp_context("foo")
+    entity Foo;
+
+    type test_toolInput = {};
+}"#;
+
+        let schema_stub = Fragment::from_cedarschema_str(schema, Extensions::all_available())
+            .expect("Failed to parse schema")
+            .0;
+
+        let tool = r#"{
+    "name": "test_tool",
+    "description": "A tool for testing purposes",
+    "parameters": {
+        "type": "object",
+        "properties": {
+            "test_dt": {"type": "string", "format": "date-time"},
+            "test_obj": {
+                "type": "object",
+                "properties": {
+                    "test1": {"type": "float"},
+                    "test2": {"type": "string"}
+                }
+            },
+            "test_obj2": { "type": "object", "additionalProperties": {"type": "string"} }
+        },
+        "required": ["test_dt"]
+    }
+}"#;
+
+        let tool =
+            ServerDescription::from_json_str(tool).expect("Failed to parse tool description");
+        let config = SchemaGeneratorConfig::default().erase_annotations(false);
+        let mut schema_generator = SchemaGenerator::new_with_config(schema_stub.clone(), config)
+            .expect("Failed to create schema generator");
+
+        assert_matches!(
+            schema_generator.add_actions_from_server_description(&tool),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(..))
+        );
+        assert_eq!(&schema_stub, schema_generator.get_schema());
+    }
+
+    #[test]
+    fn test_resource_from_annotation() {
+        let stub = |anno: &str| {
+            let schema = format!(
+                r#"namespace Test {{
+    @mcp_principal
+    entity user;
+    @mcp_resource
+    entity resource;
+    entity Folder in [Folder];
+    @mcp_resource_from("{anno}")
+    entity File in [Folder];
+}}"#
+            );
+            Fragment::from_cedarschema_str(&schema, Extensions::all_available())
+                .expect("Failed to parse schema")
+                .0
+        };
+        let server = ServerDescription::from_json_str(
+            r#"[
+    {"name": "read_file", "inputSchema": {"properties": {"path": {"type": "string"}}, "required": ["path"]}},
+    {"name": "search", "inputSchema": {"properties": {"path": {"type": "string"}}}}
+]"#,
+        )
+        .expect("Failed to parse server description");
+        let resource_types = |schema_generator: &SchemaGenerator, tool: &str| {
+            schema_generator.get_schema().0[&Some("Test".parse().unwrap())].actions[tool]
+                .applies_to
+                .as_ref()
+                .map(|applies_to| {
+                    applies_to
+                        .resource_types
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                })
+        };
+
+        let mut schema_generator = SchemaGenerator::new(stub("read_file.path"))
+            .expect("Failed to create schema generator");
+        schema_generator
+            .add_actions_from_server_description(&server)
+            .expect("Failed to add tools");
+        assert_eq!(
+            resource_types(&schema_generator, "read_file"),
+            Some(vec!["File".to_string()])
+        );
+        assert_eq!(
+            resource_types(&schema_generator, "search"),
+            Some(vec!["resource".to_string()])
+        );
+        assert!(!schema_generator
+            .get_schema_as_str()
+            .contains("mcp_resource_from"));
+        schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+
+        // The argument follows the last `.`, since MCP tool names may contain `.`s
+        let arguments = resource_arguments(
+            &stub("fs.stat.path, fs.list.dir").0[&Some("Test".parse().unwrap())],
+        )
+        .expect("Failed to parse annotation");
+        assert_eq!(arguments["fs.stat"].argument, "path");
+        assert_eq!(arguments["fs.list"].argument, "dir");
+
+        // The argument must be a required input property
+        let mut schema_generator = SchemaGenerator::new(stub("read_file.path, search.path"))
+            .expect("Failed to create schema generator");
+        assert_matches!(
+            schema_generator.add_actions_from_server_description(&server),
+            Err(SchemaGeneratorError::InvalidResourceArgument(_))
+        );
+        let mut schema_generator = SchemaGenerator::new(stub("read_file.name"))
+            .expect("Failed to create schema generator");
+        assert_matches!(
+            schema_generator.add_actions_from_server_description(&server),
+            Err(SchemaGeneratorError::InvalidResourceArgument(_))
+        );
+
+        for anno in [
+            "read_file",
+            "read_file.",
+            ".path",
+            "read_file.path, read_file.name",
+        ] {
+            assert_matches!(
+                SchemaGenerator::new(stub(anno)),
+                Err(SchemaGeneratorError::MalformedResourceFrom(_))
+            );
+        }
+    }
+
+    #[test]
+    fn test_tools_annotation() {
+        let stub = |resource_tools: &str| {
+            let schema = format!(
+                r#"namespace Test {{
+    @mcp_principal
+    entity Agent;
+    @mcp_principal
+    @mcp_tools("github_*")
+    entity User;
+    @mcp_resource
+    @mcp_tools("github_*")
+    entity Repo;
+    @mcp_resource
+    @mcp_tools("{resource_tools}")
+    entity Server;
+}}"#
+            );
+            Fragment::from_cedarschema_str(&schema, Extensions::all_available())
+                .expect("Failed to parse schema")
+                .0
+        };
+        let server = ServerDescription::from_json_str(
+            r#"[
+    {"name": "github_list_repos", "inputSchema": {"properties": {}}},
+    {"name": "search", "inputSchema": {"properties": {}}}
+]"#,
+        )
+        .expect("Failed to parse server description");
+        let applies_to = |schema_generator: &SchemaGenerator, tool: &str| {
+            let applies_to = schema_generator.get_schema().0[&Some("Test".parse().unwrap())]
+                .actions[tool]
+                .applies_to
+                .as_ref()
+                .expect("Action should have an apply spec");
+            let names = |types: &[RawName]| {
+                let mut names = types.iter().map(ToString::to_string).collect::<Vec<_>>();
+                names.sort();
+                names
+            };
+            (
+                names(&applies_to.principal_types),
+                names(&applies_to.resource_types),
+            )
+        };
+
+        let mut schema_generator =
+            SchemaGenerator::new(stub("list_*|search")).expect("Failed to create schema generator");
+        schema_generator
+            .add_actions_from_server_description(&server)
+            .expect("Failed to add tools");
+        assert_eq!(
+            applies_to(&schema_generator, "github_list_repos"),
+            (vec!["Agent".into(), "User".into()], vec!["Repo".into()])
+        );
+        assert_eq!(
+            applies_to(&schema_generator, "search"),
+            (vec!["Agent".into()], vec!["Server".into()])
+        );
+        assert!(!schema_generator.get_schema_as_str().contains("mcp_tools"));
+
+        // Every tool needs at least one resource type
+        let mut schema_generator =
+            SchemaGenerator::new(stub("list_*")).expect("Failed to create schema generator");
+        assert_matches!(
+            schema_generator.add_actions_from_server_description(&server),
+            Err(SchemaGeneratorError::NoResourceTypesForTool(tool)) if tool == "search"
+        );
+
+        assert_matches!(
+            SchemaGenerator::new(stub("list_*||search")),
+            Err(SchemaGeneratorError::MalformedToolPattern(_))
+        );
+    }
+
+    #[test]
+    fn test_redaction_actions() {
+        let tool = ToolDescription::from_json_str(
+            r##"{
+    "name": "get_customer",
+    "annotations": { "readOnlyHint": true },
+    "inputSchema": { "properties": { "id": { "type": "string" } } },
+    "outputSchema": {
+        "properties": {
+            "name": { "type": "string" },
+            "ssn": { "type": "string", "description": "Social security number" },
+            "address": {
+                "type": "object",
+                "properties": {
+                    "city": { "type": "string" },
+                    "zip": { "type": "string" }
+                },
+                "required": ["city"]
+            },
+            "manager": { "$ref": "#/$defs/person" },
+            "contacts": { "type": "array", "items": { "$ref": "#/$defs/person" } }
+        },
+        "required": ["name", "address", "contacts"],
+        "$defs": {
+            "person": {
+                "type": "object",
+                "properties": {
+                    "email": { "type": "string" },
+                    "manager": { "$ref": "#/$defs/person" }
+                }
+            }
+        }
+    }
+}"##,
+        )
+        .expect("Failed to parse tool description");
+        let actions = |config: SchemaGeneratorConfig| {
+            let mut schema_generator = SchemaGenerator::new_with_config(test_schema_stub(), config)
+                .expect("Failed to create schema generator");
+            schema_generator
+                .add_action_from_tool_description(&tool)
+                .expect("Failed to add tool");
+            // The generated schema is valid
+            schema_generator
+                .new_request_generator()
+                .expect("Failed to construct request generator");
+            schema_generator.get_schema().0[&Some("Test".parse().unwrap())]
+                .actions
+                .clone()
+        };
+
+        let config = SchemaGeneratorConfig::default().redaction_actions(true);
+        assert_eq!(
+            actions(config.clone()).keys().collect::<Vec<_>>(),
+            vec!["get_customer"]
+        );
+
+        let actions = actions(
+            config
+                .include_outputs(true)
+                .doc_annotations(true)
+                .annotation_action_groups(true),
+        );
+        // Optional properties of nested objects have their own redaction action, but a type
+        // definition is not expanded within itself and elements of arrays are not redacted
+        assert_eq!(
+            actions.keys().collect::<Vec<_>>(),
+            vec![
+                "destructiveTools",
+                "get_customer",
+                "get_customer.address.zip",
+                "get_customer.manager",
+                "get_customer.manager.email",
+                "get_customer.manager.manager",
+                "get_customer.ssn",
+                "idempotentTools",
+                "openWorldTools",
+                "readOnlyTools",
+            ]
+        );
+        // Redaction actions are not members of the tool's action groups
+        assert!(actions["get_customer"].member_of.is_some());
+        assert!(actions["get_customer.ssn"].member_of.is_none());
+        assert!(actions["get_customer.address.zip"].member_of.is_none());
+        // Redaction actions share the context of the tool's action
+        assert_eq!(
+            actions["get_customer.ssn"].applies_to,
+            actions["get_customer"].applies_to
+        );
+        assert!(actions["get_customer.ssn"]
+            .annotations
+            .0
+            .contains_key(&*identifiers::DOC));
+    }
+
+    /// A server description with a single tool `tool_name` whose input refers to the
+    /// server's shared type definition `Path`.
+    fn server_with_tool(tool_name: &str) -> ServerDescription {
+        let tools = format!(
+            r##"{{"result": {{
+    "tools": [{{
+        "name": "{tool_name}",
+        "inputSchema": {{
+            "type": "object",
+            "properties": {{ "path": {{ "$ref": "#/$defs/Path" }} }}
+        }}
+    }}],
+    "$defs": {{ "Path": {{ "type": "string" }} }}
+}}}}"##
+        );
+        ServerDescription::from_json_str(&tools).expect("Failed to parse server description")
+    }
+
+    #[test]
+    fn test_add_servers_with_ids() {
+        let mut schema_generator =
+            SchemaGenerator::new(test_schema_stub()).expect("Failed to create schema generator");
+        schema_generator
+            .add_actions_from_server_description_with_id("github", &server_with_tool("search"))
+            .expect("Failed to add first server");
+        schema_generator
+            .add_actions_from_server_description_with_id("jira", &server_with_tool("create_issue"))
+            .expect("Failed to add second server");
+
+        let schema = schema_generator.get_schema();
+        let root_nsdef = schema
+            .0
+            .get(&Some("Test".parse().unwrap()))
+            .expect("Expected namespace Test to exist");
+        for (server, tool) in [("github", "search"), ("jira", "create_issue")] {
+            assert_matches!(
+                root_nsdef.actions.get(server),
+                Some(ActionType {
+                    applies_to: None,
+                    ..
+                })
+            );
+            let action = root_nsdef.actions.get(tool).expect("Expected tool action");
+            assert_eq!(
+                action.member_of,
+                Some(vec![ActionEntityUID::new(None, server.into())])
+            );
+            // Each server's shared type definitions live in the server's namespace
+            let server_ns = Some(format!("Test::{server}").parse().unwrap());
+            assert!(schema.0[&server_ns]
+                .common_types
+                .keys()
+                .any(|ty| ty.as_ref().to_string() == "Path"));
+        }
+
+        // The generated schema is valid
+        schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+    }
+
+    #[test]
+    fn test_add_servers_with_ids_keeps_mcp_action_groups() {
+        let schema = r#"namespace Test {
+    @mcp_principal("User")
+    entity user;
+
+    @mcp_resource("McpServer")
+    entity resource;
+
+    @mcp_action
+    action call_tool;
+}"#;
+        let mut schema_generator =
+            SchemaGenerator::from_cedarschema_str(schema).expect("Failed to create generator");
+        schema_generator
+            .add_actions_from_server_description_with_id("github", &server_with_tool("search"))
+            .expect("Failed to add server");
+
+        let root_nsdef = &schema_generator.get_schema().0[&Some("Test".parse().unwrap())];
+        assert_eq!(
+            root_nsdef.actions["search"].member_of,
+            Some(vec![
+                ActionEntityUID::new(None, "call_tool".into()),
+                ActionEntityUID::new(None, "github".into()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_add_servers_with_ids_errors() {
+        let mut schema_generator =
+            SchemaGenerator::new(test_schema_stub()).expect("Failed to create schema generator");
+        schema_generator
+            .add_actions_from_server_description_with_id("github", &server_with_tool("search"))
+            .expect("Failed to add server");
+        let schema = schema_generator.get_schema().clone();
+
+        assert_matches!(
+            schema_generator
+                .add_actions_from_server_description_with_id("github", &server_with_tool("other")),
+            Err(SchemaGeneratorError::DuplicateServerId(id)) if id == "github"
+        );
+        assert_matches!(
+            schema_generator
+                .add_actions_from_server_description_with_id("jira", &server_with_tool("search")),
+            Err(SchemaGeneratorError::ToolNameCollision(_))
+        );
+        // Server ids and tool names share the action namespace
+        assert_matches!(
+            schema_generator
+                .add_actions_from_server_description_with_id("search", &server_with_tool("other")),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(_))
+        );
+        assert_matches!(
+            schema_generator
+                .add_actions_from_server_description_with_id("jira", &server_with_tool("github")),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(_))
+        );
+        assert_matches!(
+            schema_generator.add_actions_from_server_description_with_id(
+                "not-an-id",
+                &server_with_tool("other")
+            ),
+            Err(SchemaGeneratorError::ReservedName(_))
+        );
+        // Servers with ids cannot be mixed with tools added without an id
+        assert_matches!(
+            schema_generator.add_actions_from_server_description(&server_with_tool("other")),
+            Err(SchemaGeneratorError::ServerDescriptionMerge)
+        );
+        assert_eq!(&schema, schema_generator.get_schema());
+
+        let mut schema_generator =
+            SchemaGenerator::new(test_schema_stub()).expect("Failed to create schema generator");
+        schema_generator
+            .add_actions_from_server_description(&server_with_tool("search"))
+            .expect("Failed to add server");
+        assert_matches!(
+            schema_generator
+                .add_actions_from_server_description_with_id("jira", &server_with_tool("other")),
+            Err(SchemaGeneratorError::ServerDescriptionMerge)
+        );
+    }
+
+    #[test]
+    fn test_annotation_action_groups() {
         let schema = r#"namespace Test {
     @mcp_principal("User")
     entity user;
@@ -2398,50 +3978,64 @@ namespace Test2 {
     @mcp_resource("McpServer")
     entity resource;
 
-    @mcp_context("foo")
-    entity Foo;
-
-    type test_toolInput = {};
-}"#;
-
-        let schema_stub = Fragment::from_cedarschema_str(schema, Extensions::all_available())
-            .expect("Failed to parse schema")
-            .0;
+    @mcp_action
+    action call_tool;
 
-        let tool = r#"{
-    "name": "test_tool",
-    "description": "A tool for testing purposes",
-    "parameters": {
-        "type": "object",
-        "properties": {
-            "test_dt": {"type": "string", "format": "date-time"},
-            "test_obj": {
-                "type": "object",
-                "properties": {
-                    "test1": {"type": "float"},
-                    "test2": {"type": "string"}
-                }
-            },
-            "test_obj2": { "type": "object", "additionalProperties": {"type": "string"} }
-        },
-        "required": ["test_dt"]
-    }
+    action destructiveTools in [call_tool];
 }"#;
-
-        let tool = ToolDescription::from_json_str(tool).expect("Failed to parse tool description");
-        let config = SchemaGeneratorConfig::default().erase_annotations(false);
-        let mut schema_generator = SchemaGenerator::new_with_config(schema_stub.clone(), config)
-            .expect("Failed to create schema generator");
-
-        assert_matches!(
-            schema_generator.add_action_from_tool_description(&tool),
-            Err(SchemaGeneratorError::ConflictingSchemaNameError(..))
+        let tools = r#"[
+    {"name": "read", "inputSchema": {"type": "object"}, "annotations": {"readOnlyHint": true, "openWorldHint": false}},
+    {"name": "update", "inputSchema": {"type": "object"}, "annotations": {"destructiveHint": false, "idempotentHint": true}},
+    {"name": "delete", "inputSchema": {"type": "object"}}
+]"#;
+        let config = SchemaGeneratorConfig::default().annotation_action_groups(true);
+        let mut schema_generator =
+            SchemaGenerator::from_cedarschema_str_with_config(schema, config)
+                .expect("Failed to create generator");
+        schema_generator
+            .add_actions_from_server_description(&ServerDescription::from_json_str(tools).unwrap())
+            .expect("Failed to add tools");
+
+        let root_nsdef = &schema_generator.get_schema().0[&Some("Test".parse().unwrap())];
+        // Groups missing from the stub are added, the stub's groups are kept as is
+        for group in ["readOnlyTools", "idempotentTools", "openWorldTools"] {
+            assert_matches!(
+                root_nsdef.actions.get(group),
+                Some(ActionType {
+                    applies_to: None,
+                    member_of: None,
+                    ..
+                })
+            );
+        }
+        assert_eq!(
+            root_nsdef.actions["destructiveTools"].member_of,
+            Some(vec![ActionEntityUID::new(None, "call_tool".into())])
         );
-        assert_eq!(&schema_stub, schema_generator.get_schema());
+        let member_of = |action: &str, groups: &[&str]| {
+            assert_eq!(
+                root_nsdef.actions[action].member_of,
+                Some(
+                    std::iter::once("call_tool")
+                        .chain(groups.iter().copied())
+                        .map(|group| ActionEntityUID::new(None, group.into()))
+                        .collect()
+                ),
+                "{action}"
+            );
+        };
+        member_of("read", &["readOnlyTools", "idempotentTools"]);
+        member_of("update", &["idempotentTools", "openWorldTools"]);
+        member_of("delete", &["destructiveTools", "openWorldTools"]);
+
+        // The generated schema is valid
+        schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
     }
 
     #[test]
-    fn test_adding_server_actions_errors() {
+    fn test_tool_pattern_action_groups() {
         let schema = r#"namespace Test {
     @mcp_principal("User")
     entity user;
@@ -2449,47 +4043,355 @@ namespace Test2 {
     @mcp_resource("McpServer")
     entity resource;
 
-    @mcp_context("foo")
-    entity Foo;
+    @mcp_action
+    @mcp_action_group("*")
+    action call_tool;
 
-    type test_toolInput = {};
+    @mcp_action_group("fs_*|read_file|write_file")
+    action filesystem;
+
+    @mcp_action_group("*_file")
+    action files in [filesystem];
 }"#;
+        let tools = r#"[
+    {"name": "fs_list", "inputSchema": {"type": "object"}},
+    {"name": "read_file", "inputSchema": {"type": "object"}},
+    {"name": "delete_file", "inputSchema": {"type": "object"}},
+    {"name": "search", "inputSchema": {"type": "object"}}
+]"#;
+        let mut schema_generator =
+            SchemaGenerator::from_cedarschema_str(schema).expect("Failed to create generator");
+        schema_generator
+            .add_actions_from_server_description(&ServerDescription::from_json_str(tools).unwrap())
+            .expect("Failed to add tools");
+
+        let root_nsdef = &schema_generator.get_schema().0[&Some("Test".parse().unwrap())];
+        let member_of = |action: &str, groups: &[&str]| {
+            assert_eq!(
+                root_nsdef.actions[action].member_of,
+                Some(
+                    groups
+                        .iter()
+                        .map(|group| ActionEntityUID::new(None, (*group).into()))
+                        .collect()
+                ),
+                "{action}"
+            );
+        };
+        // Groups matching every tool are not repeated
+        member_of("fs_list", &["call_tool", "filesystem"]);
+        member_of("read_file", &["call_tool", "files", "filesystem"]);
+        member_of("delete_file", &["call_tool", "files"]);
+        member_of("search", &["call_tool"]);
+        // The stub's groups are kept as is
+        member_of("files", &["filesystem"]);
+        assert!(!schema_generator
+            .get_schema_as_str()
+            .contains("mcp_action_group"));
+
+        // Policies can refer to the groups
+        let request_generator = schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+        let policies = cedar_policy_core::parser::parse_policyset(
+            r#"permit(principal, action in Test::Action::"filesystem", resource);"#,
+        )
+        .expect("Failed to parse policies");
+        let is_allowed = |tool: &str| {
+            let input = Input::from_json_str(&format!(
+                r#"{{"params": {{"tool": "{tool}", "args": {{}}}}}}"#
+            ))
+            .expect("Failed to parse input");
+            request_generator
+                .is_authorized(
+                    r#"Test::user::"alice""#.parse().unwrap(),
+                    r#"Test::resource::"server""#.parse().unwrap(),
+                    std::iter::empty(),
+                    Entities::new(),
+                    &input,
+                    None,
+                    &policies,
+                )
+                .expect("Failed to authorize")
+                .is_allowed()
+        };
+        assert!(is_allowed("fs_list"));
+        assert!(is_allowed("delete_file"));
+        assert!(!is_allowed("search"));
 
-        let schema_stub = Fragment::from_cedarschema_str(schema, Extensions::all_available())
-            .expect("Failed to parse schema")
-            .0;
+        assert_matches!(
+            SchemaGenerator::from_cedarschema_str(&schema.replace("\"*_file\"", "\"\"")),
+            Err(SchemaGeneratorError::MalformedToolPattern(_))
+        );
+    }
 
-        let tool = r#"{
-    "name": "test_tool",
-    "description": "A tool for testing purposes",
-    "parameters": {
-        "type": "object",
-        "properties": {
-            "test_dt": {"type": "string", "format": "date-time"},
-            "test_obj": {
-                "type": "object",
-                "properties": {
-                    "test1": {"type": "float"},
-                    "test2": {"type": "string"}
+    #[test]
+    fn test_annotation_action_groups_conflicting_tool_name() {
+        let config = SchemaGeneratorConfig::default().annotation_action_groups(true);
+        let mut schema_generator = SchemaGenerator::new_with_config(test_schema_stub(), config)
+            .expect("Failed to create schema generator");
+        let tools = r#"[{"name": "readOnlyTools", "inputSchema": {"type": "object"}}]"#;
+        assert_matches!(
+            schema_generator.add_actions_from_server_description(
+                &ServerDescription::from_json_str(tools).unwrap()
+            ),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(_))
+        );
+        assert_matches!(
+            schema_generator.add_actions_from_server_description_with_id(
+                "destructiveTools",
+                &server_with_tool("search")
+            ),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(_))
+        );
+    }
+
+    #[test]
+    fn test_resource_template_actions() {
+        let resources = ResourceList::from_json_str(
+            r#"{"result": {"resourceTemplates": [
+    {"uriTemplate": "file:///{+path}{?encoding}", "name": "read_file", "description": "A project file"},
+    {"uriTemplate": "users://{id}/profile", "name": "user_profile"}
+]}}"#,
+        )
+        .expect("Failed to parse resource templates");
+        let config = SchemaGeneratorConfig::default().doc_annotations(true);
+        let mut schema_generator = SchemaGenerator::new_with_config(test_schema_stub(), config)
+            .expect("Failed to create schema generator");
+        schema_generator
+            .add_actions_from_resource_templates(&resources)
+            .expect("Failed to add resource templates");
+
+        let root_nsdef = &schema_generator.get_schema().0[&Some("Test".parse().unwrap())];
+        let context_attributes = |action: &str| {
+            assert_matches!(
+                &root_nsdef.actions[action].applies_to,
+                Some(ApplySpec {
+                    context: AttributesOrContext(Type::Type {
+                        ty: TypeVariant::Record(context),
+                        ..
+                    }),
+                    ..
+                }) => {
+                    let input = assert_matches!(
+                        &context.attributes["input"].ty,
+                        Type::Type { ty: TypeVariant::Record(input), .. } => input
+                    );
+                    (
+                        context.attributes.keys().cloned().collect::<Vec<_>>(),
+                        input
+                            .attributes
+                            .iter()
+                            .map(|(name, attr)| (name.clone(), attr.required))
+                            .collect::<Vec<_>>(),
+                    )
                 }
-            },
-            "test_obj2": { "type": "object", "additionalProperties": {"type": "string"} }
-        },
-        "required": ["test_dt"]
+            )
+        };
+        assert_eq!(
+            context_attributes("read_file"),
+            (
+                vec!["foo".into(), "input".into(), "uri".into()],
+                vec![("encoding".into(), false), ("path".into(), true)]
+            )
+        );
+        assert_eq!(
+            context_attributes("user_profile"),
+            (
+                vec!["foo".into(), "input".into(), "uri".into()],
+                vec![("id".into(), true)]
+            )
+        );
+        assert!(root_nsdef.actions["read_file"]
+            .annotations
+            .0
+            .contains_key(&*identifiers::DOC));
+
+        // Resource templates and tools share the namespace of actions
+        assert_matches!(
+            schema_generator.add_actions_from_resource_templates(&resources),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(_))
+        );
+        assert_matches!(
+            schema_generator.add_actions_from_server_description(&server_with_tool("read_file")),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(_))
+        );
+        schema_generator
+            .add_actions_from_server_description(&server_with_tool("search"))
+            .expect("Failed to add tools");
+
+        // The generated schema is valid
+        schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
     }
-}"#;
 
-        let tool =
-            ServerDescription::from_json_str(tool).expect("Failed to parse tool description");
-        let config = SchemaGeneratorConfig::default().erase_annotations(false);
-        let mut schema_generator = SchemaGenerator::new_with_config(schema_stub.clone(), config)
+    #[test]
+    fn test_prompt_actions() {
+        let prompts = PromptList::from_json_str(
+            r#"{"result": {"prompts": [
+    {"name": "code_review", "description": "Review code", "arguments": [
+        {"name": "code", "description": "The code to review", "required": true},
+        {"name": "language"}
+    ]},
+    {"name": "greeting"}
+]}}"#,
+        )
+        .expect("Failed to parse prompts");
+        let config = SchemaGeneratorConfig::default().doc_annotations(true);
+        let mut schema_generator = SchemaGenerator::new_with_config(test_schema_stub(), config)
             .expect("Failed to create schema generator");
+        schema_generator
+            .add_actions_from_prompts(&prompts)
+            .expect("Failed to add prompts");
+
+        let root_nsdef = &schema_generator.get_schema().0[&Some("Test".parse().unwrap())];
+        let input_attributes = |prompt: &str| {
+            let id: UnreservedId = format!("{prompt}Input").parse().unwrap();
+            assert_matches!(
+                &root_nsdef.common_types[&CommonTypeId::new(id).unwrap()].ty,
+                Type::Type { ty: TypeVariant::Record(input), .. } => input
+                    .attributes
+                    .iter()
+                    .map(|(name, attr)| (name.clone(), attr.required))
+                    .collect::<Vec<_>>()
+            )
+        };
+        assert_eq!(
+            input_attributes("code_review"),
+            vec![("code".into(), true), ("language".into(), false)]
+        );
+        assert_eq!(input_attributes("greeting"), vec![]);
+        assert_matches!(
+            &root_nsdef.actions["code_review"].applies_to,
+            Some(ApplySpec {
+                context: AttributesOrContext(Type::Type {
+                    ty: TypeVariant::Record(context),
+                    ..
+                }),
+                ..
+            }) => assert_eq!(
+                context.attributes.keys().cloned().collect::<Vec<_>>(),
+                vec!["foo".to_smolstr(), "input".to_smolstr()]
+            )
+        );
+        assert!(root_nsdef.actions["code_review"]
+            .annotations
+            .0
+            .contains_key(&*identifiers::DOC));
 
+        // Prompts and tools share the namespace of actions
         assert_matches!(
-            schema_generator.add_actions_from_server_description(&tool),
-            Err(SchemaGeneratorError::ConflictingSchemaNameError(..))
+            schema_generator.add_actions_from_prompts(&prompts),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(_))
         );
-        assert_eq!(&schema_stub, schema_generator.get_schema());
+        assert_matches!(
+            schema_generator.add_actions_from_server_description(&server_with_tool("greeting")),
+            Err(SchemaGeneratorError::ConflictingSchemaNameError(_))
+        );
+        schema_generator
+            .add_actions_from_server_description(&server_with_tool("search"))
+            .expect("Failed to add tools");
+
+        // The generated schema is valid
+        schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+    }
+
+    #[test]
+    fn test_doc_annotations() {
+        let tools = r##"{"result": {
+    "tools": [{
+        "name": "create_ticket",
+        "description": "Create a \"ticket\"\nin the tracker",
+        "inputSchema": {
+            "type": "object",
+            "properties": {
+                "title": {"type": "string", "description": "The ticket's title"},
+                "priority": {"type": "string", "enum": ["low", "high"], "description": "How urgent"},
+                "owner": {"$ref": "#/$defs/Person", "description": "Who owns it"},
+                "labels": {
+                    "type": "array",
+                    "description": "Labels",
+                    "items": {"type": "object", "properties": {"name": {"type": "string", "description": "Label name"}}}
+                }
+            }
+        }
+    }],
+    "$defs": {
+        "Person": {"type": "object", "description": "A person", "properties": {"login": {"type": "string"}}},
+        "Id": {"type": "string", "description": "An id"}
+    }
+}}"##;
+        let description = ServerDescription::from_json_str(tools).unwrap();
+        let doc = |annotations: &Annotations| {
+            annotations
+                .0
+                .get(&*identifiers::DOC)
+                .and_then(|anno| anno.as_ref())
+                .map(|anno| anno.val.to_string())
+        };
+
+        let config = SchemaGeneratorConfig::default().doc_annotations(true);
+        let mut schema_generator = SchemaGenerator::new_with_config(test_schema_stub(), config)
+            .expect("Failed to create schema generator");
+        schema_generator
+            .add_actions_from_server_description(&description)
+            .expect("Failed to add tools");
+
+        // The annotations survive a round trip through the human-readable format
+        let schema = Fragment::from_cedarschema_str(
+            &schema_generator.get_schema().to_cedarschema().unwrap(),
+            Extensions::all_available(),
+        )
+        .expect("Failed to parse generated schema")
+        .0;
+        let root_nsdef = &schema.0[&Some("Test".parse().unwrap())];
+        assert_eq!(
+            doc(&root_nsdef.actions["create_ticket"].annotations),
+            Some("Create a \"ticket\"\nin the tracker".to_string())
+        );
+        assert_eq!(
+            doc(
+                &root_nsdef.common_types[&CommonTypeId::new("Id".parse().unwrap()).unwrap()]
+                    .annotations
+            ),
+            Some("An id".to_string())
+        );
+        assert_eq!(
+            doc(&root_nsdef.entity_types[&"Person".parse().unwrap()].annotations),
+            Some("A person".to_string())
+        );
+        let input = &root_nsdef.common_types
+            [&CommonTypeId::new("create_ticketInput".parse().unwrap()).unwrap()];
+        assert_matches!(&input.ty, Type::Type { ty: TypeVariant::Record(record), .. } => {
+            for (attr, expected) in [
+                ("title", "The ticket's title"),
+                ("priority", "How urgent"),
+                ("owner", "Who owns it"),
+                ("labels", "Labels"),
+            ] {
+                assert_eq!(doc(&record.attributes[attr].annotations), Some(expected.to_string()));
+            }
+        });
+        let input_nsdef = &schema.0[&Some("Test::create_ticket::Input".parse().unwrap())];
+        assert_eq!(
+            doc(&input_nsdef.entity_types[&"priority".parse().unwrap()].annotations),
+            Some("How urgent".to_string())
+        );
+        assert_eq!(
+            doc(&input_nsdef.entity_types[&"labels".parse().unwrap()].annotations),
+            Some("Labels".to_string())
+        );
+
+        // Descriptions are dropped by default
+        let mut schema_generator =
+            SchemaGenerator::new(test_schema_stub()).expect("Failed to create schema generator");
+        schema_generator
+            .add_actions_from_server_description(&description)
+            .expect("Failed to add tools");
+        assert!(!schema_generator.get_schema_as_str().contains("@doc"));
     }
 
     #[test]
@@ -2692,9 +4594,13 @@ namespace Test2 {
         assert!(is_primitive(&PropertyType::Number));
         assert!(is_primitive(&PropertyType::String));
         assert!(is_primitive(&PropertyType::Decimal));
-        assert!(is_primitive(&PropertyType::Datetime));
+        assert!(is_primitive(&PropertyType::Datetime {
+            format: DatetimeFormat::Date
+        }));
         assert!(is_primitive(&PropertyType::Duration));
-        assert!(is_primitive(&PropertyType::IpAddr));
+        assert!(is_primitive(&PropertyType::IpAddr {
+            format: IpAddrFormat::Ipv6
+        }));
         assert!(is_primitive(&PropertyType::Null));
         assert!(is_primitive(&PropertyType::Unknown));
 
@@ -2713,7 +4619,9 @@ namespace Test2 {
             types: vec![PropertyType::String]
         }));
         assert!(!is_primitive(&PropertyType::Union {
-            types: vec![PropertyType::String]
+            types: vec![PropertyType::String],
+            kind: UnionKind::AnyOf,
+            discriminator: None,
         }));
     }
 
@@ -3201,9 +5109,8 @@ namespace Test2 {
             .add_action_from_tool_description(&bad_tool())
             .is_err());
 
-        assert_eq!(
-            schema_gen.tools.tool_descriptions().count(),
-            0,
+        assert!(
+            !schema_gen.has_tools(),
             "Failed tool should not remain in generator state"
         );
 
@@ -3226,6 +5133,188 @@ namespace Test2 {
         gen.add_action_from_tool_description(&good_tool())
             .expect("Generator should accept a valid tool after a failed server description");
     }
+
+    #[test]
+    fn test_constraints_do_not_change_schema() {
+        let generate = |tool: &str| {
+            let tool =
+                ToolDescription::from_json_str(tool).expect("Failed to parse tool description");
+            let config = SchemaGeneratorConfig::default().deduplicate_entity_types(true);
+            let mut schema_generator = SchemaGenerator::new_with_config(test_schema_stub(), config)
+                .expect("Failed to create schema generator");
+            schema_generator
+                .add_action_from_tool_description(&tool)
+                .expect("Failed to add tool description");
+            schema_generator.get_schema_as_str()
+        };
+
+        let constrained = generate(
+            r#"{
+    "name": "log_hours",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "hours_needed": {"type": "integer", "minimum": 0, "maximum": 80},
+            "ticket": {"type": "string", "pattern": "^[A-Z]+-[0-9]+$"},
+            "tags": {"type": "array", "items": {"type": "string", "maxLength": 16}, "uniqueItems": true},
+            "window": {
+                "type": "object",
+                "properties": {"start": {"type": "integer", "minimum": 0}},
+                "required": ["start"]
+            }
+        },
+        "required": ["hours_needed"]
+    }
+}"#,
+        );
+        let unconstrained = generate(
+            r#"{
+    "name": "log_hours",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "hours_needed": {"type": "integer"},
+            "ticket": {"type": "string"},
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "window": {
+                "type": "object",
+                "properties": {"start": {"type": "integer"}},
+                "required": ["start"]
+            }
+        },
+        "required": ["hours_needed"]
+    }
+}"#,
+        );
+        assert_eq!(constrained, unconstrained);
+    }
+
+    #[test]
+    fn test_all_of_matches_flattened_object() {
+        let generate = |tool: &str| {
+            let tool =
+                ToolDescription::from_json_str(tool).expect("Failed to parse tool description");
+            let mut schema_generator = SchemaGenerator::new(test_schema_stub())
+                .expect("Failed to create schema generator");
+            schema_generator
+                .add_action_from_tool_description(&tool)
+                .expect("Failed to add tool description");
+            schema_generator.get_schema_as_str()
+        };
+
+        let composed = generate(
+            r#"{
+    "name": "create_ticket",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "ticket": {
+                "allOf": [
+                    {"type": "object", "properties": {"id": {"type": "string"}}, "required": ["id"]},
+                    {"properties": {"priority": {"type": "integer"}}}
+                ]
+            }
+        },
+        "required": ["ticket"]
+    }
+}"#,
+        );
+        let flattened = generate(
+            r#"{
+    "name": "create_ticket",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "ticket": {
+                "type": "object",
+                "properties": {"id": {"type": "string"}, "priority": {"type": "integer"}},
+                "required": ["id"]
+            }
+        },
+        "required": ["ticket"]
+    }
+}"#,
+        );
+        assert_eq!(composed, flattened);
+    }
+
+    #[test]
+    fn test_discriminated_union_choices_are_named_after_tags() {
+        let tool = ToolDescription::from_json_str(
+            r#"{
+    "name": "draw",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "shape": {
+                "oneOf": [
+                    {"type": "object", "properties": {"kind": {"const": "circle"}, "radius": {"type": "integer"}}},
+                    {"type": "object", "properties": {"kind": {"const": "square"}, "side": {"type": "integer"}}}
+                ],
+                "discriminator": {"propertyName": "kind"}
+            },
+            "mark": {
+                "oneOf": [
+                    {"type": "object", "properties": {"kind": {"const": "typeChoice1"}}},
+                    {"type": "object", "properties": {"kind": {"const": "not an id"}}}
+                ],
+                "discriminator": {"propertyName": "kind"}
+            },
+            "size": {"oneOf": [{"type": "integer"}, {"type": "string"}]}
+        }
+    }
+}"#,
+        )
+        .expect("Failed to parse tool description");
+        let config = SchemaGeneratorConfig::default().objects_as_records(true);
+        let mut schema_generator = SchemaGenerator::new_with_config(test_schema_stub(), config)
+            .expect("Failed to create schema generator");
+        schema_generator
+            .add_action_from_tool_description(&tool)
+            .expect("Failed to add tool description");
+        let schema = schema_generator.get_schema_as_str();
+        assert!(schema.contains("circle?: Test::draw::Input::shape::circle"));
+        assert!(schema.contains("square?: Test::draw::Input::shape::square"));
+        assert!(!schema.contains("shape::TypeChoice"));
+        // Unions without a discriminator keep positional names
+        assert!(schema.contains("typeChoice0?: Long"));
+        // Tags named like positional names are not used, so they cannot collide with them
+        assert!(schema.contains("typeChoice0?: Test::draw::Input::mark::TypeChoice0"));
+        assert!(schema.contains("typeChoice1?: Test::draw::Input::mark::TypeChoice1"));
+        schema_generator
+            .new_request_generator()
+            .expect("Failed to construct request generator");
+    }
+
+    #[test]
+    fn test_const_and_non_string_enums() {
+        let tool = ToolDescription::from_json_str(
+            r#"{
+    "name": "manage_job",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "operation": {"const": "create"},
+            "priority": {"type": "integer", "enum": [1, 2, 3]},
+            "confirm": {"type": "boolean", "const": true},
+            "levels": {"type": "array", "items": {"type": "integer", "enum": [0, 1]}}
+        },
+        "required": ["operation", "priority"]
+    }
+}"#,
+        )
+        .expect("Failed to parse tool description");
+        let mut schema_generator =
+            SchemaGenerator::new(test_schema_stub()).expect("Failed to create schema generator");
+        schema_generator
+            .add_action_from_tool_description(&tool)
+            .expect("Failed to add tool description");
+        let schema = schema_generator.get_schema_as_str();
+        assert!(schema.contains(r#"entity operation enum ["create"];"#));
+        assert!(schema.contains("@mcp_enum(\"[1, 2, 3]\")\n    priority: Long"));
+        assert!(schema.contains("@mcp_enum(\"[true]\")\n    confirm?: Bool"));
+        assert!(schema.contains("@mcp_enum(\"[0, 1]\")\n    levels?: Set<Long>"));
+    }
 }
 
 #[cfg(test)]
@@ -16,10 +16,16 @@
 
 mod generator;
 pub use generator::{
-    AuthorizationComponents, RequestGenerator, RequestGeneratorError, SchemaGenerator,
-    SchemaGeneratorConfig, SchemaGeneratorError,
+    AttributeChange, AuthorizationComponents, AuthorizationResponse, AuthorizedTools, Decision,
+    PolicyEvaluationError, PolicyImpact, PolicyImpactReport, RequestGenerator,
+    RequestGeneratorError, SchemaGenerator, SchemaGeneratorConfig, SchemaGeneratorError,
 };
 
+#[cfg(feature = "proxy")]
+mod proxy;
+#[cfg(feature = "proxy")]
+pub use proxy::{AuthorizationProxy, ProxyError, DENIED_ERROR_CODE, INVALID_PARAMS_ERROR_CODE};
+
 #[cfg(feature = "cli")]
 mod cli;
 #[cfg(feature = "cli")]
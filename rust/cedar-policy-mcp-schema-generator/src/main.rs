@@ -14,27 +14,20 @@
  * limitations under the License.
  */
 
-use cedar_policy_mcp_schema_generator::{CliArgs, ErrorFormat};
+use cedar_policy_mcp_schema_generator::CliArgs;
 use clap::Parser;
 use std::process::ExitCode;
 
 fn main() -> ExitCode {
     let args = CliArgs::parse();
 
-    let err_hook: Option<miette::ErrorHook> = match args.get_error_format() {
-        ErrorFormat::Human => None, // This is the default.
-        ErrorFormat::Plain => Some(Box::new(|_| {
-            Box::new(miette::NarratableReportHandler::new())
-        })),
-        ErrorFormat::Json => Some(Box::new(|_| Box::new(miette::JSONReportHandler::new()))),
-    };
-    if let Some(err_hook) = err_hook {
-        #[expect(
-            clippy::expect_used,
-            reason = "The function `set_hook` returns an error if a hook has already been installed. We have just entered `main`, so we know a hook has not been installed."
-        )]
-        miette::set_hook(err_hook).expect("failed to install error-reporting hook");
-    }
+    let error_format = args.get_error_format();
+    #[expect(
+        clippy::expect_used,
+        reason = "The function `set_hook` returns an error if a hook has already been installed. We have just entered `main`, so we know a hook has not been installed."
+    )]
+    miette::set_hook(Box::new(move |_| error_format.report_handler()))
+        .expect("failed to install error-reporting hook");
 
     match args.exec() {
         Ok(_) => ExitCode::SUCCESS,
@@ -1671,6 +1671,89 @@ mod cli {
         cmd.unwrap().assert().success().stdout("DENY\n").stderr("");
     }
 
+    #[test]
+    fn test_authorize_simple_json_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let entities_fname = temp_dir.path().join("entities.json");
+        std::fs::write(&entities_fname, "[]").unwrap();
+
+        let principal = "MyMcpServer::User::\"test_user\"";
+        let resource = "MyMcpServer::McpServer::\"test_server\"";
+        let context_json = r#"{
+    "session": {
+        "currentTimestamp": {
+            "__extn": {
+                "fn": "datetime",
+                "arg": "2025-12-16"
+            }
+        },
+        "ipaddr": {
+            "__extn": {
+                "fn": "ip",
+                "arg": "10.0.0.1"
+            }
+        }
+    }
+}"#;
+        let context_fname = temp_dir.path().join("context.json");
+        std::fs::write(&context_fname, context_json).unwrap();
+
+        let policy_fname = temp_dir.path().join("policies.cedar");
+        std::fs::write(
+            &policy_fname,
+            r#"permit(principal, action, resource) when { context.input.str_attr == "howdy" };
+permit(principal, action, resource) when { context.input.int_attr + 9223372036854775807 > 0 };
+forbid(principal, action, resource) when { context.input.bool_attr };"#,
+        )
+        .unwrap();
+
+        let input = r#"{
+    "params": {
+        "tool": "test_tool",
+        "args": {
+            "bool_attr": false,
+            "int_attr": 1,
+            "float_attr": 1.0,
+            "str_attr": "howdy",
+            "enum_attr": "variant2",
+            "dt_attr": "2025-12-16",
+            "null_attr": null
+        }
+    }
+}"#;
+        let input_fname = temp_dir.path().join("input.json");
+        std::fs::write(&input_fname, input).unwrap();
+
+        let mut cmd = cargo_bin_cmd!("cedar-policy-mcp-schema-generator");
+        let cmd = cmd
+            .arg("authorize")
+            .arg("examples/stub.cedarschema")
+            .arg("examples/simple/tool.json")
+            .arg("-l")
+            .arg(principal)
+            .arg("-r")
+            .arg(resource)
+            .arg("--context")
+            .arg(&context_fname)
+            .arg("--policies")
+            .arg(&policy_fname)
+            .arg("--entities")
+            .arg(&entities_fname)
+            .arg("--mcp-tool-input")
+            .arg(&input_fname)
+            .arg("--output-format")
+            .arg("json");
+        cmd.unwrap()
+            .assert()
+            .success()
+            .stdout(concat!(
+                r#"{"decision":"allow","determiningPolicies":["policy0"],"#,
+                r#""errors":[{"policyId":"policy1","message":"integer overflow while attempting to add the values `1` and `9223372036854775807`"}]}"#,
+                "\n"
+            ))
+            .stderr("");
+    }
+
     #[test]
     fn test_authorize_tuple_allow() {
         let temp_dir = TempDir::new().unwrap();
@@ -2081,4 +2164,245 @@ mod cli {
             .arg(&input_fname);
         cmd.unwrap().assert().success().stdout("DENY\n").stderr("");
     }
+
+    const IMPACT_POLICIES: &str = r#"permit(principal, action, resource) when { context.input.path like "/tmp/*" };
+forbid(principal, action == MyMcpServer::Action::"read_file", resource) when { context.input has follow_links && context.input.follow_links };
+forbid(principal, action == MyMcpServer::Action::"read_file", resource) when { context.input has encoding && context.input.encoding == "utf-16" };"#;
+
+    #[test]
+    fn test_impact_human_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy_fname = temp_dir.path().join("policies.cedar");
+        std::fs::write(&policy_fname, IMPACT_POLICIES).unwrap();
+
+        let mut cmd = cargo_bin_cmd!("cedar-policy-mcp-schema-generator");
+        let cmd = cmd
+            .arg("impact")
+            .arg("examples/stub.cedarschema")
+            .arg("examples/impact/old_tools.json")
+            .arg("examples/impact/new_tools.json")
+            .arg("--policies")
+            .arg(&policy_fname);
+        cmd.unwrap()
+            .assert()
+            .success()
+            .stdout(
+                r#"Added actions:
+  write_file
+Removed actions:
+  delete_file
+Added attributes:
+  read_file: input.offset (Long (optional))
+Removed attributes:
+  read_file: input.follow_links (Bool (optional))
+Retyped attributes:
+  read_file: input.encoding (String (optional) -> Long (optional))
+Impacted policies:
+  policy1:
+    references removed attribute `input.follow_links` of `read_file`
+  policy2:
+    fails to validate: the types Long and String are not compatible
+    references retyped attribute `input.encoding` of `read_file`
+"#,
+            )
+            .stderr("");
+    }
+
+    #[test]
+    fn test_impact_json_output() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy_fname = temp_dir.path().join("policies.cedar");
+        std::fs::write(&policy_fname, IMPACT_POLICIES).unwrap();
+
+        let mut cmd = cargo_bin_cmd!("cedar-policy-mcp-schema-generator");
+        let cmd = cmd
+            .arg("impact")
+            .arg("examples/stub.cedarschema")
+            .arg("examples/impact/old_tools.json")
+            .arg("examples/impact/new_tools.json")
+            .arg("--policies")
+            .arg(&policy_fname)
+            .arg("--output-format")
+            .arg("json");
+        cmd.unwrap()
+            .assert()
+            .success()
+            .stdout(concat!(
+                r#"{"addedActions":["write_file"],"removedActions":["delete_file"],"#,
+                r#""addedAttributes":[{"action":"read_file","path":"input.offset","oldType":null,"newType":"Long (optional)"}],"#,
+                r#""removedAttributes":[{"action":"read_file","path":"input.follow_links","oldType":"Bool (optional)","newType":null}],"#,
+                r#""retypedAttributes":[{"action":"read_file","path":"input.encoding","oldType":"String (optional)","newType":"Long (optional)"}],"#,
+                r#""policies":[{"policyId":"policy1","validationErrors":[],"#,
+                r#""removedAttributes":[{"action":"read_file","path":"input.follow_links","oldType":"Bool (optional)","newType":null}],"retypedAttributes":[]},"#,
+                r#"{"policyId":"policy2","validationErrors":["the types Long and String are not compatible"],"removedAttributes":[],"#,
+                r#""retypedAttributes":[{"action":"read_file","path":"input.encoding","oldType":"String (optional)","newType":"Long (optional)"}]}]}"#,
+                "\n"
+            ))
+            .stderr("");
+    }
+
+    #[test]
+    fn test_impact_no_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy_fname = temp_dir.path().join("policies.cedar");
+        std::fs::write(&policy_fname, IMPACT_POLICIES).unwrap();
+
+        let mut cmd = cargo_bin_cmd!("cedar-policy-mcp-schema-generator");
+        let cmd = cmd
+            .arg("impact")
+            .arg("examples/stub.cedarschema")
+            .arg("examples/impact/old_tools.json")
+            .arg("examples/impact/old_tools.json")
+            .arg("--policies")
+            .arg(&policy_fname);
+        cmd.unwrap()
+            .assert()
+            .success()
+            .stdout("No changes to actions or context types.\n")
+            .stderr("");
+    }
+
+    #[test]
+    fn test_validate_passes() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy_fname = temp_dir.path().join("policies.cedar");
+        std::fs::write(&policy_fname, IMPACT_POLICIES).unwrap();
+
+        let mut cmd = cargo_bin_cmd!("cedar-policy-mcp-schema-generator");
+        let cmd = cmd
+            .arg("validate")
+            .arg("examples/stub.cedarschema")
+            .arg("examples/impact/old_tools.json")
+            .arg("--policies")
+            .arg(&policy_fname);
+        cmd.unwrap()
+            .assert()
+            .success()
+            .stdout("Validation passed\n")
+            .stderr("");
+    }
+
+    #[test]
+    fn test_validate_warnings_follow_error_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy_fname = temp_dir.path().join("policies.cedar");
+        std::fs::write(
+            &policy_fname,
+            "permit(principal, action, resource) when { false };",
+        )
+        .unwrap();
+
+        let mut cmd = cargo_bin_cmd!("cedar-policy-mcp-schema-generator");
+        let cmd = cmd
+            .arg("validate")
+            .arg("examples/stub.cedarschema")
+            .arg("examples/impact/new_tools.json")
+            .arg("--policies")
+            .arg(&policy_fname)
+            .arg("--error-format")
+            .arg("json");
+        cmd.assert()
+            .success()
+            .stdout("Validation passed\n")
+            .stderr(concat!(
+                r#"{"message": "for policy `policy0`, policy is impossible: the policy expression evaluates to false for all valid requests","#,
+                r#""severity": "warning","causes": [],"filename": "","labels": [{"span": {"offset": 0,"length": 51}}],"related": []}"#,
+                "\n"
+            ));
+    }
+
+    #[test]
+    fn test_validate_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let policy_fname = temp_dir.path().join("policies.cedar");
+        std::fs::write(
+            &policy_fname,
+            r#"permit(principal, action, resource) when { context.input.path like "/tmp/*" };
+forbid(principal, action == MyMcpServer::Action::"read_file", resource) when { context.input has encoding && context.input.encoding == "utf-16" };"#,
+        )
+        .unwrap();
+
+        let mut cmd = cargo_bin_cmd!("cedar-policy-mcp-schema-generator");
+        let cmd = cmd
+            .arg("validate")
+            .arg("examples/stub.cedarschema")
+            .arg("examples/impact/new_tools.json")
+            .arg("--policies")
+            .arg(&policy_fname)
+            .arg("--error-format")
+            .arg("json");
+        cmd.assert()
+            .failure()
+            .stdout("")
+            .stderr(concat!(
+                r#"{"message": "Policies failed to validate against the generated schema","code": "cli_error::policy_validation","severity": "error","causes": [],"labels": [],"#,
+                r#""related": [{"message": "the types Long and String are not compatible","severity": "error","causes": [],"#,
+                r#""help": "for policy `policy1`, both operands to a `==` expression must have compatible types. Types must be exactly equal to be compatible","#,
+                r#""filename": "","labels": [{"span": {"offset": 188,"length": 34}}],"related": []}]}"#,
+                "\n"
+            ));
+    }
+
+    #[test]
+    fn test_proxy_with_echo_server() {
+        let temp_dir = TempDir::new().unwrap();
+        let context_fname = temp_dir.path().join("context.json");
+        std::fs::write(
+            &context_fname,
+            r#"{"session": {"currentTimestamp": {"__extn": {"fn": "datetime", "arg": "2025-12-16"}}, "ipaddr": {"__extn": {"fn": "ip", "arg": "10.0.0.1"}}}}"#,
+        )
+        .unwrap();
+        let policy_fname = temp_dir.path().join("policies.cedar");
+        std::fs::write(
+            &policy_fname,
+            r#"permit(principal, action, resource) when { context.input.path like "/tmp/*" };"#,
+        )
+        .unwrap();
+
+        // `cat` stands in for an MCP server echoing the messages it receives
+        let mut cmd = cargo_bin_cmd!("cedar-policy-mcp-schema-generator");
+        let cmd = cmd
+            .arg("proxy")
+            .arg("examples/stub.cedarschema")
+            .arg("examples/impact/new_tools.json")
+            .arg("-l")
+            .arg("MyMcpServer::User::\"alice\"")
+            .arg("-r")
+            .arg("MyMcpServer::McpServer::\"files\"")
+            .arg("--context")
+            .arg(&context_fname)
+            .arg("--policies")
+            .arg(&policy_fname)
+            .arg("--")
+            .arg("cat")
+            .write_stdin(concat!(
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {"name": "read_file", "arguments": {"path": "/tmp/notes.txt"}}}"#,
+                "\n",
+                r#"{"jsonrpc": "2.0", "id": 2, "method": "tools/call", "params": {"name": "read_file", "arguments": {"path": "/etc/passwd"}}}"#,
+                "\n",
+            ));
+        let output = cmd.unwrap();
+        output.clone().assert().success();
+        let messages = String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(messages.len(), 2);
+        assert!(messages.contains(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {"name": "read_file", "arguments": {"path": "/tmp/notes.txt"}},
+        })));
+        assert!(messages.contains(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "error": {
+                "code": -32001,
+                "message": "Call to tool `read_file` denied",
+                "data": {"determiningPolicies": []},
+            },
+        })));
+    }
 }
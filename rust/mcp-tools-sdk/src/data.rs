@@ -23,8 +23,9 @@ use std::path::Path;
 use super::deserializer;
 use super::err::DeserializationError;
 use super::parser::{self, json_value::LocatedValue};
+use super::serializer;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// A struct representing a JSON encodable `Number`.
 pub struct Number(SmolStr);
 
@@ -104,6 +105,8 @@ pub enum TypedValue {
     Union {
         /// index of which type within union type this validates against
         index: usize,
+        /// The discriminator value of the matched type, if the union type has a discriminator
+        tag: Option<SmolStr>,
         /// The validated value
         value: Box<TypedValue>,
     },
@@ -308,6 +311,86 @@ impl Input {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A struct representing an MCP `resources/read` request
+pub struct ResourceRead {
+    pub(crate) uri: SmolStr,
+}
+
+impl ResourceRead {
+    /// Construct a `resources/read` request for the resource at `uri`
+    pub fn new(uri: SmolStr) -> Self {
+        Self { uri }
+    }
+
+    /// Get the URI of the requested resource
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Deserialize an MCP `resources/read` json request into a `ResourceRead`
+    pub fn from_json_str(json_str: &str) -> Result<Self, DeserializationError> {
+        let mut parser = parser::json_parser::JsonParser::new(json_str);
+        deserializer::resource_read_from_json_value(&parser.get_value()?)
+    }
+
+    /// Deserialize an MCP `resources/read` json request into a `ResourceRead`
+    pub fn from_json_file<P: AsRef<Path>>(json_file: P) -> Result<Self, DeserializationError> {
+        let contents = std::fs::read_to_string(json_file.as_ref()).map_err(|e| {
+            DeserializationError::read_error(json_file.as_ref().into(), format!("{e}"))
+        })?;
+        Self::from_json_str(&contents)
+    }
+}
+
+#[derive(Debug, Clone)]
+/// A struct representing an MCP `prompts/get` request
+pub struct PromptGet {
+    pub(crate) name: SmolStr,
+    pub(crate) args: HashMap<SmolStr, LocatedValue>,
+}
+
+impl PromptGet {
+    /// Get the name of the requested prompt
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get all arguments to the prompt
+    pub fn get_args(&self) -> impl Iterator<Item = (&str, BorrowedValue<'_>)> {
+        self.args
+            .iter()
+            .map(|(k, v)| (k.as_str(), BorrowedValue(v)))
+    }
+
+    /// Get an argument to the prompt if it exists
+    pub fn get_arg(&self, arg: &str) -> Option<BorrowedValue<'_>> {
+        self.args.get(arg).map(BorrowedValue)
+    }
+
+    /// The `Input` of a tool call with the same name and arguments as this request
+    pub(crate) fn to_input(&self) -> Input {
+        Input {
+            name: self.name.clone(),
+            args: self.args.clone(),
+        }
+    }
+
+    /// Deserialize an MCP `prompts/get` json request into a `PromptGet`
+    pub fn from_json_str(json_str: &str) -> Result<Self, DeserializationError> {
+        let mut parser = parser::json_parser::JsonParser::new(json_str);
+        deserializer::prompt_get_from_json_value(&parser.get_value()?)
+    }
+
+    /// Deserialize an MCP `prompts/get` json request into a `PromptGet`
+    pub fn from_json_file<P: AsRef<Path>>(json_file: P) -> Result<Self, DeserializationError> {
+        let contents = std::fs::read_to_string(json_file.as_ref()).map_err(|e| {
+            DeserializationError::read_error(json_file.as_ref().into(), format!("{e}"))
+        })?;
+        Self::from_json_str(&contents)
+    }
+}
+
 #[derive(Debug, Clone)]
 /// A struct representing a validated MCP `call/tool` request with typed arguments
 pub struct TypedInput {
@@ -336,6 +419,8 @@ impl TypedInput {
 /// A struct representing an MCP `call/tool` response
 pub struct Output {
     pub(crate) results: HashMap<SmolStr, LocatedValue>,
+    /// The `content` of the response, if any
+    pub(crate) content: Option<LocatedValue>,
 }
 
 impl Output {
@@ -351,6 +436,40 @@ impl Output {
         self.results.get(res).map(BorrowedValue)
     }
 
+    /// Remove the returned result `res` if it exists, returning whether it did
+    pub fn remove_result(&mut self, res: &str) -> bool {
+        self.results.remove(res).is_some()
+    }
+
+    /// Remove the returned value at `path` if it exists, returning whether it did. The first
+    /// element of `path` names a result and the following ones name nested object properties.
+    pub fn remove_result_at(&mut self, path: &[impl AsRef<str>]) -> bool {
+        let Some((prop, parents)) = path.split_last() else {
+            return false;
+        };
+        let Some((res, parents)) = parents.split_first() else {
+            return self.remove_result(prop.as_ref());
+        };
+        let mut value = self.results.get_mut(res.as_ref());
+        for key in parents {
+            value = value
+                .and_then(LocatedValue::get_object_mut)
+                .and_then(|obj| obj.get_mut(key.as_ref()));
+        }
+        value
+            .and_then(LocatedValue::get_object_mut)
+            .is_some_and(|obj| obj.remove(prop.as_ref()).is_some())
+    }
+
+    /// Serialize this `Output` as an MCP `tools/call` json response. Its `structuredContent`
+    /// holds the returned results. The `content` of the deserialized response is kept, but the
+    /// text of its text blocks holding a JSON object is replaced by the serialized results (or,
+    /// without a `content`, the results are repeated in a text block). Other content blocks are
+    /// kept as is, so they may still disclose removed results.
+    pub fn to_json_string(&self) -> String {
+        serializer::output_to_json(self).to_string()
+    }
+
     /// Deserialize an MCP `tools/call` json response into an `Output`
     pub fn from_json_str(json_str: &str) -> Result<Self, DeserializationError> {
         let mut parser = parser::json_parser::JsonParser::new(json_str);
@@ -1000,6 +1119,35 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_output_remove_result_at() {
+        let output = r#"{
+    "result": {
+        "content": [],
+        "structuredContent": {
+            "customer": {"name": "Alice", "address": {"city": "Paris", "zip": "75001"}},
+            "id": 1
+        }
+    }
+}"#;
+        let mut output = Output::from_json_str(output).expect("Failed to parse output");
+        assert!(output.remove_result_at(&["customer", "address", "zip"]));
+        assert!(!output.remove_result_at(&["customer", "address", "zip"]));
+        assert!(!output.remove_result_at(&["customer", "name", "first"]));
+        assert!(!output.remove_result_at(&["missing", "name"]));
+        assert!(!output.remove_result_at(&[] as &[&str]));
+        let customer = output
+            .results
+            .get("customer")
+            .expect("customer was removed");
+        assert!(customer.get("name").is_some());
+        let address = customer.get("address").expect("address was removed");
+        assert!(address.get("city").is_some());
+        assert!(address.get("zip").is_none());
+        assert!(output.remove_result_at(&["id"]));
+        assert!(output.get_result("id").is_none());
+    }
+
     #[test]
     fn test_output_not_object_errors() {
         let output = r#""Not a well formed MCP \"tools/call\" output""#;
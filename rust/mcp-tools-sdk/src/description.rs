@@ -16,14 +16,17 @@
 
 //! This module defines representations for properties in MCP tools definitions.
 
+use regex::Regex;
 use smol_str::{SmolStr, ToSmolStr};
 use std::collections::HashMap;
 use std::path::Path;
 
-use super::data::{self, Input, Output};
+use super::data::{self, Input, Number, Output, PromptGet};
 use super::deserializer;
-use super::err::{DeserializationError, ValidationError};
+use super::err::{DeserializationError, ValidationError, ValidationErrors};
 use super::parser;
+use super::serializer;
+use super::uri_template::UriTemplate;
 use super::validation::{validate_input, validate_output};
 
 /// The type a `Property` can take: supported types in JSON Schema maps to `PropertyTypes`.
@@ -48,19 +51,38 @@ pub enum PropertyType {
     Decimal,
     /// A datetime: `{"type": "string", "format": "date"}` or `{"type": "string", "format": "date-time"}`
     /// in JSON Schema. Both standard formats map to this variant.
-    Datetime,
+    Datetime {
+        /// The `format` of the datetime
+        format: DatetimeFormat,
+    },
     /// A duration: `{"type": "string", "format": "duration"}` in JSON Schema.
     Duration,
     /// An IP address: `{"type": "string", "format": "ipv4"}` or `{"type": "string", "format": "ipv6"}`
     /// in JSON Schema. Both standard formats map to this variant.
-    IpAddr,
+    IpAddr {
+        /// The `format` of the IP address
+        format: IpAddrFormat,
+    },
     /// A null value: `{"type": "null"}` in JSON Schema.
     Null,
     /// An enumeration: `{"type": "string", "enum": ["a", "b", ...]}` in JSON Schema.
+    /// A string `const` (e.g., `{"const": "a"}`) is an enumeration with a single variant.
     Enum {
         /// The variants of the enum; an ordered list of strings
         variants: Vec<SmolStr>,
     },
+    /// An enumeration of integers: `{"type": "integer", "enum": [1, 2, ...]}` in JSON Schema.
+    /// An integer `const` (e.g., `{"const": 1}`) is an enumeration with a single variant.
+    IntegerEnum {
+        /// The variants of the enum; an ordered list of integers
+        variants: Vec<i64>,
+    },
+    /// An enumeration of booleans: `{"type": "boolean", "enum": [true]}` or
+    /// `{"type": "boolean", "const": true}` in JSON Schema.
+    BoolEnum {
+        /// The variants of the enum; an ordered list of booleans
+        variants: Vec<bool>,
+    },
     /// A homogeneous array: `{"type": "array", "items": <schema>}` in JSON Schema.
     Array {
         /// The type of elements of the homogenous array
@@ -77,6 +99,10 @@ pub enum PropertyType {
     Union {
         /// The set of types in the union
         types: Vec<PropertyType>,
+        /// Whether values must match at least one (`anyOf`) or exactly one (`oneOf`) of the types
+        kind: UnionKind,
+        /// The OpenAPI-style `discriminator` of the union, if any
+        discriminator: Option<Discriminator>,
     },
     /// An object: `{"type": "object", "properties": {...}}` in JSON Schema.
     /// `additional_properties` corresponds to the `additionalProperties` keyword when it is a schema object.
@@ -91,6 +117,212 @@ pub enum PropertyType {
         /// The name of the type being referenced
         name: SmolStr,
     },
+    /// A type refined by JSON Schema validation keywords, e.g., `{"type": "integer", "minimum": 0}`.
+    /// Only produced when at least one supported constraint keyword is present.
+    Constrained {
+        /// The type being constrained
+        ty: Box<PropertyType>,
+        /// The constraints values of this type must satisfy
+        constraints: Box<PropertyConstraints>,
+    },
+}
+
+impl PropertyType {
+    /// Returns the underlying type, looking through any [`PropertyType::Constrained`] wrapper.
+    pub fn base_type(&self) -> &PropertyType {
+        match self {
+            Self::Constrained { ty, .. } => ty.base_type(),
+            _ => self,
+        }
+    }
+
+    /// Returns the [`PropertyConstraints`] of this type if it is a [`PropertyType::Constrained`].
+    pub fn constraints(&self) -> Option<&PropertyConstraints> {
+        match self {
+            Self::Constrained { constraints, .. } => Some(constraints),
+            _ => None,
+        }
+    }
+
+    /// Serialize this type as a JSON Schema.
+    pub fn to_json_string(&self) -> String {
+        serializer::property_type_to_json(self).to_string()
+    }
+}
+
+/// The JSON Schema `format` of a [`PropertyType::Datetime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DatetimeFormat {
+    /// `{"format": "date"}`: a full date, e.g., `2025-01-31`.
+    Date,
+    /// `{"format": "date-time"}`: a date and time, e.g., `2025-01-31T12:00:00Z`.
+    DateTime,
+}
+
+/// The JSON Schema `format` of a [`PropertyType::IpAddr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IpAddrFormat {
+    /// `{"format": "ipv4"}`: an IPv4 address.
+    Ipv4,
+    /// `{"format": "ipv6"}`: an IPv6 address.
+    Ipv6,
+}
+
+/// How values are matched against the types of a [`PropertyType::Union`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnionKind {
+    /// `{"anyOf": [...]}` (or a `type` array): values must match at least one type.
+    AnyOf,
+    /// `{"oneOf": [...]}`: values must match exactly one type.
+    OneOf,
+}
+
+/// An OpenAPI-style discriminator of a [`PropertyType::Union`]:
+/// `{"oneOf": [...], "discriminator": {"propertyName": "kind"}}`.
+///
+/// The discriminator value of each type in the union is the `const` value of its
+/// `propertyName` property, the key of the `mapping` entry referencing it, or the name
+/// of the type definition it references. A discriminator is only kept if every type in
+/// the union has a distinct discriminator value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Discriminator {
+    pub(crate) property_name: SmolStr,
+    pub(crate) values: Vec<SmolStr>,
+}
+
+impl Discriminator {
+    /// The name of the property whose value identifies the type of a value
+    pub fn property_name(&self) -> &str {
+        &self.property_name
+    }
+
+    /// The discriminator value of each type of the union (in the same order as the types)
+    pub fn values(&self) -> &[SmolStr] {
+        &self.values
+    }
+}
+
+/// The JSON Schema validation keywords restricting the values a [`PropertyType`] may take.
+///
+/// Numeric constraints only apply to numbers, length and `pattern` constraints only apply
+/// to strings, and item constraints only apply to arrays (matching JSON Schema semantics).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PropertyConstraints {
+    pub(crate) minimum: Option<Number>,
+    pub(crate) maximum: Option<Number>,
+    pub(crate) exclusive_minimum: Option<Number>,
+    pub(crate) exclusive_maximum: Option<Number>,
+    pub(crate) multiple_of: Option<Number>,
+    pub(crate) min_length: Option<usize>,
+    pub(crate) max_length: Option<usize>,
+    pub(crate) pattern: Option<Pattern>,
+    pub(crate) min_items: Option<usize>,
+    pub(crate) max_items: Option<usize>,
+    pub(crate) unique_items: bool,
+}
+
+impl PropertyConstraints {
+    /// Returns `true` if no constraint is set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// The inclusive lower bound of numbers (`minimum`).
+    pub fn minimum(&self) -> Option<&Number> {
+        self.minimum.as_ref()
+    }
+
+    /// The inclusive upper bound of numbers (`maximum`).
+    pub fn maximum(&self) -> Option<&Number> {
+        self.maximum.as_ref()
+    }
+
+    /// The exclusive lower bound of numbers (`exclusiveMinimum`).
+    pub fn exclusive_minimum(&self) -> Option<&Number> {
+        self.exclusive_minimum.as_ref()
+    }
+
+    /// The exclusive upper bound of numbers (`exclusiveMaximum`).
+    pub fn exclusive_maximum(&self) -> Option<&Number> {
+        self.exclusive_maximum.as_ref()
+    }
+
+    /// The number that numbers must be a multiple of (`multipleOf`).
+    pub fn multiple_of(&self) -> Option<&Number> {
+        self.multiple_of.as_ref()
+    }
+
+    /// The minimum number of characters in strings (`minLength`).
+    pub fn min_length(&self) -> Option<usize> {
+        self.min_length
+    }
+
+    /// The maximum number of characters in strings (`maxLength`).
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// The regular expression strings must match (`pattern`).
+    pub fn pattern(&self) -> Option<&Pattern> {
+        self.pattern.as_ref()
+    }
+
+    /// The minimum number of elements in arrays (`minItems`).
+    pub fn min_items(&self) -> Option<usize> {
+        self.min_items
+    }
+
+    /// The maximum number of elements in arrays (`maxItems`).
+    pub fn max_items(&self) -> Option<usize> {
+        self.max_items
+    }
+
+    /// Returns `true` if array elements must be pairwise distinct (`uniqueItems`).
+    pub fn unique_items(&self) -> bool {
+        self.unique_items
+    }
+}
+
+/// A regular expression from the `pattern` keyword of a JSON Schema.
+///
+/// Like JSON Schema, matching is unanchored: a string matches if any substring matches.
+/// Two `Pattern`s are equal if their source strings are equal.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: SmolStr,
+    regex: Regex,
+}
+
+impl Pattern {
+    /// Compile a new `Pattern`, failing if `source` is not a supported regular expression.
+    pub(crate) fn new(source: SmolStr) -> Result<Self, regex::Error> {
+        let regex = Regex::new(&source)?;
+        Ok(Self { source, regex })
+    }
+
+    /// Returns the source of this regular expression.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns `true` if `s` matches this regular expression.
+    pub fn is_match(&self, s: &str) -> bool {
+        self.regex.is_match(s)
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for Pattern {}
+
+impl std::hash::Hash for Pattern {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.source.hash(state);
+    }
 }
 
 /// Representation of an input (or output) `Property`
@@ -141,7 +373,7 @@ impl Property {
 }
 
 /// Representation of a `TypeDef` used for defining `Property`s
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PropertyTypeDef {
     pub(crate) name: SmolStr,
     pub(crate) prop_type: PropertyType,
@@ -172,10 +404,15 @@ impl PropertyTypeDef {
     pub fn description(&self) -> Option<&str> {
         self.description.as_deref()
     }
+
+    /// Serialize this type definition as the JSON Schema it has in `$defs`.
+    pub fn to_json_string(&self) -> String {
+        serializer::type_def_to_json(self).to_string()
+    }
 }
 
 /// Container for convienently representing a collection of `TypeDefs`
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct PropertyTypeDefs {
     pub(crate) type_defs: HashMap<SmolStr, PropertyTypeDef>,
 }
@@ -195,7 +432,7 @@ impl PropertyTypeDefs {
 /// A collection of Input (or Output) Properties of an MCP tool Description
 /// I.e., a Representation of the data in the `Parameters`, `InputSchema` or `OutputSchema`
 /// attribute of an MCP tool Descritpion
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Parameters {
     pub(crate) properties: Vec<Property>,
     pub(crate) type_defs: PropertyTypeDefs,
@@ -219,16 +456,130 @@ impl Parameters {
     pub fn type_definitions(&self) -> impl Iterator<Item = &PropertyTypeDef> {
         self.type_defs.values()
     }
+
+    /// Serialize these `Parameters` as the JSON Schema of an object (e.g., a tool's `inputSchema`).
+    pub fn to_json_string(&self) -> String {
+        serializer::parameters_to_json(self).to_string()
+    }
+}
+
+/// The annotations of an MCP tool (i.e., the `annotations` attribute of an MCP Tool Description).
+///
+/// Annotations are hints describing the behavior of a tool. Each hint is `None` unless set by the
+/// tool description; the `is_*` methods apply the defaults of the MCP specification to absent hints.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolAnnotations {
+    pub(crate) title: Option<String>,
+    pub(crate) read_only_hint: Option<bool>,
+    pub(crate) destructive_hint: Option<bool>,
+    pub(crate) idempotent_hint: Option<bool>,
+    pub(crate) open_world_hint: Option<bool>,
+}
+
+impl ToolAnnotations {
+    /// Set the human-readable title of the tool
+    pub fn with_title(self, title: String) -> Self {
+        Self {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    /// Set the `readOnlyHint` of the tool
+    pub fn with_read_only_hint(self, val: bool) -> Self {
+        Self {
+            read_only_hint: Some(val),
+            ..self
+        }
+    }
+
+    /// Set the `destructiveHint` of the tool
+    pub fn with_destructive_hint(self, val: bool) -> Self {
+        Self {
+            destructive_hint: Some(val),
+            ..self
+        }
+    }
+
+    /// Set the `idempotentHint` of the tool
+    pub fn with_idempotent_hint(self, val: bool) -> Self {
+        Self {
+            idempotent_hint: Some(val),
+            ..self
+        }
+    }
+
+    /// Set the `openWorldHint` of the tool
+    pub fn with_open_world_hint(self, val: bool) -> Self {
+        Self {
+            open_world_hint: Some(val),
+            ..self
+        }
+    }
+
+    /// Get the human-readable title of the tool (if it exists)
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Get the `readOnlyHint` of the tool (if it exists)
+    pub fn read_only_hint(&self) -> Option<bool> {
+        self.read_only_hint
+    }
+
+    /// Get the `destructiveHint` of the tool (if it exists)
+    pub fn destructive_hint(&self) -> Option<bool> {
+        self.destructive_hint
+    }
+
+    /// Get the `idempotentHint` of the tool (if it exists)
+    pub fn idempotent_hint(&self) -> Option<bool> {
+        self.idempotent_hint
+    }
+
+    /// Get the `openWorldHint` of the tool (if it exists)
+    pub fn open_world_hint(&self) -> Option<bool> {
+        self.open_world_hint
+    }
+
+    /// Whether the tool does not modify its environment (default: false)
+    pub fn is_read_only(&self) -> bool {
+        self.read_only_hint.unwrap_or(false)
+    }
+
+    /// Whether the tool may perform destructive updates to its environment (default: true).
+    /// Read-only tools are never destructive.
+    pub fn is_destructive(&self) -> bool {
+        !self.is_read_only() && self.destructive_hint.unwrap_or(true)
+    }
+
+    /// Whether calling the tool repeatedly with the same arguments has no additional effect
+    /// on its environment (default: false). Read-only tools are always idempotent.
+    pub fn is_idempotent(&self) -> bool {
+        self.is_read_only() || self.idempotent_hint.unwrap_or(false)
+    }
+
+    /// Whether the tool may interact with an "open world" of external entities (default: true)
+    pub fn is_open_world(&self) -> bool {
+        self.open_world_hint.unwrap_or(true)
+    }
+
+    /// Whether no annotation is set
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
 }
 
 /// A Representation of a Single Tool Description
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ToolDescription {
     pub(crate) name: SmolStr,
+    pub(crate) title: Option<String>,
     pub(crate) description: Option<String>,
     pub(crate) inputs: Parameters,
     pub(crate) outputs: Parameters,
     pub(crate) type_defs: PropertyTypeDefs,
+    pub(crate) annotations: ToolAnnotations,
 }
 
 impl ToolDescription {
@@ -242,10 +593,28 @@ impl ToolDescription {
     ) -> Self {
         Self {
             name,
+            title: None,
             description,
             inputs,
             outputs,
             type_defs: PropertyTypeDefs::new(type_defs),
+            annotations: ToolAnnotations::default(),
+        }
+    }
+
+    /// Set the human-readable title of this tool (i.e., its `title` attribute)
+    pub fn with_title(self, title: String) -> Self {
+        Self {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    /// Set the annotations of this tool
+    pub fn with_annotations(self, annotations: ToolAnnotations) -> Self {
+        Self {
+            annotations,
+            ..self
         }
     }
 
@@ -254,6 +623,17 @@ impl ToolDescription {
         &self.name
     }
 
+    /// Get the human-readable title of this tool (if it exists).
+    /// This is the tool's `title` attribute, or else the `title` of its annotations.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref().or_else(|| self.annotations.title())
+    }
+
+    /// Get the annotations of this tool
+    pub fn annotations(&self) -> &ToolAnnotations {
+        &self.annotations
+    }
+
     /// Get the description of this tool (if it exists)
     pub fn description(&self) -> Option<&str> {
         self.description.as_deref()
@@ -275,6 +655,11 @@ impl ToolDescription {
         self.type_defs.values()
     }
 
+    /// Serialize this `ToolDescription` as an MCP Tool Description JSON.
+    pub fn to_json_string(&self) -> String {
+        serializer::tool_description_to_json(self).to_string()
+    }
+
     /// Deserialize an MCP Tool Description JSON into a `ToolDescription`
     pub fn from_json_str(json_str: &str) -> Result<Self, DeserializationError> {
         let mut parser = parser::json_parser::JsonParser::new(json_str);
@@ -295,7 +680,7 @@ impl ToolDescription {
         input: &Input,
         type_defs: HashMap<SmolStr, PropertyTypeDef>,
     ) -> Result<data::TypedInput, ValidationError> {
-        validate_input(self, input, type_defs)
+        validate_input(self, input, type_defs).map_err(|e| e.into_first_error())
     }
 
     /// Validates the `Output` matches this `ToolDescription`'s output schema.
@@ -304,15 +689,38 @@ impl ToolDescription {
         output: &Output,
         type_defs: HashMap<SmolStr, PropertyTypeDef>,
     ) -> Result<data::TypedOutput, ValidationError> {
+        validate_output(self, output, type_defs).map_err(|e| e.into_first_error())
+    }
+
+    /// Validates the `Input` matches this `ToolDescription`'s input schema.
+    /// Unlike `validate_input`, validation does not stop at the first error: every
+    /// error is reported along with the JSON Pointer to, and source location of, the
+    /// offending argument.
+    pub fn validate_input_accumulating(
+        &self,
+        input: &Input,
+        type_defs: HashMap<SmolStr, PropertyTypeDef>,
+    ) -> Result<data::TypedInput, ValidationErrors> {
+        validate_input(self, input, type_defs).map_err(|e| e.into_validation_errors(&input.args))
+    }
+
+    /// Validates the `Output` matches this `ToolDescription`'s output schema,
+    /// reporting every error found (see `validate_input_accumulating`).
+    pub fn validate_output_accumulating(
+        &self,
+        output: &Output,
+        type_defs: HashMap<SmolStr, PropertyTypeDef>,
+    ) -> Result<data::TypedOutput, ValidationErrors> {
         validate_output(self, output, type_defs)
+            .map_err(|e| e.into_validation_errors(&output.results))
     }
 }
 
 /// A representation of a collection of MCP Tools (e.g., all the tools provided by an MCP Server)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ServerDescription {
-    tools: HashMap<SmolStr, ToolDescription>,
-    type_defs: PropertyTypeDefs,
+    pub(crate) tools: HashMap<SmolStr, ToolDescription>,
+    pub(crate) type_defs: PropertyTypeDefs,
 }
 
 impl ServerDescription {
@@ -333,18 +741,448 @@ impl ServerDescription {
         self.tools.values()
     }
 
-    /// Get any `TypeDefs` defined within this `ServerDescription` (i.e., `TypeDefs` shared between tools)
-    pub fn type_definitions(&self) -> impl Iterator<Item = &PropertyTypeDef> {
-        self.type_defs.values()
+    /// Get any `TypeDefs` defined within this `ServerDescription` (i.e., `TypeDefs` shared between tools)
+    pub fn type_definitions(&self) -> impl Iterator<Item = &PropertyTypeDef> {
+        self.type_defs.values()
+    }
+
+    /// Serialize this `ServerDescription` as an MCP `tools/list` json response.
+    /// Tools and type definitions are ordered by name.
+    pub fn to_json_string(&self) -> String {
+        serializer::server_description_to_json(self).to_string()
+    }
+
+    /// Deserialize an MCP `tools/list` json response (or JSON Array of Tool Descriptions) into a `ServerDescription`
+    pub fn from_json_str(json_str: &str) -> Result<Self, DeserializationError> {
+        let mut parser = parser::json_parser::JsonParser::new(json_str);
+        deserializer::server_description_from_json_value(parser.get_value()?)
+    }
+
+    /// Deserialize an MCP `tools/list` json response (or JSON Array of Tool Descriptions) into a `ServerDescription`
+    pub fn from_json_file<P: AsRef<Path>>(json_file: P) -> Result<Self, DeserializationError> {
+        let contents = std::fs::read_to_string(json_file.as_ref()).map_err(|e| {
+            DeserializationError::read_error(json_file.as_ref().into(), format!("{e}"))
+        })?;
+        Self::from_json_str(&contents)
+    }
+
+    /// Validate the `Input` against the corresponding tool within this `ServerDescription`
+    pub fn validate_input(&self, input: &Input) -> Result<data::TypedInput, ValidationError> {
+        match self.tools.get(input.name()) {
+            Some(tool) => tool.validate_input(input, self.type_defs.type_defs.clone()),
+            None => Err(ValidationError::tool_not_found(input.name().into())),
+        }
+    }
+
+    /// Validate the `Output` against the corresponding tool within this `ServerDescription`
+    pub fn validate_output(
+        &self,
+        tool_name: &str,
+        output: &Output,
+    ) -> Result<data::TypedOutput, ValidationError> {
+        match self.tools.get(tool_name) {
+            Some(tool) => tool.validate_output(output, self.type_defs.type_defs.clone()),
+            None => Err(ValidationError::tool_not_found(tool_name.into())),
+        }
+    }
+
+    /// Validate the `Input` against the corresponding tool within this `ServerDescription`,
+    /// reporting every error found (see `ToolDescription::validate_input_accumulating`).
+    pub fn validate_input_accumulating(
+        &self,
+        input: &Input,
+    ) -> Result<data::TypedInput, ValidationErrors> {
+        match self.tools.get(input.name()) {
+            Some(tool) => tool.validate_input_accumulating(input, self.type_defs.type_defs.clone()),
+            None => Err(ValidationError::tool_not_found(input.name().into()).into()),
+        }
+    }
+
+    /// Validate the `Output` against the corresponding tool within this `ServerDescription`,
+    /// reporting every error found (see `ToolDescription::validate_input_accumulating`).
+    pub fn validate_output_accumulating(
+        &self,
+        tool_name: &str,
+        output: &Output,
+    ) -> Result<data::TypedOutput, ValidationErrors> {
+        match self.tools.get(tool_name) {
+            Some(tool) => {
+                tool.validate_output_accumulating(output, self.type_defs.type_defs.clone())
+            }
+            None => Err(ValidationError::tool_not_found(tool_name.into()).into()),
+        }
+    }
+}
+
+/// A Representation of a single MCP Resource (i.e., an element of the `resources/list` response)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceDescription {
+    pub(crate) uri: SmolStr,
+    pub(crate) name: SmolStr,
+    pub(crate) title: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) mime_type: Option<String>,
+}
+
+impl ResourceDescription {
+    /// Construct a Resource Description from its components
+    pub fn new(uri: SmolStr, name: SmolStr, description: Option<String>) -> Self {
+        Self {
+            uri,
+            name,
+            title: None,
+            description,
+            mime_type: None,
+        }
+    }
+
+    /// Set the human-readable title of this resource (i.e., its `title` attribute)
+    pub fn with_title(self, title: String) -> Self {
+        Self {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    /// Set the MIME type of this resource (i.e., its `mimeType` attribute)
+    pub fn with_mime_type(self, mime_type: String) -> Self {
+        Self {
+            mime_type: Some(mime_type),
+            ..self
+        }
+    }
+
+    /// Get the URI of this resource
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Get the name of this resource
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the human-readable title of this resource (if it exists)
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Get the description of this resource (if it exists)
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the MIME type of this resource (if it exists)
+    pub fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_deref()
+    }
+}
+
+/// A Representation of a single MCP Resource Template (i.e., an element of the `resources/templates/list` response)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceTemplateDescription {
+    pub(crate) uri_template: UriTemplate,
+    pub(crate) name: SmolStr,
+    pub(crate) title: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) mime_type: Option<String>,
+}
+
+impl ResourceTemplateDescription {
+    /// Construct a Resource Template Description from its components
+    pub fn new(uri_template: UriTemplate, name: SmolStr, description: Option<String>) -> Self {
+        Self {
+            uri_template,
+            name,
+            title: None,
+            description,
+            mime_type: None,
+        }
+    }
+
+    /// Set the human-readable title of this resource template (i.e., its `title` attribute)
+    pub fn with_title(self, title: String) -> Self {
+        Self {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    /// Set the MIME type of the resources matching this template (i.e., its `mimeType` attribute)
+    pub fn with_mime_type(self, mime_type: String) -> Self {
+        Self {
+            mime_type: Some(mime_type),
+            ..self
+        }
+    }
+
+    /// Get the URI Template of this resource template
+    pub fn uri_template(&self) -> &UriTemplate {
+        &self.uri_template
+    }
+
+    /// Get the name of this resource template
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the human-readable title of this resource template (if it exists)
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Get the description of this resource template (if it exists)
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the MIME type of the resources matching this template (if it exists)
+    pub fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_deref()
+    }
+}
+
+/// A representation of the resources and resource templates provided by an MCP Server
+/// (i.e., the output of `resources/list` and `resources/templates/list`)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ResourceList {
+    pub(crate) resources: Vec<ResourceDescription>,
+    pub(crate) templates: Vec<ResourceTemplateDescription>,
+}
+
+impl ResourceList {
+    /// Create a new Resource List from its components
+    pub fn new(
+        resources: impl Iterator<Item = ResourceDescription>,
+        templates: impl Iterator<Item = ResourceTemplateDescription>,
+    ) -> Self {
+        Self {
+            resources: resources.collect(),
+            templates: templates.collect(),
+        }
+    }
+
+    /// Get an iterator to all resource descriptions within this `ResourceList`, in listing order
+    pub fn resources(&self) -> impl Iterator<Item = &ResourceDescription> {
+        self.resources.iter()
+    }
+
+    /// Get an iterator to all resource template descriptions within this `ResourceList`, in listing order
+    pub fn resource_templates(&self) -> impl Iterator<Item = &ResourceTemplateDescription> {
+        self.templates.iter()
+    }
+
+    /// Add the resources and resource templates of `other` to this `ResourceList`
+    /// (e.g., to combine the `resources/list` and `resources/templates/list` responses of a server)
+    pub fn extend(&mut self, other: ResourceList) {
+        self.resources.extend(other.resources);
+        self.templates.extend(other.templates);
+    }
+
+    /// Find the first resource template (in listing order) whose URI Template matches `uri`,
+    /// returning it along with the values of the template's variables defined by `uri`
+    pub fn match_uri(
+        &self,
+        uri: &str,
+    ) -> Option<(&ResourceTemplateDescription, HashMap<SmolStr, SmolStr>)> {
+        self.templates.iter().find_map(|template| {
+            template
+                .uri_template()
+                .match_uri(uri)
+                .map(|values| (template, values))
+        })
+    }
+
+    /// Deserialize an MCP `resources/list` or `resources/templates/list` json response
+    /// (or JSON Array of Resource and Resource Template Descriptions) into a `ResourceList`
+    pub fn from_json_str(json_str: &str) -> Result<Self, DeserializationError> {
+        let mut parser = parser::json_parser::JsonParser::new(json_str);
+        deserializer::resource_list_from_json_value(&parser.get_value()?)
+    }
+
+    /// Deserialize an MCP `resources/list` or `resources/templates/list` json response
+    /// (or JSON Array of Resource and Resource Template Descriptions) into a `ResourceList`
+    pub fn from_json_file<P: AsRef<Path>>(json_file: P) -> Result<Self, DeserializationError> {
+        let contents = std::fs::read_to_string(json_file.as_ref()).map_err(|e| {
+            DeserializationError::read_error(json_file.as_ref().into(), format!("{e}"))
+        })?;
+        Self::from_json_str(&contents)
+    }
+}
+
+/// A Representation of a single argument of an MCP Prompt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptArgument {
+    pub(crate) name: SmolStr,
+    pub(crate) title: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) required: bool,
+}
+
+impl PromptArgument {
+    /// Construct a Prompt Argument from its components
+    pub fn new(name: SmolStr, required: bool, description: Option<String>) -> Self {
+        Self {
+            name,
+            title: None,
+            description,
+            required,
+        }
+    }
+
+    /// Set the human-readable title of this argument (i.e., its `title` attribute)
+    pub fn with_title(self, title: String) -> Self {
+        Self {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    /// Get the name of this argument
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the human-readable title of this argument (if it exists)
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Get the description of this argument (if it exists)
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Check if this argument must be provided to get the prompt
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
+/// A Representation of a single MCP Prompt (i.e., an element of the `prompts/list` response)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptDescription {
+    pub(crate) name: SmolStr,
+    pub(crate) title: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) arguments: Vec<PromptArgument>,
+}
+
+impl PromptDescription {
+    /// Construct a Prompt Description from its components
+    pub fn new(
+        name: SmolStr,
+        arguments: impl Iterator<Item = PromptArgument>,
+        description: Option<String>,
+    ) -> Self {
+        Self {
+            name,
+            title: None,
+            description,
+            arguments: arguments.collect(),
+        }
+    }
+
+    /// Set the human-readable title of this prompt (i.e., its `title` attribute)
+    pub fn with_title(self, title: String) -> Self {
+        Self {
+            title: Some(title),
+            ..self
+        }
+    }
+
+    /// Get the name of this prompt
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the human-readable title of this prompt (if it exists)
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Get the description of this prompt (if it exists)
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get an iterator to the arguments of this prompt, in listing order
+    pub fn arguments(&self) -> impl Iterator<Item = &PromptArgument> {
+        self.arguments.iter()
+    }
+
+    /// Get the `Parameters` describing the arguments of this prompt.
+    /// MCP Prompt arguments are strings, so each argument is a `String` property.
+    pub fn inputs(&self) -> Parameters {
+        let properties = self
+            .arguments
+            .iter()
+            .map(|arg| {
+                Property::new(
+                    arg.name.clone(),
+                    arg.required,
+                    PropertyType::String,
+                    arg.description.clone(),
+                )
+            })
+            .collect();
+        Parameters::new(properties, HashMap::new())
+    }
+
+    /// Get a `ToolDescription` whose input schema describes the arguments of this prompt
+    /// (see `inputs`), allowing prompts to be handled like tools without outputs.
+    pub fn to_tool_description(&self) -> ToolDescription {
+        let tool = ToolDescription::new(
+            self.name.clone(),
+            self.inputs(),
+            Parameters::new(Vec::new(), HashMap::new()),
+            HashMap::new(),
+            self.description.clone(),
+        );
+        match &self.title {
+            Some(title) => tool.with_title(title.clone()),
+            None => tool,
+        }
+    }
+
+    /// Validates the arguments of the `PromptGet` request against the arguments of this prompt.
+    pub fn validate_get(&self, get: &PromptGet) -> Result<data::TypedInput, ValidationError> {
+        self.to_tool_description()
+            .validate_input(&get.to_input(), HashMap::new())
+    }
+}
+
+/// A representation of the prompts provided by an MCP Server (i.e., the output of `prompts/list`)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PromptList {
+    pub(crate) prompts: Vec<PromptDescription>,
+}
+
+impl PromptList {
+    /// Create a new Prompt List from its components
+    pub fn new(prompts: impl Iterator<Item = PromptDescription>) -> Self {
+        Self {
+            prompts: prompts.collect(),
+        }
+    }
+
+    /// Get an iterator to all prompt descriptions within this `PromptList`, in listing order
+    pub fn prompts(&self) -> impl Iterator<Item = &PromptDescription> {
+        self.prompts.iter()
+    }
+
+    /// Get the description of the prompt named `name` (if it exists)
+    pub fn prompt(&self, name: &str) -> Option<&PromptDescription> {
+        self.prompts.iter().find(|prompt| prompt.name() == name)
     }
 
-    /// Deserialize an MCP `tools/list` json response (or JSON Array of Tool Descriptions) into a `ServerDescription`
+    /// Deserialize an MCP `prompts/list` json response (or JSON Array of Prompt Descriptions) into a `PromptList`
     pub fn from_json_str(json_str: &str) -> Result<Self, DeserializationError> {
         let mut parser = parser::json_parser::JsonParser::new(json_str);
-        deserializer::server_description_from_json_value(parser.get_value()?)
+        deserializer::prompt_list_from_json_value(&parser.get_value()?)
     }
 
-    /// Deserialize an MCP `tools/list` json response (or JSON Array of Tool Descriptions) into a `ServerDescription`
+    /// Deserialize an MCP `prompts/list` json response (or JSON Array of Prompt Descriptions) into a `PromptList`
     pub fn from_json_file<P: AsRef<Path>>(json_file: P) -> Result<Self, DeserializationError> {
         let contents = std::fs::read_to_string(json_file.as_ref()).map_err(|e| {
             DeserializationError::read_error(json_file.as_ref().into(), format!("{e}"))
@@ -352,23 +1190,11 @@ impl ServerDescription {
         Self::from_json_str(&contents)
     }
 
-    /// Validate the `Input` against the corresponding tool within this `ServerDescription`
-    pub fn validate_input(&self, input: &Input) -> Result<data::TypedInput, ValidationError> {
-        match self.tools.get(input.name()) {
-            Some(tool) => tool.validate_input(input, self.type_defs.type_defs.clone()),
-            None => Err(ValidationError::tool_not_found(input.name().into())),
-        }
-    }
-
-    /// Validate the `Output` against the corresponding tool within this `ServerDescription`
-    pub fn validate_output(
-        &self,
-        tool_name: &str,
-        output: &Output,
-    ) -> Result<data::TypedOutput, ValidationError> {
-        match self.tools.get(tool_name) {
-            Some(tool) => tool.validate_output(output, self.type_defs.type_defs.clone()),
-            None => Err(ValidationError::tool_not_found(tool_name.into())),
+    /// Validate the `PromptGet` request against the corresponding prompt within this `PromptList`
+    pub fn validate_get(&self, get: &PromptGet) -> Result<data::TypedInput, ValidationError> {
+        match self.prompt(get.name()) {
+            Some(prompt) => prompt.validate_get(get),
+            None => Err(ValidationError::prompt_not_found(get.name().into())),
         }
     }
 }
@@ -376,7 +1202,9 @@ impl ServerDescription {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::err::ValidationDiagnostic;
     use cool_asserts::assert_matches;
+    use miette::Diagnostic;
     use smol_str::ToSmolStr;
 
     #[test]
@@ -397,11 +1225,18 @@ mod test {
     fn test_type_def() {
         let type_def = PropertyTypeDef::new(
             "my_type".into(),
-            PropertyType::Datetime,
+            PropertyType::Datetime {
+                format: DatetimeFormat::Date,
+            },
             Some("My Type".to_string()),
         );
         assert!(type_def.name() == "my_type");
-        assert_matches!(type_def.property_type(), PropertyType::Datetime);
+        assert_matches!(
+            type_def.property_type(),
+            PropertyType::Datetime {
+                format: DatetimeFormat::Date
+            }
+        );
         assert_matches!(type_def.description(), Some("My Type"));
     }
 
@@ -2113,4 +2948,517 @@ mod test {
             Err(ValidationError::UnexpectedTypeName(..))
         )
     }
+
+    fn validate_constrained_attr(
+        schema: &str,
+        value: &str,
+    ) -> Result<data::TypedInput, ValidationError> {
+        let tool_description = format!(
+            r#"{{
+    "name": "test_tool",
+    "inputSchema": {{
+        "type": "object",
+        "properties": {{ "attr": {schema} }},
+        "required": ["attr"]
+    }}
+}}"#
+        );
+        let tools = ServerDescription::from_json_str(&tool_description).unwrap();
+        let tool_input =
+            format!(r#"{{"params": {{"name": "test_tool", "arguments": {{"attr": {value}}}}}}}"#);
+        let input = Input::from_json_str(&tool_input).unwrap();
+        tools.validate_input(&input)
+    }
+
+    #[test]
+    fn test_validate_input_satisfies_constraints() {
+        let cases = [
+            (r#"{"type": "integer", "minimum": 0, "maximum": 10}"#, "0"),
+            (r#"{"type": "integer", "minimum": 0, "maximum": 10}"#, "10"),
+            (r#"{"type": "number", "exclusiveMinimum": 0}"#, "0.5"),
+            (r#"{"type": "number", "exclusiveMaximum": 1.5}"#, "1"),
+            (r#"{"type": "number", "multipleOf": 0.1}"#, "0.3"),
+            (r#"{"type": "integer", "multipleOf": 3}"#, "-9"),
+            (
+                r#"{"type": "string", "minLength": 2, "maxLength": 2}"#,
+                r#""éé""#,
+            ),
+            (
+                r#"{"type": "string", "pattern": "[0-9]{3}"}"#,
+                r#""abc123""#,
+            ),
+            (
+                r#"{"type": "array", "minItems": 1, "maxItems": 2}"#,
+                "[1, 2]",
+            ),
+            (
+                r#"{"type": "array", "uniqueItems": true}"#,
+                r#"[1, "1", [1], {"a": 1}]"#,
+            ),
+            // Constraints for other kinds of values do not apply
+            (
+                r#"{"type": ["string", "integer"], "minLength": 5, "minimum": 5}"#,
+                "7",
+            ),
+        ];
+        for (schema, value) in cases {
+            assert_matches!(
+                validate_constrained_attr(schema, value),
+                Ok(_),
+                "expected {value} to satisfy {schema}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_input_violates_constraints_errors() {
+        let check = |schema: &str, value: &str| validate_constrained_attr(schema, value);
+        assert_matches!(
+            check(r#"{"type": "integer", "minimum": 0}"#, "-500"),
+            Err(ValidationError::ValueBelowMinimum(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "number", "maximum": 1.5}"#, "1.6"),
+            Err(ValidationError::ValueAboveMaximum(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "integer", "exclusiveMinimum": 0}"#, "0"),
+            Err(ValidationError::ValueNotAboveExclusiveMinimum(..))
+        );
+        assert_matches!(
+            check(
+                r#"{"type": "integer", "maximum": 3, "exclusiveMaximum": true}"#,
+                "3"
+            ),
+            Err(ValidationError::ValueNotBelowExclusiveMaximum(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "integer", "multipleOf": 3}"#, "10"),
+            Err(ValidationError::ValueNotMultipleOf(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "number", "multipleOf": 0.1}"#, "0.35"),
+            Err(ValidationError::ValueNotMultipleOf(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "string", "minLength": 3}"#, r#""éé""#),
+            Err(ValidationError::StringTooShort(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "string", "maxLength": 3}"#, r#""abcd""#),
+            Err(ValidationError::StringTooLong(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "string", "pattern": "^[a-z]+$"}"#, r#""abc1""#),
+            Err(ValidationError::PatternMismatch(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "array", "minItems": 1}"#, "[]"),
+            Err(ValidationError::TooFewItems(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "array", "maxItems": 1}"#, "[null, null]"),
+            Err(ValidationError::TooManyItems(..))
+        );
+        assert_matches!(
+            check(r#"{"type": "array", "uniqueItems": true}"#, "[1, 2, 1.0]"),
+            Err(ValidationError::DuplicateItems)
+        );
+    }
+
+    #[test]
+    fn test_validate_input_nested_and_referenced_constraints() {
+        let tool_description = r##"{
+    "name": "test_tool",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "hours": { "type": "array", "items": { "$ref": "#/$defs/hours" } }
+        },
+        "$defs": {
+            "hours": { "type": "integer", "minimum": 0, "maximum": 24 }
+        }
+    }
+}"##;
+        let tools = ServerDescription::from_json_str(tool_description).unwrap();
+
+        let input = Input::from_json_str(
+            r#"{"params": {"name": "test_tool", "arguments": {"hours": [0, 8, 24]}}}"#,
+        )
+        .unwrap();
+        assert_matches!(tools.validate_input(&input), Ok(_));
+
+        let input = Input::from_json_str(
+            r#"{"params": {"name": "test_tool", "arguments": {"hours": [8, 25]}}}"#,
+        )
+        .unwrap();
+        assert_matches!(
+            tools.validate_input(&input),
+            Err(ValidationError::ValueAboveMaximum(..))
+        );
+    }
+
+    #[test]
+    fn test_validate_input_const_and_non_string_enums() {
+        let check = |schema: &str, value: &str| validate_constrained_attr(schema, value);
+        assert_matches!(
+            check(r#"{"enum": [1, 2, 3]}"#, "2"),
+            Ok(data::TypedInput { args, .. }) if matches!(args.get("attr"), Some(data::TypedValue::Integer(2)))
+        );
+        assert_matches!(
+            check(r#"{"enum": [1, 2, 3]}"#, "4"),
+            Err(ValidationError::InvalidEnumVariant(..))
+        );
+        assert_matches!(
+            check(r#"{"enum": [1, 2, 3]}"#, r#""1""#),
+            Err(ValidationError::InvalidValueForType)
+        );
+        assert_matches!(
+            check(r#"{"type": "boolean", "const": true}"#, "true"),
+            Ok(_)
+        );
+        assert_matches!(
+            check(r#"{"type": "boolean", "const": true}"#, "false"),
+            Err(ValidationError::InvalidEnumVariant(..))
+        );
+        assert_matches!(check(r#"{"const": "create"}"#, r#""create""#), Ok(_));
+        assert_matches!(
+            check(r#"{"const": "create"}"#, r#""delete""#),
+            Err(ValidationError::InvalidEnumVariant(..))
+        );
+        assert_matches!(check(r#"{"enum": ["a", null]}"#, "null"), Ok(_));
+    }
+
+    #[test]
+    fn test_validate_input_one_of_requires_exactly_one_match() {
+        let check = |schema: &str, value: &str| validate_constrained_attr(schema, value);
+        let one_of = r#"{"oneOf": [{"type": "integer"}, {"type": "number"}]}"#;
+        let any_of = r#"{"anyOf": [{"type": "integer"}, {"type": "number"}]}"#;
+        assert_matches!(
+            check(one_of, "1"),
+            Err(ValidationError::AmbiguousValueForOneOfType)
+        );
+        assert_matches!(
+            check(one_of, "1.5"),
+            Ok(data::TypedInput { args, .. }) if matches!(args.get("attr"), Some(data::TypedValue::Union { index: 1, .. }))
+        );
+        assert_matches!(
+            check(any_of, "1"),
+            Ok(data::TypedInput { args, .. }) if matches!(args.get("attr"), Some(data::TypedValue::Union { index: 0, .. }))
+        );
+        assert_matches!(
+            check(one_of, r#""1""#),
+            Err(ValidationError::InvalidValueForUnionType)
+        );
+    }
+
+    #[test]
+    fn test_validate_input_discriminated_union() {
+        let schema = r#"{
+            "oneOf": [
+                {"type": "object", "properties": {"kind": {"const": "circle"}, "radius": {"type": "number"}}},
+                {"type": "object", "properties": {"kind": {"const": "square"}, "radius": {"type": "number"}}}
+            ],
+            "discriminator": {"propertyName": "kind"}
+        }"#;
+        let check = |value: &str| validate_constrained_attr(schema, value);
+        // Both types accept the value's properties, but the discriminator picks one
+        assert_matches!(
+            check(r#"{"kind": "square", "radius": 1}"#),
+            Ok(data::TypedInput { args, .. }) if matches!(
+                args.get("attr"),
+                Some(data::TypedValue::Union { index: 1, tag: Some(tag), .. }) if tag == "square"
+            )
+        );
+        assert_matches!(
+            check(r#"{"kind": "triangle"}"#),
+            Err(ValidationError::UnknownDiscriminatorValue(..))
+        );
+        assert_matches!(
+            check(r#"{"kind": "circle", "side": 1}"#),
+            Err(ValidationError::UnexpectedProperty(..))
+        );
+    }
+
+    #[test]
+    fn test_validate_input_discriminated_one_of_requires_exactly_one_match() {
+        let check = |union_kind: &str, value: &str| {
+            let tool_description = format!(
+                r##"{{
+    "name": "test_tool",
+    "inputSchema": {{
+        "type": "object",
+        "properties": {{
+            "shape": {{
+                "{union_kind}": [{{"$ref": "#/$defs/Circle"}}, {{"$ref": "#/$defs/Square"}}],
+                "discriminator": {{
+                    "propertyName": "kind",
+                    "mapping": {{"round": "#/$defs/Circle", "boxy": "#/$defs/Square"}}
+                }}
+            }}
+        }},
+        "required": ["shape"],
+        "$defs": {{
+            "Circle": {{"type": "object", "properties": {{"kind": {{"type": "string"}}, "radius": {{"type": "number"}}}}}},
+            "Square": {{"type": "object", "properties": {{"kind": {{"type": "string"}}, "radius": {{"type": "number"}}, "side": {{"type": "number"}}}}}}
+        }}
+    }}
+}}"##
+            );
+            let tools = ServerDescription::from_json_str(&tool_description).unwrap();
+            let tool_input = format!(
+                r#"{{"params": {{"name": "test_tool", "arguments": {{"shape": {value}}}}}}}"#
+            );
+            tools.validate_input(&Input::from_json_str(&tool_input).unwrap())
+        };
+        // The discriminator picks `Circle`, but the value matches `Square` too
+        assert_matches!(
+            check("oneOf", r#"{"kind": "round", "radius": 1}"#),
+            Err(ValidationError::AmbiguousValueForOneOfType)
+        );
+        assert_matches!(
+            check("anyOf", r#"{"kind": "round", "radius": 1}"#),
+            Ok(data::TypedInput { args, .. }) if matches!(
+                args.get("shape"),
+                Some(data::TypedValue::Union { index: 0, tag: Some(tag), .. }) if tag == "round"
+            )
+        );
+        assert_matches!(
+            check("oneOf", r#"{"kind": "boxy", "side": 1}"#),
+            Ok(data::TypedInput { args, .. }) if matches!(
+                args.get("shape"),
+                Some(data::TypedValue::Union { index: 1, tag: Some(tag), .. }) if tag == "boxy"
+            )
+        );
+    }
+
+    #[test]
+    fn test_validate_input_all_of_composition() {
+        let tool_description = r##"{
+    "name": "test_tool",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "ticket": {
+                "allOf": [
+                    { "$ref": "#/$defs/base" },
+                    { "properties": { "priority": { "type": "integer", "maximum": 5 } }, "required": ["priority"] }
+                ]
+            }
+        },
+        "required": ["ticket"],
+        "$defs": {
+            "base": { "type": "object", "properties": { "id": { "type": "string" } }, "required": ["id"] }
+        }
+    }
+}"##;
+        let tools = ServerDescription::from_json_str(tool_description).unwrap();
+
+        let input = Input::from_json_str(
+            r#"{"params": {"name": "test_tool", "arguments": {"ticket": {"id": "T-1", "priority": 3}}}}"#,
+        )
+        .unwrap();
+        assert_matches!(tools.validate_input(&input), Ok(_));
+
+        let input = Input::from_json_str(
+            r#"{"params": {"name": "test_tool", "arguments": {"ticket": {"priority": 3}}}}"#,
+        )
+        .unwrap();
+        assert_matches!(tools.validate_input(&input), Err(_));
+
+        let input = Input::from_json_str(
+            r#"{"params": {"name": "test_tool", "arguments": {"ticket": {"id": "T-1", "priority": 9}}}}"#,
+        )
+        .unwrap();
+        assert_matches!(
+            tools.validate_input(&input),
+            Err(ValidationError::ValueAboveMaximum(..))
+        );
+    }
+
+    #[test]
+    fn test_validate_input_accumulating() {
+        let tool_description = r##"{
+    "name": "search",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "query": { "type": "string", "minLength": 1 },
+            "limit": { "type": "integer" },
+            "filters": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "date": { "type": "string", "format": "date-time" },
+                        "a/b": { "type": "boolean" }
+                    },
+                    "required": ["date"]
+                }
+            }
+        },
+        "required": ["query", "limit"]
+    }
+}"##;
+        let tools = ServerDescription::from_json_str(tool_description).unwrap();
+        let source = r#"{"params": {"name": "search", "arguments": {
+            "query": "",
+            "filters": [
+                {"date": "2024-01-01"},
+                {"a/b": true},
+                {"date": "yesterday", "a/b": 1}
+            ]
+        }}}"#;
+        let input = Input::from_json_str(source).unwrap();
+
+        // Fail fast validation reports only the first error
+        assert_matches!(
+            tools.validate_input(&input),
+            Err(ValidationError::StringTooShort(..))
+        );
+
+        let errors = tools.validate_input_accumulating(&input).unwrap_err();
+        let found = errors
+            .diagnostics()
+            .iter()
+            .map(|d| {
+                let span = d
+                    .labels()
+                    .and_then(|mut labels| labels.next())
+                    .and_then(|label| source.get(label.offset()..label.offset() + label.len()));
+                (d.path(), span)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("/query", Some(r#""""#)),
+                ("/limit", None),
+                ("/filters/1/date", Some(r#"{"a/b": true}"#)),
+                ("/filters/2/date", Some(r#""yesterday""#)),
+                ("/filters/2/a~1b", Some("1")),
+            ]
+        );
+        assert_matches!(
+            errors.diagnostics().get(3).map(ValidationDiagnostic::error),
+            Some(ValidationError::InvalidDatetimeLiteral(..))
+        );
+        assert_eq!(
+            errors.diagnostics().get(3).unwrap().to_string(),
+            "/filters/2/date: Invalid Datetime Literal: yesterday"
+        );
+
+        let input = Input::from_json_str(
+            r#"{"params": {"name": "search", "arguments": {"query": "cedar", "limit": 10, "filters": []}}}"#,
+        )
+        .unwrap();
+        assert_matches!(tools.validate_input_accumulating(&input), Ok(_));
+
+        let input =
+            Input::from_json_str(r#"{"params": {"name": "missing", "arguments": {}}}"#).unwrap();
+        let errors = tools.validate_input_accumulating(&input).unwrap_err();
+        assert_matches!(
+            errors.diagnostics(),
+            [d] if d.path().is_empty() && matches!(d.error(), ValidationError::ToolNotFound(..))
+        );
+    }
+
+    #[test]
+    fn test_validate_output_accumulating_reports_union_and_unexpected_properties() {
+        let tool_description = r##"{
+    "name": "test_tool",
+    "inputSchema": { "type": "object", "properties": {} },
+    "outputSchema": {
+        "type": "object",
+        "properties": {
+            "id": { "anyOf": [{ "type": "string" }, { "type": "integer" }] },
+            "tags": { "type": "array", "items": { "type": "string" } }
+        }
+    }
+}"##;
+        let tools = ServerDescription::from_json_str(tool_description).unwrap();
+        let output = Output::from_json_str(
+            r#"{"result": {"structuredContent": {"id": true, "tags": ["a", 1], "extra": null}}}"#,
+        )
+        .unwrap();
+        let errors = tools
+            .validate_output_accumulating("test_tool", &output)
+            .unwrap_err();
+        let found = errors
+            .diagnostics()
+            .iter()
+            .map(|d| (d.path(), d.error().to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("/id", "Could not match value to union type".to_string()),
+                ("/tags/1", "Value does not match expected type".to_string()),
+                ("/extra", "Unexpected property on object: extra".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_input_json_pointer_refs() {
+        let tool_description = r##"{
+    "name": "test_tool",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "user": { "$ref": "#/definitions/user" },
+            "role": { "$ref": "#/definitions/user/properties/role" },
+            "tag": { "$ref": "#tag" }
+        },
+        "required": ["user", "role"],
+        "definitions": {
+            "user": {
+                "type": "object",
+                "properties": {
+                    "role": { "enum": ["admin", "member"] },
+                    "tags": { "type": "array", "items": { "$anchor": "tag", "type": "string", "maxLength": 3 } }
+                }
+            }
+        }
+    }
+}"##;
+        let tools = ServerDescription::from_json_str(tool_description).unwrap();
+
+        let input = Input::from_json_str(
+            r#"{"params": {"name": "test_tool", "arguments": {"user": {"role": "admin"}, "role": "member", "tag": "abc"}}}"#,
+        )
+        .unwrap();
+        assert_matches!(tools.validate_input(&input), Ok(_));
+
+        let input = Input::from_json_str(
+            r#"{"params": {"name": "test_tool", "arguments": {"user": {}, "role": "owner"}}}"#,
+        )
+        .unwrap();
+        assert_matches!(tools.validate_input(&input), Err(_));
+
+        let input = Input::from_json_str(
+            r#"{"params": {"name": "test_tool", "arguments": {"user": {}, "role": "admin", "tag": "abcd"}}}"#,
+        )
+        .unwrap();
+        assert_matches!(
+            tools.validate_input(&input),
+            Err(ValidationError::StringTooLong(..))
+        );
+    }
+
+    #[test]
+    fn test_constrained_typedef_cycle_is_error() {
+        let tool_description = r##"{
+    "name": "test_tool",
+    "inputSchema": { "type": "object", "properties": {} },
+    "$defs": {
+        "a": { "$ref": "#/$defs/b", "minLength": 1 },
+        "b": { "$ref": "#/$defs/a" }
+    }
+}"##;
+        assert_matches!(
+            ToolDescription::from_json_str(tool_description),
+            Err(DeserializationError::NonWellFoundedTypeDefinitions(..))
+        );
+    }
 }
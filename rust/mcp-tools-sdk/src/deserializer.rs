@@ -14,16 +14,21 @@
  * limitations under the License.
  */
 
-use super::data::{Input, Output};
+use super::data::{Input, Number, Output, PromptGet, ResourceRead, Value};
 use super::description::{
-    Parameters, Property, PropertyType, PropertyTypeDef, ServerDescription, ToolDescription,
+    DatetimeFormat, Discriminator, IpAddrFormat, Parameters, Pattern, PromptArgument,
+    PromptDescription, PromptList, Property, PropertyConstraints, PropertyType, PropertyTypeDef,
+    ResourceDescription, ResourceList, ResourceTemplateDescription, ServerDescription,
+    ToolAnnotations, ToolDescription, UnionKind,
 };
 use super::err::{ContentType, DeserializationError};
-use super::parser::json_value::{LocatedString, LocatedValue};
+use super::parser::json_value::{parse_pointer, LocatedString, LocatedValue};
+use super::uri_template::UriTemplate;
 
 use linked_hash_map::LinkedHashMap;
 use smol_str::SmolStr;
 
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 
 /// Deserialize an MCP `tools/list` json response into a `ServerDescription`
@@ -34,6 +39,9 @@ pub(crate) fn server_description_from_json_value(
     if json_value.is_object() {
         match json_value.get("result") {
             Some(result) => {
+                let scope = DefsScope::default();
+                let root = SchemaRoot::new(result);
+                let scope = scope.with_root(&root);
                 let tools_json = result.get("tools").ok_or_else(|| {
                     DeserializationError::missing_attribute(result, "tools", Vec::new())
                 })?;
@@ -45,14 +53,17 @@ pub(crate) fn server_description_from_json_value(
                 Ok(ServerDescription::new(
                     tools
                         .iter()
-                        .map(tool_description_from_json_value_inner)
+                        .map(|tool| tool_description_from_json_value_inner(tool, &scope))
                         .collect::<Result<Vec<_>, _>>()?
                         .into_iter(),
-                    typedefs_from_json_value(result.get("$defs"), ContentType::ToolParameters)?,
+                    typedefs_from_root(&root, ContentType::ToolParameters, &scope)?,
                 ))
             }
             None => Ok(ServerDescription::new(
-                std::iter::once(tool_description_from_json_value_inner(&json_value)?),
+                std::iter::once(tool_description_from_json_value_inner(
+                    &json_value,
+                    &DefsScope::default(),
+                )?),
                 HashMap::new(),
             )),
         }
@@ -65,7 +76,7 @@ pub(crate) fn server_description_from_json_value(
         Ok(ServerDescription::new(
             tools
                 .iter()
-                .map(tool_description_from_json_value_inner)
+                .map(|tool| tool_description_from_json_value_inner(tool, &DefsScope::default()))
                 .collect::<Result<Vec<_>, _>>()?
                 .into_iter(),
             HashMap::new(),
@@ -79,11 +90,12 @@ pub(crate) fn tool_description_from_json_value(
     json_value: LocatedValue,
 ) -> Result<ToolDescription, DeserializationError> {
     // Public interface consumes the value, but ref is sufficient for deserialization
-    tool_description_from_json_value_inner(&json_value)
+    tool_description_from_json_value_inner(&json_value, &DefsScope::default())
 }
 
 fn tool_description_from_json_value_inner(
     json_value: &LocatedValue,
+    scope: &DefsScope<'_>,
 ) -> Result<ToolDescription, DeserializationError> {
     let tool_obj = json_value.get_object().ok_or_else(|| {
         DeserializationError::unexpected_type(
@@ -109,13 +121,15 @@ fn tool_description_from_json_value_inner(
             vec!["inputSchema".to_string()],
         )
     })?;
-    let inputs = parameters_from_json_value(inputs)?;
+    let root = SchemaRoot::new(json_value);
+    let scope = scope.with_root(&root);
+    let inputs = parameters_from_json_value(inputs, &scope)?;
     let outputs = tool_obj
         .get("outputSchema")
-        .map(parameters_from_json_value)
+        .map(|outputs| parameters_from_json_value(outputs, &scope))
         .transpose()?
         .unwrap_or_else(|| Parameters::new(Vec::new(), HashMap::new()));
-    let type_defs = typedefs_from_json_value(tool_obj.get("$defs"), ContentType::ToolParameters)?;
+    let type_defs = typedefs_from_root(&root, ContentType::ToolParameters, &scope)?;
     let description = tool_obj
         .get("description")
         .map(|json| {
@@ -128,17 +142,81 @@ fn tool_description_from_json_value_inner(
             })
         })
         .transpose()?;
-    Ok(ToolDescription::new(
-        name,
-        inputs,
-        outputs,
-        type_defs,
-        description,
-    ))
+    let title = tool_obj
+        .get("title")
+        .map(|json| {
+            json.get_string().ok_or_else(|| {
+                DeserializationError::unexpected_type(
+                    json,
+                    "Expected `title` attribute of a MCP Tool Description to be a string.",
+                    ContentType::ToolDescription,
+                )
+            })
+        })
+        .transpose()?;
+    let annotations = tool_obj
+        .get("annotations")
+        .map(tool_annotations_from_json_value)
+        .transpose()?
+        .unwrap_or_default();
+    let tool = ToolDescription::new(name, inputs, outputs, type_defs, description)
+        .with_annotations(annotations);
+    Ok(match title {
+        Some(title) => tool.with_title(title),
+        None => tool,
+    })
+}
+
+/// Deserialize the `annotations` attribute of an MCP Tool Description.
+/// Attributes other than the `title` and the standard hints are ignored.
+fn tool_annotations_from_json_value(
+    json_value: &LocatedValue,
+) -> Result<ToolAnnotations, DeserializationError> {
+    let annotations_obj = json_value.get_object().ok_or_else(|| {
+        DeserializationError::unexpected_type(
+            json_value,
+            "Expected `annotations` attribute of a MCP Tool Description to be a JSON object.",
+            ContentType::ToolDescription,
+        )
+    })?;
+    let hint = |name: &str| {
+        annotations_obj
+            .get(name)
+            .map(|json| {
+                json.get_bool().ok_or_else(|| {
+                    DeserializationError::unexpected_type(
+                        json,
+                        &format!("Expected `{name}` annotation of a MCP Tool Description to be a boolean."),
+                        ContentType::ToolDescription,
+                    )
+                })
+            })
+            .transpose()
+    };
+    let title = annotations_obj
+        .get("title")
+        .map(|json| {
+            json.get_string().ok_or_else(|| {
+                DeserializationError::unexpected_type(
+                    json,
+                    "Expected `title` annotation of a MCP Tool Description to be a string.",
+                    ContentType::ToolDescription,
+                )
+            })
+        })
+        .transpose()?;
+    Ok(ToolAnnotations {
+        title,
+        read_only_hint: hint("readOnlyHint")?,
+        destructive_hint: hint("destructiveHint")?,
+        idempotent_hint: hint("idempotentHint")?,
+        open_world_hint: hint("openWorldHint")?,
+    })
 }
 
 fn parameters_from_json_value(
     json_value: &LocatedValue,
+    scope: &DefsScope<'_>,
 ) -> Result<Parameters, DeserializationError> {
     // Unwrap "json" wrapper it exists
     let json_value = json_value.get("json").unwrap_or(json_value);
@@ -149,37 +227,62 @@ fn parameters_from_json_value(
             ContentType::ToolParameters,
         )
     })?;
-    let type_defs = typedefs_from_json_value(params_obj.get("$defs"), ContentType::ToolParameters)?;
+    let root = SchemaRoot::new(json_value);
+    let scope = scope.with_root(&root);
     let required =
         required_from_json_value(params_obj.get("required"), ContentType::ToolParameters)?;
     let properties = properties_from_json_value(
         params_obj.get("properties"),
         &required,
         ContentType::ToolParameters,
+        &scope,
     )?;
+    // Type definitions are deserialized last to include those synthesized for `$ref`s
+    let type_defs = typedefs_from_root(&root, ContentType::ToolParameters, &scope)?;
     Ok(Parameters::new(properties, type_defs))
 }
 
-fn typedefs_from_json_value(
-    json_value: Option<&LocatedValue>,
+/// Deserialize the type definitions of a schema root: the entries of its `$defs` and `definitions`
+/// attributes, along with the type definitions synthesized for `$ref`s to its other subschemas.
+fn typedefs_from_root(
+    root: &SchemaRoot<'_>,
     content_type: ContentType,
+    scope: &DefsScope<'_>,
 ) -> Result<HashMap<SmolStr, PropertyTypeDef>, DeserializationError> {
-    let type_defs = json_value.map(|json_value| {
-        let defs = json_value.get_object().ok_or_else(|| DeserializationError::unexpected_type(
-            json_value,
-            "Expected attribute `$defs` to be a JSON object mapping type names to JSON Type Schemas.",
-            content_type
-        ))?;
-        defs.iter()
-            .map(|(name, val)| {
-                let name = name.to_smolstr();
-                let description = val.get("description").and_then(|desc| desc.get_string());
-                property_type_from_json_value(val).map(|ptype| {
-                    (name.clone(), PropertyTypeDef::new(name, ptype, description))
-                })
-            })
-            .collect::<Result<_, _>>()
-    }).unwrap_or_else(|| Ok(HashMap::new()))?;
+    let mut type_defs = HashMap::new();
+    for keyword in DEFS_KEYWORDS {
+        let Some(json_value) = root.json.get(keyword) else {
+            continue;
+        };
+        let defs = json_value.get_object().ok_or_else(|| {
+            DeserializationError::unexpected_type(
+                json_value,
+                &format!("Expected attribute `{keyword}` to be a JSON object mapping type names to JSON Type Schemas."),
+                content_type,
+            )
+        })?;
+        for (name, val) in defs {
+            let name = name.to_smolstr();
+            if type_defs.contains_key(&name) {
+                return Err(DeserializationError::unexpected_value(
+                    val,
+                    &format!(
+                        "Type definition `{name}` is defined in both `$defs` and `definitions`."
+                    ),
+                    content_type,
+                ));
+            }
+            let description = val.get("description").and_then(|desc| desc.get_string());
+            let ptype = property_type_from_json_value(val, scope)?;
+            type_defs.insert(name.clone(), PropertyTypeDef::new(name, ptype, description));
+        }
+    }
+    let synthesized = std::mem::take(&mut *root.synthesized.borrow_mut());
+    type_defs.extend(
+        synthesized
+            .into_iter()
+            .filter_map(|(name, synthesized)| synthesized.def.map(|def| (name, def))),
+    );
     typedefs_are_well_founded(&type_defs)?;
     Ok(type_defs)
 }
@@ -219,6 +322,7 @@ fn properties_from_json_value(
     json_value: Option<&LocatedValue>,
     required: &HashSet<SmolStr>,
     content_type: ContentType,
+    scope: &DefsScope<'_>,
 ) -> Result<Vec<Property>, DeserializationError> {
     json_value
         .map(|json_value| {
@@ -228,7 +332,7 @@ fn properties_from_json_value(
                     .map(|(name, ptype_json)| {
                         let name = name.to_smolstr();
                         let required = required.contains(&name);
-                        property_from_json_value(ptype_json, name, required)
+                        property_from_json_value(ptype_json, name, required, scope)
                     })
                     .collect::<Result<_, _>>()
             } else if json_value.get_bool() == Some(false) {
@@ -248,6 +352,7 @@ fn property_from_json_value(
     json_value: &LocatedValue,
     name: SmolStr,
     required: bool,
+    scope: &DefsScope<'_>,
 ) -> Result<Property, DeserializationError> {
     let description = json_value
         .get("description")
@@ -264,14 +369,49 @@ fn property_from_json_value(
     Ok(Property::new(
         name,
         required,
-        property_type_from_json_value(json_value)?,
+        property_type_from_json_value(json_value, scope)?,
         description,
     ))
 }
 
 /// Extract the `PropertyType` from a json value.
+/// Wraps the type in a [`PropertyType::Constrained`] if the schema has validation keywords.
 fn property_type_from_json_value(
     json_value: &LocatedValue,
+    scope: &DefsScope<'_>,
+) -> Result<PropertyType, DeserializationError> {
+    let ty = unconstrained_property_type_from_json_value(json_value, scope)?;
+    let constraints = json_value
+        .get_object()
+        .map(constraints_from_map)
+        .transpose()?
+        .unwrap_or_default();
+    if constraints.is_empty() {
+        Ok(ty)
+    } else {
+        Ok(PropertyType::Constrained {
+            ty: Box::new(ty),
+            constraints: Box::new(constraints),
+        })
+    }
+}
+
+/// Extract the `PropertyType` from a json value, ignoring any validation keywords.
+fn unconstrained_property_type_from_json_value(
+    json_value: &LocatedValue,
+    scope: &DefsScope<'_>,
+) -> Result<PropertyType, DeserializationError> {
+    let ty = simple_property_type_from_json_value(json_value, scope)?;
+    match json_value.get("allOf") {
+        Some(all_of_json) => all_of_from_json_value(json_value, ty, all_of_json, scope),
+        None => Ok(ty),
+    }
+}
+
+/// Extract the `PropertyType` described by the `type`, `anyOf` / `oneOf` or `$ref` keywords of a json value.
+fn simple_property_type_from_json_value(
+    json_value: &LocatedValue,
+    scope: &DefsScope<'_>,
 ) -> Result<PropertyType, DeserializationError> {
     // Should be a JSON Schema Object like {"type": "string", "format": "date""}
     let ptype_obj = json_value.get_object().ok_or_else(|| {
@@ -281,27 +421,31 @@ fn property_type_from_json_value(
             ContentType::Property,
         )
     })?;
-    if let Some(type_json) = ptype_obj.get("type") {
+    let literals_type = match literals_from_map(ptype_obj)? {
+        Some(literals) => enum_from_json_values(literals, ptype_obj.get("type"))?,
+        None => None,
+    };
+    if let Some(ty) = literals_type {
+        Ok(ty)
+    } else if let Some(type_json) = ptype_obj.get("type") {
         match type_json.get_str() {
             Some("boolean") => Ok(PropertyType::Bool),
             Some("integer") => Ok(PropertyType::Integer),
             Some("float") => Ok(PropertyType::Float),
             Some("number") => Ok(PropertyType::Number),
             Some("string") => {
-                if let Some(enum_json) = ptype_obj.get("enum") {
-                    enum_from_json_value(enum_json)
-                } else if let Some(format_json) = ptype_obj.get("format") {
+                if let Some(format_json) = ptype_obj.get("format") {
                     property_type_of_format(format_json)
                 } else {
                     Ok(PropertyType::String)
                 }
             }
             Some("null") => Ok(PropertyType::Null),
-            Some("array") => property_type_from_json_array_def(ptype_obj),
+            Some("array") => property_type_from_json_array_def(ptype_obj, scope),
             Some("object") => {
                 let required = required_from_json_value(ptype_obj.get("required"), ContentType::ToolParameters)?;
-                let properties = properties_from_json_value(ptype_obj.get("properties"), &required, ContentType::ToolParameters)?;
-                let additional_properties = additional_properties_from_map(ptype_obj)?;
+                let properties = properties_from_json_value(ptype_obj.get("properties"), &required, ContentType::ToolParameters, scope)?;
+                let additional_properties = additional_properties_from_map(ptype_obj, scope)?;
                 Ok(PropertyType::Object { properties, additional_properties })
             }
             Some(_) => Err(DeserializationError::unexpected_value(
@@ -310,9 +454,9 @@ fn property_type_from_json_value(
                 ContentType::PropertyType
             )),
             // The type is not a simple string, it should be an array
-            None => union_type_of_json_value(type_json, ptype_obj)
+            None => union_type_of_json_value(type_json, ptype_obj, scope)
         }
-    } else if let Some(union_json) = get_value_from_map(ptype_obj, &["anyOf", "oneOf"]) {
+    } else if let Some((kind, union_json)) = union_json_of_map(ptype_obj) {
         let typ_arr = union_json.get_array().ok_or_else(|| {
             DeserializationError::unexpected_type(
                 json_value,
@@ -322,203 +466,961 @@ fn property_type_from_json_value(
         })?;
         let types = typ_arr
             .iter()
-            .map(property_type_from_json_value)
-            .collect::<Result<_, _>>()?;
-        Ok(PropertyType::Union { types })
+            .map(|ty_json| property_type_from_json_value(ty_json, scope))
+            .collect::<Result<Vec<_>, _>>()?;
+        let discriminator = match ptype_obj.get("discriminator") {
+            Some(discriminator_json) => {
+                discriminator_from_json_value(discriminator_json, typ_arr, &types, scope)?
+            }
+            None => None,
+        };
+        Ok(PropertyType::Union {
+            types,
+            kind,
+            discriminator,
+        })
     } else if let Some(ref_json) = ptype_obj.get("$ref") {
-        let s = ref_json.get_str().ok_or_else(|| {
-            DeserializationError::unexpected_type(
-                ref_json,
-                "Expected `$ref` attribute to be a string.",
-                ContentType::PropertyType,
-            )
-        })?;
-        let s = s.strip_prefix("#/$defs/").ok_or_else(|| {
-            DeserializationError::unexpected_value(
-                ref_json,
-                "Expected `$ref` attribute to begin with `#/$defs/`",
-                ContentType::Property,
-            )
-        })?;
-        Ok(PropertyType::Ref { name: s.into() })
+        ref_type_from_json_value(ref_json, scope)
     } else {
         Ok(PropertyType::Unknown)
     }
 }
 
-/// Parse `additionalProperties` from a JSON Schema object map.
-/// Returns `None` for booleans/null (or absent), and propagates errors for malformed schemas.
-fn additional_properties_from_map(
-    map: &LinkedHashMap<LocatedString, LocatedValue>,
-) -> Result<Option<Box<PropertyType>>, DeserializationError> {
-    match map.get("additionalProperties") {
-        Some(json) if json.is_bool() || json.is_null() => Ok(None),
-        Some(json) => Ok(Some(Box::new(property_type_from_json_value(json)?))),
-        None => Ok(None),
+/// Merge the schemas of an `allOf` with the type described by the keywords next to the `allOf`.
+///
+/// Schemas that do not describe a type (e.g., `{"description": "..."}`) are ignored, and
+/// schemas that only have validation keywords (e.g., `{"maxLength": 8}`) constrain the merged type.
+/// If more than one schema describes a type, they must all be objects (or `$ref`s to objects),
+/// which are merged into a single [`PropertyType::Object`].
+fn all_of_from_json_value(
+    json_value: &LocatedValue,
+    sibling_ty: PropertyType,
+    all_of_json: &LocatedValue,
+    scope: &DefsScope<'_>,
+) -> Result<PropertyType, DeserializationError> {
+    let schemas = all_of_json.get_array().ok_or_else(|| {
+        DeserializationError::unexpected_type(
+            all_of_json,
+            "Expected `allOf` attribute to be an array of JSON Schemas.",
+            ContentType::PropertyType,
+        )
+    })?;
+
+    let mut typed_parts = Vec::new();
+    let mut constraints = Vec::new();
+    match implicit_object_type(json_value, sibling_ty, scope)? {
+        PropertyType::Unknown => (),
+        ty => typed_parts.push((json_value, ty)),
+    }
+    for schema in schemas {
+        let ty = property_type_from_json_value(schema, scope)?;
+        match implicit_object_type(schema, ty, scope)? {
+            PropertyType::Unknown => (),
+            PropertyType::Constrained {
+                ty,
+                constraints: schema_constraints,
+            } if *ty == PropertyType::Unknown => constraints.push(schema_constraints),
+            ty => typed_parts.push((schema, ty)),
+        }
     }
+
+    let merged = if typed_parts.len() > 1 {
+        merge_object_types(&typed_parts, scope)?
+    } else {
+        typed_parts
+            .pop()
+            .map_or(PropertyType::Unknown, |(_, ty)| ty)
+    };
+    Ok(constraints
+        .into_iter()
+        .fold(merged, |ty, constraints| PropertyType::Constrained {
+            ty: Box::new(ty),
+            constraints,
+        }))
 }
 
-/// Generate a [`PropertyType`] when the JSON value's is like `{"type": "array", "items": {...} }`.
-/// Depending on whether there is `prefixItems` and `"items": "false"`, this results in a
-/// [`PropertyType::Tuple`] or a [`PropertyType::Array`].
-fn property_type_from_json_array_def(
-    ptype_obj: &LinkedHashMap<LocatedString, LocatedValue>,
+/// Schemas within an `allOf` often leave out `"type": "object"`, so an otherwise
+/// [`PropertyType::Unknown`] schema with `properties` or `additionalProperties` is an object.
+fn implicit_object_type(
+    json_value: &LocatedValue,
+    ty: PropertyType,
+    scope: &DefsScope<'_>,
 ) -> Result<PropertyType, DeserializationError> {
-    let prefix_items = ptype_obj.get("prefixItems").and_then(|v| v.get_array());
-    let items_json = ptype_obj.get("items");
-
-    if let Some(prefix) = prefix_items {
-        let prefix_types: Vec<PropertyType> = prefix
-            .iter()
-            .map(property_type_from_json_value)
-            .collect::<Result<_, _>>()?;
+    match json_value.get_object() {
+        Some(obj)
+            if ty == PropertyType::Unknown
+                && (obj.contains_key("properties") || obj.contains_key("additionalProperties")) =>
+        {
+            let required =
+                required_from_json_value(obj.get("required"), ContentType::ToolParameters)?;
+            let properties = properties_from_json_value(
+                obj.get("properties"),
+                &required,
+                ContentType::ToolParameters,
+                scope,
+            )?;
+            let additional_properties = additional_properties_from_map(obj, scope)?;
+            Ok(PropertyType::Object {
+                properties,
+                additional_properties,
+            })
+        }
+        _ => Ok(ty),
+    }
+}
 
-        match items_json {
-            // items: false means closed tuple
-            Some(items) if items.get_bool() == Some(false) => Ok(PropertyType::Tuple {
-                types: prefix_types,
-            }),
-            // items is a schema — check if all prefixItems match it, collapse to array
-            Some(items) if items.is_object() => {
-                let items_type = property_type_from_json_value(items)?;
-                if prefix_types.iter().all(|t| *t == items_type) {
-                    Ok(PropertyType::Array {
-                        element_ty: Box::new(items_type),
-                    })
-                } else {
-                    Ok(PropertyType::Array {
-                        element_ty: Box::new(PropertyType::Unknown),
-                    })
+/// Merge object types into a single [`PropertyType::Object`], unioning their properties and
+/// required properties. A property defined by several schemas must have the same type in each.
+fn merge_object_types(
+    parts: &[(&LocatedValue, PropertyType)],
+    scope: &DefsScope<'_>,
+) -> Result<PropertyType, DeserializationError> {
+    let mut properties: Vec<Property> = Vec::new();
+    let mut required = HashSet::new();
+    let mut additional_properties: Option<Box<PropertyType>> = None;
+    for (json_value, ty) in parts {
+        let (part_properties, part_additional_properties) =
+            object_components_of_type(json_value, ty, scope)?;
+        // `required` may list properties defined by another schema of the `allOf`
+        required.extend(required_from_json_value(
+            json_value.get("required"),
+            ContentType::PropertyType,
+        )?);
+        for property in part_properties {
+            if property.required {
+                required.insert(property.name.clone());
+            }
+            match properties.iter_mut().find(|p| p.name == property.name) {
+                Some(existing) if existing.prop_type != property.prop_type => {
+                    let property_json = json_value
+                        .get("properties")
+                        .and_then(|props| props.get(&property.name))
+                        .unwrap_or(json_value);
+                    return Err(DeserializationError::conflicting_property_types(
+                        property_json,
+                        &property.name,
+                    ));
+                }
+                Some(existing) => {
+                    if existing.description.is_none() {
+                        existing.description = property.description;
+                    }
                 }
+                None => properties.push(property),
             }
-            // items absent or items: true — open-ended, not a tuple
-            _ => Ok(PropertyType::Array {
-                element_ty: Box::new(PropertyType::Unknown),
-            }),
         }
-    } else {
-        // No prefixItems — standard array handling
-        items_json.map(|items_json| {
-            if items_json.is_object() {
-                let items_type = property_type_from_json_value(items_json)?;
-                Ok(PropertyType::Array { element_ty: Box::new(items_type) })
-            } else if items_json.is_bool() || items_json.is_null() {
-                Ok(PropertyType::Array { element_ty: Box::new(PropertyType::Unknown) })
-            } else {
-                Err(DeserializationError::unexpected_type(
-                    items_json,
-                    "Expected `items` attribute to be a JSON Schema (object) describing the type of array items.",
-                    ContentType::PropertyType
-                ))
+        match (&additional_properties, part_additional_properties) {
+            (Some(existing), Some(additional)) if *existing != additional => {
+                let additional_json = json_value.get("additionalProperties").unwrap_or(json_value);
+                return Err(DeserializationError::conflicting_property_types(
+                    additional_json,
+                    "additionalProperties",
+                ));
             }
-        }).unwrap_or_else(|| Ok(PropertyType::Array { element_ty: Box::new(PropertyType::Unknown) }))
+            (None, additional) => additional_properties = additional,
+            _ => (),
+        }
     }
+    for property in &mut properties {
+        property.required = required.contains(&property.name);
+    }
+    Ok(PropertyType::Object {
+        properties,
+        additional_properties,
+    })
 }
 
-fn enum_from_json_value(enum_json: &LocatedValue) -> Result<PropertyType, DeserializationError> {
-    let enum_variants = enum_json.get_array().ok_or_else(|| {
-        DeserializationError::unexpected_type(
-            enum_json,
-            "Expected `enum` attributed to be a JSON array of strings.",
-            ContentType::PropertyType,
-        )
-    })?;
-    let variants = enum_variants
-        .iter()
-        .map(|variant| {
-            variant.get_smolstr().ok_or_else(|| {
-                DeserializationError::unexpected_type(
-                    variant,
-                    "Expected element of `enum` attribute to be a string.",
+/// Get the properties and additional properties of an object type, resolving `$ref`s.
+fn object_components_of_type(
+    json_value: &LocatedValue,
+    ty: &PropertyType,
+    scope: &DefsScope<'_>,
+) -> Result<(Vec<Property>, Option<Box<PropertyType>>), DeserializationError> {
+    match ty.base_type() {
+        PropertyType::Object {
+            properties,
+            additional_properties,
+        } => Ok((properties.clone(), additional_properties.clone())),
+        PropertyType::Ref { name } => {
+            if scope.is_resolving(name) {
+                let mut cycle = scope.resolving_names();
+                cycle.push(name.clone());
+                return Err(DeserializationError::type_definition_cycle(cycle));
+            }
+            let def_json = scope.get(name).ok_or_else(|| {
+                DeserializationError::unexpected_value(
+                    json_value,
+                    &format!("Could not find the definition of `{name}` to merge into `allOf`."),
                     ContentType::PropertyType,
                 )
-            })
-        })
-        .collect::<Result<Vec<_>, _>>()?;
-    if variants.is_empty() {
-        Err(DeserializationError::unexpected_value(
-            enum_json,
-            "Expected non-empty list of variants for `enum` attribute.",
+            })?;
+            let scope = scope.resolving(name);
+            let def_ty = property_type_from_json_value(def_json, &scope)?;
+            object_components_of_type(def_json, &def_ty, &scope)
+        }
+        _ => Err(DeserializationError::unexpected_value(
+            json_value,
+            "Expected every schema of an `allOf` describing a type to be an object schema.",
             ContentType::PropertyType,
-        ))
-    } else {
-        Ok(PropertyType::Enum { variants })
+        )),
     }
 }
 
-fn property_type_of_format(
-    format_json: &LocatedValue,
-) -> Result<PropertyType, DeserializationError> {
-    match format_json.get_str() {
-        Some("date") => Ok(PropertyType::Datetime),
-        Some("date-time") => Ok(PropertyType::Datetime),
-        Some("duration") => Ok(PropertyType::Duration),
-        Some("ipv4") => Ok(PropertyType::IpAddr),
-        Some("ipv6") => Ok(PropertyType::IpAddr),
-        Some("decimal") => Ok(PropertyType::Decimal),
-        Some(_) => Ok(PropertyType::String),
-        None => Err(DeserializationError::unexpected_type(
-            format_json,
-            "Expected `format` attribute to be a string.",
-            ContentType::PropertyType,
-        )),
+/// Get the `anyOf` (preferred) or `oneOf` schemas of a JSON Schema, along with the kind of union they describe.
+fn union_json_of_map(
+    map: &LinkedHashMap<LocatedString, LocatedValue>,
+) -> Option<(UnionKind, &LocatedValue)> {
+    map.get("anyOf")
+        .map(|json| (UnionKind::AnyOf, json))
+        .or_else(|| map.get("oneOf").map(|json| (UnionKind::OneOf, json)))
+}
+
+/// Extract the [`Discriminator`] of a union from a `{"propertyName": ..., "mapping": {...}}` JSON object.
+///
+/// Returns `None` if a distinct discriminator value cannot be found for every type of the union.
+fn discriminator_from_json_value(
+    json_value: &LocatedValue,
+    types_json: &[LocatedValue],
+    types: &[PropertyType],
+    scope: &DefsScope<'_>,
+) -> Result<Option<Discriminator>, DeserializationError> {
+    let property_name = json_value
+        .get("propertyName")
+        .and_then(LocatedValue::get_smolstr)
+        .ok_or_else(|| {
+            DeserializationError::unexpected_type(
+                json_value,
+                "Expected `discriminator` to be a JSON object with a string `propertyName` attribute.",
+                ContentType::PropertyType,
+            )
+        })?;
+    let mapping = match json_value.get("mapping") {
+        Some(mapping_json) => {
+            let mapping = mapping_json.get_object().ok_or_else(|| {
+                DeserializationError::unexpected_type(
+                    mapping_json,
+                    "Expected `discriminator` `mapping` to be a JSON object of strings.",
+                    ContentType::PropertyType,
+                )
+            })?;
+            mapping
+                .iter()
+                .map(|(value, ref_json)| {
+                    ref_json
+                        .get_str()
+                        .map(|ref_str| (value.to_smolstr(), ref_str))
+                        .ok_or_else(|| {
+                            DeserializationError::unexpected_type(
+                                ref_json,
+                                "Expected `discriminator` `mapping` to be a JSON object of strings.",
+                                ContentType::PropertyType,
+                            )
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        None => Vec::new(),
+    };
+
+    let mut values = Vec::new();
+    for (ty_json, ty) in types_json.iter().zip(types) {
+        let const_value = implicit_object_type(ty_json, ty.clone(), scope)
+            .and_then(|ty| object_components_of_type(ty_json, &ty, scope))
+            .ok()
+            .and_then(|(properties, _)| {
+                properties
+                    .into_iter()
+                    .find(|property| property.name() == property_name)
+            })
+            .and_then(|property| match property.property_type().base_type() {
+                PropertyType::Enum { variants } => match variants.as_slice() {
+                    [value] => Some(value.clone()),
+                    _ => None,
+                },
+                _ => None,
+            });
+        let ref_str = ty_json.get("$ref").and_then(LocatedValue::get_str);
+        let mapped_value = ref_str.and_then(|ref_str| {
+            mapping
+                .iter()
+                .find(|(_, target)| *target == ref_str)
+                .map(|(value, _)| value.clone())
+        });
+        let ref_name = match ty {
+            PropertyType::Ref { name } => Some(name.clone()),
+            _ => None,
+        };
+        match const_value.or(mapped_value).or(ref_name) {
+            Some(value) if !values.contains(&value) => values.push(value),
+            _ => return Ok(None),
+        }
     }
+    Ok(Some(Discriminator {
+        property_name,
+        values,
+    }))
 }
 
-/// Returns a [`PropertyType::Union { types }`] if the json value `type_json`
-/// represents a type union.
-/// Return [`PropertyType::Unknown`] if the json value is a boolean or `null`.
-/// Otherwise, throws a `DeserializationError`.
-fn union_type_of_json_value(
-    type_json: &LocatedValue,
-    top_typ: &LinkedHashMap<LocatedString, LocatedValue>,
+/// The attributes of a schema root holding type definitions.
+const DEFS_KEYWORDS: [&str; 2] = ["$defs", "definitions"];
+
+/// Resolve a `$ref` to a [`PropertyType::Ref`].
+///
+/// A `$ref` may be a JSON Pointer (e.g., `#/$defs/A`, `#/definitions/A` or `#/$defs/A/properties/b`),
+/// an `$anchor` (e.g., `#a`), or an `$id` optionally followed by either (e.g., `a.json#/properties/b`).
+/// It is resolved against the innermost schema root in which its target is found.
+fn ref_type_from_json_value(
+    ref_json: &LocatedValue,
+    scope: &DefsScope<'_>,
 ) -> Result<PropertyType, DeserializationError> {
-    if let Some(types_json) = type_json.get_array() {
-        let types = types_json
-            .iter()
-            .map(|ty_json| tuple_type_element_of_json_value_array_element(ty_json, top_typ))
-            .collect::<Result<_, _>>()?;
-        Ok(PropertyType::Union { types })
-    } else if type_json.is_bool() || type_json.is_null() {
-        Ok(PropertyType::Unknown)
-    } else {
-        Err(DeserializationError::unexpected_type(
-            type_json,
-            "Expected `type` attribute to be a string.",
+    let reference = ref_json.get_str().ok_or_else(|| {
+        DeserializationError::unexpected_type(
+            ref_json,
+            "Expected `$ref` attribute to be a string.",
             ContentType::PropertyType,
-        ))
+        )
+    })?;
+    let (base, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+    let fragment = percent_decode(fragment);
+    let pointer = if fragment.is_empty() || fragment.starts_with('/') {
+        Some(parse_pointer(&fragment).ok_or_else(|| {
+            DeserializationError::unexpected_value(
+                ref_json,
+                "Expected `$ref` fragment to be a valid JSON Pointer.",
+                ContentType::Property,
+            )
+        })?)
+    } else {
+        None
+    };
+
+    for root in scope.roots() {
+        let base_target = if base.is_empty() {
+            Some((Vec::new(), root.json))
+        } else {
+            root.json
+                .find_path(&|value| value.get("$id").and_then(LocatedValue::get_str) == Some(base))
+        };
+        let Some((mut path, base_json)) = base_target else {
+            continue;
+        };
+        let target_path = match &pointer {
+            Some(pointer) => base_json.get_path(pointer).map(|_| pointer.clone()),
+            None => base_json
+                .find_path(&|value| is_anchor(value, &fragment))
+                .map(|(path, _)| path),
+        };
+        if let Some(target_path) = target_path {
+            path.extend(target_path);
+            return root.ref_to(ref_json, path, scope);
+        }
     }
-}
 
-fn tuple_type_element_of_json_value_array_element(
-    ty_json: &LocatedValue,
-    top_typ: &LinkedHashMap<LocatedString, LocatedValue>,
-) -> Result<PropertyType, DeserializationError> {
-    match ty_json.get_str() {
-        Some("boolean") => Ok(PropertyType::Bool),
-        Some("integer") => Ok(PropertyType::Integer),
-        Some("float") => Ok(PropertyType::Float),
-        Some("number") => Ok(PropertyType::Number),
-        Some("string") => Ok(PropertyType::String),
-        Some("null") => Ok(PropertyType::Null),
-        Some("object") => {
-            let required = required_from_json_value(top_typ.get("required"), ContentType::ToolParameters)?;
-            let properties = properties_from_json_value(top_typ.get("properties"), &required, ContentType::ToolParameters)?;
-            let additional_properties = additional_properties_from_map(top_typ)?;
-            Ok(PropertyType::Object { properties, additional_properties })
+    match pointer.as_deref() {
+        // References to missing type definitions are reported when the type is used
+        Some([keyword, name]) if base.is_empty() && DEFS_KEYWORDS.contains(&keyword.as_str()) => {
+            Ok(PropertyType::Ref { name: name.clone() })
         }
-        Some("array") => property_type_from_json_array_def(top_typ),
-        Some(_) => Err(DeserializationError::unexpected_value(
-            ty_json,
-            "Expected one of: `boolean`, `integer`, `float`, `number`, `string`, `null`, `array`, `object`.",
-            ContentType::PropertyType
+        _ => Err(DeserializationError::unexpected_value(
+            ref_json,
+            &format!("Could not resolve `$ref` `{reference}`."),
+            ContentType::Property,
         )),
-        None => property_type_from_json_value(ty_json)
     }
 }
 
-fn get_value_from_map<'a, T: AsRef<str>>(
-    map: &'a LinkedHashMap<LocatedString, LocatedValue>,
+/// Returns `true` if `json_value` is a schema with the `$anchor` `name`
+/// (or the equivalent pre-2019-09 `$id` of `#name`).
+fn is_anchor(json_value: &LocatedValue, name: &str) -> bool {
+    json_value.get("$anchor").and_then(LocatedValue::get_str) == Some(name)
+        || json_value
+            .get("$id")
+            .and_then(LocatedValue::get_str)
+            .and_then(|id| id.strip_prefix('#'))
+            == Some(name)
+}
+
+/// Decode the `%XX` escapes of a URI fragment (or any other URI component).
+pub(crate) fn percent_decode(fragment: &str) -> String {
+    let mut parts = fragment.split('%');
+    let mut decoded = parts.next().unwrap_or_default().as_bytes().to_vec();
+    for part in parts {
+        let byte = part
+            .get(..2)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match byte {
+            Some(byte) => {
+                decoded.push(byte);
+                decoded.extend_from_slice(part.get(2..).unwrap_or_default().as_bytes());
+            }
+            None => {
+                decoded.push(b'%');
+                decoded.extend_from_slice(part.as_bytes());
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The name of the type definition synthesized for the subschema at `path`,
+/// e.g., `Outer_properties_inner` for `/$defs/Outer/properties/inner`.
+fn synthesized_type_name(path: &[SmolStr]) -> SmolStr {
+    let path = match path {
+        [keyword, rest @ ..] if DEFS_KEYWORDS.contains(&keyword.as_str()) => rest,
+        _ => path,
+    };
+    path.iter()
+        .map(|token| {
+            token
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+        .into()
+}
+
+/// A JSON object whose `$defs`, `definitions` and subschemas can be referenced by `$ref`s:
+/// the JSON Schema of a tool's inputs or outputs, a tool description, or a `tools/list` result.
+#[derive(Debug)]
+struct SchemaRoot<'a> {
+    json: &'a LocatedValue,
+    /// Type definitions synthesized for `$ref`s to subschemas outside of `$defs` and `definitions`
+    synthesized: RefCell<LinkedHashMap<SmolStr, SynthesizedTypeDef>>,
+}
+
+#[derive(Debug)]
+struct SynthesizedTypeDef {
+    /// The path to the referenced subschema from the schema root
+    path: Vec<SmolStr>,
+    /// The type definition, or `None` while the subschema is being deserialized
+    def: Option<PropertyTypeDef>,
+}
+
+impl<'a> SchemaRoot<'a> {
+    fn new(json: &'a LocatedValue) -> Self {
+        Self {
+            json,
+            synthesized: RefCell::new(LinkedHashMap::new()),
+        }
+    }
+
+    /// Get the JSON Schema of the type definition `name`
+    fn get(&self, name: &str) -> Option<&'a LocatedValue> {
+        DEFS_KEYWORDS
+            .iter()
+            .find_map(|keyword| self.json.get(keyword).and_then(|defs| defs.get(name)))
+            .or_else(|| {
+                let synthesized = self.synthesized.borrow();
+                let path = &synthesized.get(name)?.path;
+                self.json.get_path(path)
+            })
+    }
+
+    /// Returns a [`PropertyType::Ref`] to the subschema at `path`, synthesizing
+    /// a type definition for it if it is not in `$defs` or `definitions`.
+    fn ref_to(
+        &self,
+        ref_json: &LocatedValue,
+        path: Vec<SmolStr>,
+        scope: &DefsScope<'_>,
+    ) -> Result<PropertyType, DeserializationError> {
+        let name = match path.as_slice() {
+            [keyword, name] if DEFS_KEYWORDS.contains(&keyword.as_str()) => {
+                return Ok(PropertyType::Ref { name: name.clone() })
+            }
+            [] => {
+                return Err(DeserializationError::unexpected_value(
+                    ref_json,
+                    "Expected `$ref` not to refer to the root of a schema.",
+                    ContentType::Property,
+                ))
+            }
+            _ => synthesized_type_name(&path),
+        };
+
+        let conflicts = match self.synthesized.borrow().get(&name) {
+            Some(synthesized) if synthesized.path == path => {
+                return Ok(PropertyType::Ref { name });
+            }
+            Some(_) => true,
+            None => DEFS_KEYWORDS.iter().any(|keyword| {
+                self.json
+                    .get(keyword)
+                    .and_then(|defs| defs.get(&name))
+                    .is_some()
+            }),
+        };
+        let target = self.json.get_path(&path).filter(|_| !conflicts).ok_or_else(|| {
+            DeserializationError::unexpected_value(
+                ref_json,
+                &format!("The type definition `{name}` for the target of this `$ref` conflicts with another type definition."),
+                ContentType::Property,
+            )
+        })?;
+
+        self.synthesized
+            .borrow_mut()
+            .insert(name.clone(), SynthesizedTypeDef { path, def: None });
+        let ptype = property_type_from_json_value(target, scope)?;
+        let description = target.get("description").and_then(|desc| desc.get_string());
+        if let Some(synthesized) = self.synthesized.borrow_mut().get_mut(&name) {
+            synthesized.def = Some(PropertyTypeDef::new(name.clone(), ptype, description));
+        }
+        Ok(PropertyType::Ref { name })
+    }
+}
+
+/// The schema roots in scope while deserializing a JSON Schema, used to resolve `$ref`s
+/// and find the definitions referenced by the schemas of an `allOf`. Lookups search the
+/// innermost scope first.
+#[derive(Debug, Clone, Copy, Default)]
+struct DefsScope<'a> {
+    frame: Option<DefsFrame<'a>>,
+    parent: Option<&'a DefsScope<'a>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum DefsFrame<'a> {
+    /// A schema root whose type definitions and subschemas can be referenced
+    Root(&'a SchemaRoot<'a>),
+    /// A type definition being resolved, used to detect cyclic `allOf`s
+    Resolving(&'a SmolStr),
+}
+
+impl<'a> DefsScope<'a> {
+    fn with_root<'b>(&'b self, root: &'b SchemaRoot<'b>) -> DefsScope<'b> {
+        DefsScope {
+            frame: Some(DefsFrame::Root(root)),
+            parent: Some(self),
+        }
+    }
+
+    fn resolving<'b>(&'b self, name: &'b SmolStr) -> DefsScope<'b> {
+        DefsScope {
+            frame: Some(DefsFrame::Resolving(name)),
+            parent: Some(self),
+        }
+    }
+
+    fn frames(&self) -> impl Iterator<Item = DefsFrame<'a>> + '_ {
+        std::iter::successors(Some(self), |scope| scope.parent).filter_map(|scope| scope.frame)
+    }
+
+    fn roots(&self) -> impl Iterator<Item = &'a SchemaRoot<'a>> + '_ {
+        self.frames().filter_map(|frame| match frame {
+            DefsFrame::Root(root) => Some(root),
+            DefsFrame::Resolving(_) => None,
+        })
+    }
+
+    fn get(&self, name: &str) -> Option<&'a LocatedValue> {
+        self.roots().find_map(|root| root.get(name))
+    }
+
+    fn is_resolving(&self, name: &str) -> bool {
+        self.frames()
+            .any(|frame| matches!(frame, DefsFrame::Resolving(resolving) if resolving == name))
+    }
+
+    fn resolving_names(&self) -> Vec<SmolStr> {
+        let mut names: Vec<SmolStr> = self
+            .frames()
+            .filter_map(|frame| match frame {
+                DefsFrame::Resolving(name) => Some(name.clone()),
+                DefsFrame::Root(_) => None,
+            })
+            .collect();
+        names.reverse();
+        names
+    }
+}
+
+/// Parse the validation keywords (`minimum`, `maxLength`, `pattern`, ...) of a JSON Schema object map.
+fn constraints_from_map(
+    map: &LinkedHashMap<LocatedString, LocatedValue>,
+) -> Result<PropertyConstraints, DeserializationError> {
+    let mut constraints = PropertyConstraints {
+        minimum: number_keyword_from_map(map, "minimum")?,
+        maximum: number_keyword_from_map(map, "maximum")?,
+        exclusive_minimum: None,
+        exclusive_maximum: None,
+        multiple_of: number_keyword_from_map(map, "multipleOf")?,
+        min_length: count_keyword_from_map(map, "minLength")?,
+        max_length: count_keyword_from_map(map, "maxLength")?,
+        pattern: map
+            .get("pattern")
+            .map(pattern_from_json_value)
+            .transpose()?,
+        min_items: count_keyword_from_map(map, "minItems")?,
+        max_items: count_keyword_from_map(map, "maxItems")?,
+        unique_items: false,
+    };
+
+    // Draft 4 JSON Schemas use booleans to mark `minimum` / `maximum` as exclusive.
+    match map.get("exclusiveMinimum") {
+        Some(json) if json.get_bool() == Some(true) => {
+            constraints.exclusive_minimum = constraints.minimum.take();
+        }
+        Some(json) if json.is_bool() => (),
+        _ => constraints.exclusive_minimum = number_keyword_from_map(map, "exclusiveMinimum")?,
+    }
+    match map.get("exclusiveMaximum") {
+        Some(json) if json.get_bool() == Some(true) => {
+            constraints.exclusive_maximum = constraints.maximum.take();
+        }
+        Some(json) if json.is_bool() => (),
+        _ => constraints.exclusive_maximum = number_keyword_from_map(map, "exclusiveMaximum")?,
+    }
+
+    if let Some(multiple_of) = map.get("multipleOf") {
+        if !constraints
+            .multiple_of
+            .as_ref()
+            .and_then(Number::to_f64)
+            .is_some_and(|f| f > 0.0)
+        {
+            return Err(DeserializationError::unexpected_value(
+                multiple_of,
+                "Expected `multipleOf` attribute to be strictly greater than 0.",
+                ContentType::PropertyType,
+            ));
+        }
+    }
+
+    if let Some(unique_json) = map.get("uniqueItems") {
+        constraints.unique_items = unique_json.get_bool().ok_or_else(|| {
+            DeserializationError::unexpected_type(
+                unique_json,
+                "Expected `uniqueItems` attribute to be a boolean.",
+                ContentType::PropertyType,
+            )
+        })?;
+    }
+
+    Ok(constraints)
+}
+
+/// Parse a validation keyword whose value must be a JSON number (e.g., `minimum`).
+fn number_keyword_from_map(
+    map: &LinkedHashMap<LocatedString, LocatedValue>,
+    keyword: &str,
+) -> Result<Option<Number>, DeserializationError> {
+    map.get(keyword)
+        .map(|json| match Value::from(json) {
+            Value::Number(num) => Ok(num),
+            _ => Err(DeserializationError::unexpected_type(
+                json,
+                &format!("Expected `{keyword}` attribute to be a number."),
+                ContentType::PropertyType,
+            )),
+        })
+        .transpose()
+}
+
+/// Parse a validation keyword whose value must be a non-negative integer (e.g., `maxLength`).
+fn count_keyword_from_map(
+    map: &LinkedHashMap<LocatedString, LocatedValue>,
+    keyword: &str,
+) -> Result<Option<usize>, DeserializationError> {
+    map.get(keyword)
+        .map(|json| {
+            json.get_numeric_str()
+                .and_then(|num| num.parse::<usize>().ok())
+                .ok_or_else(|| {
+                    DeserializationError::unexpected_value(
+                        json,
+                        &format!("Expected `{keyword}` attribute to be a non-negative integer."),
+                        ContentType::PropertyType,
+                    )
+                })
+        })
+        .transpose()
+}
+
+fn pattern_from_json_value(pattern_json: &LocatedValue) -> Result<Pattern, DeserializationError> {
+    let source = pattern_json.get_smolstr().ok_or_else(|| {
+        DeserializationError::unexpected_type(
+            pattern_json,
+            "Expected `pattern` attribute to be a string.",
+            ContentType::PropertyType,
+        )
+    })?;
+    Pattern::new(source).map_err(|e| {
+        DeserializationError::unexpected_value(
+            pattern_json,
+            &format!("Expected `pattern` attribute to be a supported regular expression: {e}"),
+            ContentType::PropertyType,
+        )
+    })
+}
+
+/// Parse `additionalProperties` from a JSON Schema object map.
+/// Returns `None` for booleans/null (or absent), and propagates errors for malformed schemas.
+fn additional_properties_from_map(
+    map: &LinkedHashMap<LocatedString, LocatedValue>,
+    scope: &DefsScope<'_>,
+) -> Result<Option<Box<PropertyType>>, DeserializationError> {
+    match map.get("additionalProperties") {
+        Some(json) if json.is_bool() || json.is_null() => Ok(None),
+        Some(json) => Ok(Some(Box::new(property_type_from_json_value(json, scope)?))),
+        None => Ok(None),
+    }
+}
+
+/// Generate a [`PropertyType`] when the JSON value's is like `{"type": "array", "items": {...} }`.
+/// Depending on whether there is `prefixItems` and `"items": "false"`, this results in a
+/// [`PropertyType::Tuple`] or a [`PropertyType::Array`].
+fn property_type_from_json_array_def(
+    ptype_obj: &LinkedHashMap<LocatedString, LocatedValue>,
+    scope: &DefsScope<'_>,
+) -> Result<PropertyType, DeserializationError> {
+    let prefix_items = ptype_obj.get("prefixItems").and_then(|v| v.get_array());
+    let items_json = ptype_obj.get("items");
+
+    if let Some(prefix) = prefix_items {
+        let prefix_types: Vec<PropertyType> = prefix
+            .iter()
+            .map(|item| property_type_from_json_value(item, scope))
+            .collect::<Result<_, _>>()?;
+
+        match items_json {
+            // items: false means closed tuple
+            Some(items) if items.get_bool() == Some(false) => Ok(PropertyType::Tuple {
+                types: prefix_types,
+            }),
+            // items is a schema — check if all prefixItems match it, collapse to array
+            Some(items) if items.is_object() => {
+                let items_type = property_type_from_json_value(items, scope)?;
+                if prefix_types.iter().all(|t| *t == items_type) {
+                    Ok(PropertyType::Array {
+                        element_ty: Box::new(items_type),
+                    })
+                } else {
+                    Ok(PropertyType::Array {
+                        element_ty: Box::new(PropertyType::Unknown),
+                    })
+                }
+            }
+            // items absent or items: true — open-ended, not a tuple
+            _ => Ok(PropertyType::Array {
+                element_ty: Box::new(PropertyType::Unknown),
+            }),
+        }
+    } else {
+        // No prefixItems — standard array handling
+        items_json.map(|items_json| {
+            if items_json.is_object() {
+                let items_type = property_type_from_json_value(items_json, scope)?;
+                Ok(PropertyType::Array { element_ty: Box::new(items_type) })
+            } else if items_json.is_bool() || items_json.is_null() {
+                Ok(PropertyType::Array { element_ty: Box::new(PropertyType::Unknown) })
+            } else {
+                Err(DeserializationError::unexpected_type(
+                    items_json,
+                    "Expected `items` attribute to be a JSON Schema (object) describing the type of array items.",
+                    ContentType::PropertyType
+                ))
+            }
+        }).unwrap_or_else(|| Ok(PropertyType::Array { element_ty: Box::new(PropertyType::Unknown) }))
+    }
+}
+
+/// Get the literal values permitted by the `const` or `enum` keywords of a JSON Schema, if any.
+fn literals_from_map(
+    map: &LinkedHashMap<LocatedString, LocatedValue>,
+) -> Result<Option<&[LocatedValue]>, DeserializationError> {
+    if let Some(const_json) = map.get("const") {
+        Ok(Some(std::slice::from_ref(const_json)))
+    } else if let Some(enum_json) = map.get("enum") {
+        let variants = enum_json.get_array().ok_or_else(|| {
+            DeserializationError::unexpected_type(
+                enum_json,
+                "Expected `enum` attributed to be a JSON array.",
+                ContentType::PropertyType,
+            )
+        })?;
+        if variants.is_empty() {
+            Err(DeserializationError::unexpected_value(
+                enum_json,
+                "Expected non-empty list of variants for `enum` attribute.",
+                ContentType::PropertyType,
+            ))
+        } else {
+            Ok(Some(variants))
+        }
+    } else {
+        Ok(None)
+    }
+}
+
+/// Build the type of the literals permitted by `const` or `enum`. Literals of different kinds
+/// (e.g., `{"enum": ["a", 1, null]}`) produce a [`PropertyType::Union`] with one type per kind.
+/// Returns `None` if a literal cannot be represented as an enumeration (i.e., it is an object,
+/// an array or a non-integer number), in which case the literals do not constrain the type.
+fn enum_from_json_values(
+    literals: &[LocatedValue],
+    type_json: Option<&LocatedValue>,
+) -> Result<Option<PropertyType>, DeserializationError> {
+    let allowed_types = match type_json {
+        None => None,
+        Some(type_json) => match type_json.get_array() {
+            Some(types) => Some(types.iter().filter_map(LocatedValue::get_str).collect()),
+            None => Some(type_json.get_str().into_iter().collect::<Vec<_>>()),
+        },
+    };
+    let check_type = |literal: &LocatedValue, types: &[&str]| match &allowed_types {
+        Some(allowed) if !types.iter().any(|ty| allowed.contains(ty)) => {
+            Err(DeserializationError::unexpected_type(
+                literal,
+                "Expected `enum` and `const` values to match the `type` of the schema.",
+                ContentType::PropertyType,
+            ))
+        }
+        _ => Ok(()),
+    };
+
+    let mut strings = Vec::new();
+    let mut integers = Vec::new();
+    let mut bools = Vec::new();
+    let mut has_null = false;
+    for literal in literals {
+        match Value::from(literal) {
+            Value::String(s) => {
+                check_type(literal, &["string"])?;
+                strings.push(s);
+            }
+            Value::Number(num) => {
+                check_type(literal, &["integer", "number", "float"])?;
+                let Some(i) = num.to_i64() else {
+                    return Ok(None);
+                };
+                integers.push(i);
+            }
+            Value::Bool(b) => {
+                check_type(literal, &["boolean"])?;
+                bools.push(b);
+            }
+            Value::Null => {
+                check_type(literal, &["null"])?;
+                has_null = true;
+            }
+            Value::Array(_) | Value::Map(_) => return Ok(None),
+        }
+    }
+
+    let mut types = Vec::new();
+    if !strings.is_empty() {
+        types.push(PropertyType::Enum { variants: strings });
+    }
+    if !integers.is_empty() {
+        types.push(PropertyType::IntegerEnum { variants: integers });
+    }
+    if !bools.is_empty() {
+        types.push(PropertyType::BoolEnum { variants: bools });
+    }
+    if has_null {
+        types.push(PropertyType::Null);
+    }
+    match <[PropertyType; 1]>::try_from(types) {
+        Ok([ty]) => Ok(Some(ty)),
+        Err(types) => Ok(Some(PropertyType::Union {
+            types,
+            kind: UnionKind::AnyOf,
+            discriminator: None,
+        })),
+    }
+}
+
+fn property_type_of_format(
+    format_json: &LocatedValue,
+) -> Result<PropertyType, DeserializationError> {
+    match format_json.get_str() {
+        Some("date") => Ok(PropertyType::Datetime {
+            format: DatetimeFormat::Date,
+        }),
+        Some("date-time") => Ok(PropertyType::Datetime {
+            format: DatetimeFormat::DateTime,
+        }),
+        Some("duration") => Ok(PropertyType::Duration),
+        Some("ipv4") => Ok(PropertyType::IpAddr {
+            format: IpAddrFormat::Ipv4,
+        }),
+        Some("ipv6") => Ok(PropertyType::IpAddr {
+            format: IpAddrFormat::Ipv6,
+        }),
+        Some("decimal") => Ok(PropertyType::Decimal),
+        Some(_) => Ok(PropertyType::String),
+        None => Err(DeserializationError::unexpected_type(
+            format_json,
+            "Expected `format` attribute to be a string.",
+            ContentType::PropertyType,
+        )),
+    }
+}
+
+/// Returns a [`PropertyType::Union { types }`] if the json value `type_json`
+/// represents a type union.
+/// Return [`PropertyType::Unknown`] if the json value is a boolean or `null`.
+/// Otherwise, throws a `DeserializationError`.
+fn union_type_of_json_value(
+    type_json: &LocatedValue,
+    top_typ: &LinkedHashMap<LocatedString, LocatedValue>,
+    scope: &DefsScope<'_>,
+) -> Result<PropertyType, DeserializationError> {
+    if let Some(types_json) = type_json.get_array() {
+        let types = types_json
+            .iter()
+            .map(|ty_json| tuple_type_element_of_json_value_array_element(ty_json, top_typ, scope))
+            .collect::<Result<_, _>>()?;
+        Ok(PropertyType::Union {
+            types,
+            kind: UnionKind::AnyOf,
+            discriminator: None,
+        })
+    } else if type_json.is_bool() || type_json.is_null() {
+        Ok(PropertyType::Unknown)
+    } else {
+        Err(DeserializationError::unexpected_type(
+            type_json,
+            "Expected `type` attribute to be a string.",
+            ContentType::PropertyType,
+        ))
+    }
+}
+
+fn tuple_type_element_of_json_value_array_element(
+    ty_json: &LocatedValue,
+    top_typ: &LinkedHashMap<LocatedString, LocatedValue>,
+    scope: &DefsScope<'_>,
+) -> Result<PropertyType, DeserializationError> {
+    match ty_json.get_str() {
+        Some("boolean") => Ok(PropertyType::Bool),
+        Some("integer") => Ok(PropertyType::Integer),
+        Some("float") => Ok(PropertyType::Float),
+        Some("number") => Ok(PropertyType::Number),
+        Some("string") => Ok(PropertyType::String),
+        Some("null") => Ok(PropertyType::Null),
+        Some("object") => {
+            let required = required_from_json_value(top_typ.get("required"), ContentType::ToolParameters)?;
+            let properties = properties_from_json_value(top_typ.get("properties"), &required, ContentType::ToolParameters, scope)?;
+            let additional_properties = additional_properties_from_map(top_typ, scope)?;
+            Ok(PropertyType::Object { properties, additional_properties })
+        }
+        Some("array") => property_type_from_json_array_def(top_typ, scope),
+        Some(_) => Err(DeserializationError::unexpected_value(
+            ty_json,
+            "Expected one of: `boolean`, `integer`, `float`, `number`, `string`, `null`, `array`, `object`.",
+            ContentType::PropertyType
+        )),
+        None => property_type_from_json_value(ty_json, scope)
+    }
+}
+
+fn get_value_from_map<'a, T: AsRef<str>>(
+    map: &'a LinkedHashMap<LocatedString, LocatedValue>,
     key_aliases: &[T],
 ) -> Option<&'a LocatedValue> {
     key_aliases.iter().find_map(|key| map.get(key.as_ref()))
@@ -624,47 +1526,803 @@ pub(crate) fn mcp_tool_output_from_json_value(
         .iter()
         .map(|(k, v)| (k.to_smolstr(), v.clone()))
         .collect();
-    Ok(Output { results })
+    Ok(Output {
+        results,
+        content: result_obj.get("content").cloned(),
+    })
 }
 
-fn typedefs_are_well_founded(
-    type_defs: &HashMap<SmolStr, PropertyTypeDef>,
-) -> Result<(), DeserializationError> {
-    // Effectively perform a BFS from each type def to see if
-    // there is any cycle of type defs that directly reference each other
-    // e.g., type A = B; type B = C; type C = a;
-    // Note: other mutually recursive types are acceptable
-    // e.g., type A = B; type B = { "a": A }
-    for (name, ty_def) in type_defs {
-        let mut cycle = vec![name.clone()];
-        let mut ty_def = ty_def;
-        while let PropertyType::Ref { name } = ty_def.property_type() {
-            if cycle.contains(name) {
-                cycle.push(name.clone());
-                return Err(DeserializationError::type_definition_cycle(cycle));
+/// Deserialize an MCP `resources/list` or `resources/templates/list` json response into a `ResourceList`.
+/// A `result` holding both `resources` and `resourceTemplates`, a JSON array of resource and
+/// resource template descriptions, or a single description are also accepted.
+pub(crate) fn resource_list_from_json_value(
+    json_value: &LocatedValue,
+) -> Result<ResourceList, DeserializationError> {
+    let mut list = ResourceList::default();
+    if let Some(descriptions) = json_value.get_array() {
+        for description in descriptions {
+            add_resource_or_template_from_json_value(&mut list, description)?;
+        }
+        return Ok(list);
+    }
+    let result = match json_value.get("result") {
+        Some(result) => result,
+        None if json_value.is_object() => {
+            add_resource_or_template_from_json_value(&mut list, json_value)?;
+            return Ok(list);
+        }
+        None => return Err(DeserializationError::unexpected_type(
+            json_value,
+            "Expected either a JSON object containing an MCP `resources/list` or `resources/templates/list` response, a JSON array of resource descriptions, or a JSON object describing a single MCP resource.",
+            ContentType::ResourceList,
+        )),
+    };
+    let resources = result.get("resources");
+    let templates = result.get("resourceTemplates");
+    if resources.is_none() && templates.is_none() {
+        return Err(DeserializationError::missing_attribute(
+            result,
+            "resources",
+            vec!["resourceTemplates".to_string()],
+        ));
+    }
+    if let Some(resources) = resources {
+        let resources = resources.get_array().ok_or_else(|| DeserializationError::unexpected_type(
+            resources,
+            "Expected `resources` attribute of MCP `resources/list` response to be an array of MCP resource descriptions.",
+            ContentType::ResourceList,
+        ))?;
+        list.resources = resources
+            .iter()
+            .map(resource_from_json_value)
+            .collect::<Result<_, _>>()?;
+    }
+    if let Some(templates) = templates {
+        let templates = templates.get_array().ok_or_else(|| DeserializationError::unexpected_type(
+            templates,
+            "Expected `resourceTemplates` attribute of MCP `resources/templates/list` response to be an array of MCP resource template descriptions.",
+            ContentType::ResourceList,
+        ))?;
+        list.templates = templates
+            .iter()
+            .map(resource_template_from_json_value)
+            .collect::<Result<_, _>>()?;
+    }
+    Ok(list)
+}
+
+/// Add the resource (or resource template, if it has a `uriTemplate` attribute) described by `json_value` to `list`
+fn add_resource_or_template_from_json_value(
+    list: &mut ResourceList,
+    json_value: &LocatedValue,
+) -> Result<(), DeserializationError> {
+    if json_value.get("uriTemplate").is_some() {
+        list.templates
+            .push(resource_template_from_json_value(json_value)?);
+    } else {
+        list.resources.push(resource_from_json_value(json_value)?);
+    }
+    Ok(())
+}
+
+/// Get the string attribute `key` of an MCP Resource (Template) Description, failing if it is not a string
+fn resource_string_attribute(
+    json_value: &LocatedValue,
+    key: &str,
+) -> Result<Option<String>, DeserializationError> {
+    json_value
+        .get(key)
+        .map(|json| {
+            json.get_string().ok_or_else(|| {
+                DeserializationError::unexpected_type(
+                    json,
+                    &format!(
+                        "Expected `{key}` attribute of a MCP Resource Description to be a string."
+                    ),
+                    ContentType::ResourceDescription,
+                )
+            })
+        })
+        .transpose()
+}
+
+/// Deserialize an MCP Resource Description json into a `ResourceDescription`
+fn resource_from_json_value(
+    json_value: &LocatedValue,
+) -> Result<ResourceDescription, DeserializationError> {
+    if !json_value.is_object() {
+        return Err(DeserializationError::unexpected_type(
+            json_value,
+            "Expected a JSON object containing an MCP resource description.",
+            ContentType::ResourceDescription,
+        ));
+    }
+    let uri = resource_string_attribute(json_value, "uri")?
+        .ok_or_else(|| DeserializationError::missing_attribute(json_value, "uri", Vec::new()))?;
+    let name = resource_string_attribute(json_value, "name")?
+        .ok_or_else(|| DeserializationError::missing_attribute(json_value, "name", Vec::new()))?;
+    let description = resource_string_attribute(json_value, "description")?;
+    let mut resource = ResourceDescription::new(uri.into(), name.into(), description);
+    if let Some(title) = resource_string_attribute(json_value, "title")? {
+        resource = resource.with_title(title);
+    }
+    if let Some(mime_type) = resource_string_attribute(json_value, "mimeType")? {
+        resource = resource.with_mime_type(mime_type);
+    }
+    Ok(resource)
+}
+
+/// Deserialize an MCP Resource Template Description json into a `ResourceTemplateDescription`
+fn resource_template_from_json_value(
+    json_value: &LocatedValue,
+) -> Result<ResourceTemplateDescription, DeserializationError> {
+    if !json_value.is_object() {
+        return Err(DeserializationError::unexpected_type(
+            json_value,
+            "Expected a JSON object containing an MCP resource template description.",
+            ContentType::ResourceDescription,
+        ));
+    }
+    let uri_template = json_value.get("uriTemplate").ok_or_else(|| {
+        DeserializationError::missing_attribute(json_value, "uriTemplate", Vec::new())
+    })?;
+    let uri_template = uri_template
+        .get_str()
+        .ok_or_else(|| {
+            DeserializationError::unexpected_type(
+                uri_template,
+                "Expected `uriTemplate` attribute of a MCP Resource Template Description to be a string.",
+                ContentType::ResourceDescription,
+            )
+        })?
+        .parse::<UriTemplate>()
+        .map_err(|e| {
+            DeserializationError::unexpected_value(
+                uri_template,
+                &e.to_string(),
+                ContentType::ResourceDescription,
+            )
+        })?;
+    let name = resource_string_attribute(json_value, "name")?
+        .ok_or_else(|| DeserializationError::missing_attribute(json_value, "name", Vec::new()))?;
+    let description = resource_string_attribute(json_value, "description")?;
+    let mut template = ResourceTemplateDescription::new(uri_template, name.into(), description);
+    if let Some(title) = resource_string_attribute(json_value, "title")? {
+        template = template.with_title(title);
+    }
+    if let Some(mime_type) = resource_string_attribute(json_value, "mimeType")? {
+        template = template.with_mime_type(mime_type);
+    }
+    Ok(template)
+}
+
+/// Deserialize an MCP `resources/read` json request into a `ResourceRead`
+pub(crate) fn resource_read_from_json_value(
+    json_value: &LocatedValue,
+) -> Result<ResourceRead, DeserializationError> {
+    if !json_value.is_object() {
+        return Err(DeserializationError::unexpected_type(
+            json_value,
+            "MCP `resources/read` request should be an object",
+            ContentType::ResourceReadRequest,
+        ));
+    }
+    let params = json_value
+        .get("params")
+        .ok_or_else(|| DeserializationError::missing_attribute(json_value, "params", vec![]))?;
+    if !params.is_object() {
+        return Err(DeserializationError::unexpected_type(
+            params,
+            "MCP `resources/read` request \"params\" attribute should be an object",
+            ContentType::ResourceReadRequest,
+        ));
+    }
+    let uri = params
+        .get("uri")
+        .ok_or_else(|| DeserializationError::missing_attribute(params, "uri", vec![]))?;
+    let uri = uri.get_smolstr().ok_or_else(|| {
+        DeserializationError::unexpected_type(
+            uri,
+            "Expected \"uri\" attribute to be a string",
+            ContentType::ResourceReadRequest,
+        )
+    })?;
+    Ok(ResourceRead::new(uri))
+}
+
+/// Deserialize an MCP `prompts/list` json response into a `PromptList`.
+/// A JSON array of prompt descriptions, or a single description, are also accepted.
+pub(crate) fn prompt_list_from_json_value(
+    json_value: &LocatedValue,
+) -> Result<PromptList, DeserializationError> {
+    if let Some(descriptions) = json_value.get_array() {
+        return Ok(PromptList::new(
+            descriptions
+                .iter()
+                .map(prompt_from_json_value)
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter(),
+        ));
+    }
+    let result = match json_value.get("result") {
+        Some(result) => result,
+        None if json_value.is_object() => {
+            return Ok(PromptList::new(std::iter::once(prompt_from_json_value(
+                json_value,
+            )?)))
+        }
+        None => return Err(DeserializationError::unexpected_type(
+            json_value,
+            "Expected either a JSON object containing an MCP `prompts/list` response, a JSON array of prompt descriptions, or a JSON object describing a single MCP prompt.",
+            ContentType::PromptList,
+        )),
+    };
+    let prompts = result
+        .get("prompts")
+        .ok_or_else(|| DeserializationError::missing_attribute(result, "prompts", Vec::new()))?;
+    let prompts = prompts.get_array().ok_or_else(|| {
+        DeserializationError::unexpected_type(
+            prompts,
+            "Expected `prompts` attribute of MCP `prompts/list` response to be an array of MCP prompt descriptions.",
+            ContentType::PromptList,
+        )
+    })?;
+    Ok(PromptList::new(
+        prompts
+            .iter()
+            .map(prompt_from_json_value)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter(),
+    ))
+}
+
+/// Get the string attribute `key` of an MCP Prompt (Argument) Description, failing if it is not a string
+fn prompt_string_attribute(
+    json_value: &LocatedValue,
+    key: &str,
+) -> Result<Option<String>, DeserializationError> {
+    json_value
+        .get(key)
+        .map(|json| {
+            json.get_string().ok_or_else(|| {
+                DeserializationError::unexpected_type(
+                    json,
+                    &format!(
+                        "Expected `{key}` attribute of a MCP Prompt Description to be a string."
+                    ),
+                    ContentType::PromptDescription,
+                )
+            })
+        })
+        .transpose()
+}
+
+/// Deserialize an MCP Prompt Description json into a `PromptDescription`
+fn prompt_from_json_value(
+    json_value: &LocatedValue,
+) -> Result<PromptDescription, DeserializationError> {
+    if !json_value.is_object() {
+        return Err(DeserializationError::unexpected_type(
+            json_value,
+            "Expected a JSON object containing an MCP prompt description.",
+            ContentType::PromptDescription,
+        ));
+    }
+    let name = prompt_string_attribute(json_value, "name")?
+        .ok_or_else(|| DeserializationError::missing_attribute(json_value, "name", Vec::new()))?;
+    let description = prompt_string_attribute(json_value, "description")?;
+    let arguments = match json_value.get("arguments") {
+        Some(arguments) => arguments
+            .get_array()
+            .ok_or_else(|| {
+                DeserializationError::unexpected_type(
+                    arguments,
+                    "Expected `arguments` attribute of a MCP Prompt Description to be an array of prompt arguments.",
+                    ContentType::PromptDescription,
+                )
+            })?
+            .iter()
+            .map(prompt_argument_from_json_value)
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    let mut names = HashSet::new();
+    if let Some(duplicate) = arguments.iter().find(|arg| !names.insert(arg.name())) {
+        return Err(DeserializationError::unexpected_value(
+            json_value,
+            &format!(
+                "Prompt argument `{}` is declared more than once.",
+                duplicate.name()
+            ),
+            ContentType::PromptDescription,
+        ));
+    }
+    let mut prompt = PromptDescription::new(name.into(), arguments.into_iter(), description);
+    if let Some(title) = prompt_string_attribute(json_value, "title")? {
+        prompt = prompt.with_title(title);
+    }
+    Ok(prompt)
+}
+
+/// Deserialize an argument of an MCP Prompt Description json into a `PromptArgument`
+fn prompt_argument_from_json_value(
+    json_value: &LocatedValue,
+) -> Result<PromptArgument, DeserializationError> {
+    if !json_value.is_object() {
+        return Err(DeserializationError::unexpected_type(
+            json_value,
+            "Expected a JSON object containing an MCP prompt argument.",
+            ContentType::PromptDescription,
+        ));
+    }
+    let name = prompt_string_attribute(json_value, "name")?
+        .ok_or_else(|| DeserializationError::missing_attribute(json_value, "name", Vec::new()))?;
+    let description = prompt_string_attribute(json_value, "description")?;
+    let required = match json_value.get("required") {
+        Some(required) => required.get_bool().ok_or_else(|| {
+            DeserializationError::unexpected_type(
+                required,
+                "Expected `required` attribute of a MCP Prompt Argument to be a boolean.",
+                ContentType::PromptDescription,
+            )
+        })?,
+        None => false,
+    };
+    let mut argument = PromptArgument::new(name.into(), required, description);
+    if let Some(title) = prompt_string_attribute(json_value, "title")? {
+        argument = argument.with_title(title);
+    }
+    Ok(argument)
+}
+
+/// Deserialize an MCP `prompts/get` json request into a `PromptGet`
+pub(crate) fn prompt_get_from_json_value(
+    json_value: &LocatedValue,
+) -> Result<PromptGet, DeserializationError> {
+    if !json_value.is_object() {
+        return Err(DeserializationError::unexpected_type(
+            json_value,
+            "MCP `prompts/get` request should be an object",
+            ContentType::PromptGetRequest,
+        ));
+    }
+    let params = json_value
+        .get("params")
+        .ok_or_else(|| DeserializationError::missing_attribute(json_value, "params", vec![]))?;
+    if !params.is_object() {
+        return Err(DeserializationError::unexpected_type(
+            params,
+            "MCP `prompts/get` request \"params\" attribute should be an object",
+            ContentType::PromptGetRequest,
+        ));
+    }
+    let name = params
+        .get("name")
+        .ok_or_else(|| DeserializationError::missing_attribute(params, "name", vec![]))?;
+    let name = name.get_smolstr().ok_or_else(|| {
+        DeserializationError::unexpected_type(
+            name,
+            "Expected \"name\" attribute to be a string",
+            ContentType::PromptGetRequest,
+        )
+    })?;
+    // Prompts without arguments may be requested without `arguments`
+    let args = match params.get("arguments") {
+        Some(args) => args
+            .get_object()
+            .ok_or_else(|| {
+                DeserializationError::unexpected_type(
+                    args,
+                    "Expected \"arguments\" attribute to be an object",
+                    ContentType::PromptGetRequest,
+                )
+            })?
+            .iter()
+            .map(|(k, v)| (k.to_smolstr(), v.clone()))
+            .collect(),
+        None => HashMap::new(),
+    };
+    Ok(PromptGet { name, args })
+}
+
+fn typedefs_are_well_founded(
+    type_defs: &HashMap<SmolStr, PropertyTypeDef>,
+) -> Result<(), DeserializationError> {
+    // Effectively perform a BFS from each type def to see if
+    // there is any cycle of type defs that directly reference each other
+    // e.g., type A = B; type B = C; type C = a;
+    // Note: other mutually recursive types are acceptable
+    // e.g., type A = B; type B = { "a": A }
+    for (name, ty_def) in type_defs {
+        let mut cycle = vec![name.clone()];
+        let mut ty_def = ty_def;
+        while let PropertyType::Ref { name } = ty_def.property_type().base_type() {
+            if cycle.contains(name) {
+                cycle.push(name.clone());
+                return Err(DeserializationError::type_definition_cycle(cycle));
+            }
+            match type_defs.get(name) {
+                Some(tdef) => {
+                    cycle.push(name.clone());
+                    ty_def = tdef
+                }
+                _ => break,
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::err::ValidationError;
+    use crate::parser::json_parser::JsonParser;
+    use cool_asserts::assert_matches;
+
+    fn parse_property_type(json: &str) -> Result<PropertyType, DeserializationError> {
+        let mut parser = JsonParser::new(json);
+        let value = parser.get_value().expect("JSON should parse");
+        property_type_from_json_value(&value, &DefsScope::default())
+    }
+
+    /// Parse a property type in the scope of a schema root.
+    fn parse_property_type_in_root(
+        root_json: &str,
+        json: &str,
+    ) -> Result<PropertyType, DeserializationError> {
+        parse_property_type_and_typedefs(root_json, json).map(|(ty, _)| ty)
+    }
+
+    /// Parse a property type in the scope of a schema root, returning the
+    /// property type and the type definitions of the root.
+    fn parse_property_type_and_typedefs(
+        root_json: &str,
+        json: &str,
+    ) -> Result<(PropertyType, HashMap<SmolStr, PropertyTypeDef>), DeserializationError> {
+        let mut parser = JsonParser::new(root_json);
+        let root_value = parser.get_value().expect("JSON should parse");
+        let mut parser = JsonParser::new(json);
+        let value = parser.get_value().expect("JSON should parse");
+        let root = SchemaRoot::new(&root_value);
+        let scope = DefsScope::default();
+        let scope = scope.with_root(&root);
+        let ty = property_type_from_json_value(&value, &scope)?;
+        let type_defs = typedefs_from_root(&root, ContentType::ToolParameters, &scope)?;
+        Ok((ty, type_defs))
+    }
+
+    #[test]
+    fn test_ref_to_definitions() {
+        let (ty, type_defs) = parse_property_type_and_typedefs(
+            r#"{"definitions": {"Foo": {"type": "string"}}}"#,
+            r##"{"$ref": "#/definitions/Foo"}"##,
+        )
+        .expect("`$ref` should resolve");
+        assert_matches!(ty, PropertyType::Ref { name } if name == "Foo");
+        assert_matches!(
+            type_defs.get("Foo").map(PropertyTypeDef::property_type),
+            Some(PropertyType::String)
+        );
+    }
+
+    #[test]
+    fn test_ref_to_nested_subschema() {
+        let root = r#"{
+            "$defs": {
+                "Outer": {
+                    "type": "object",
+                    "properties": {
+                        "inner": {"type": "integer", "description": "An inner value"},
+                        "a/b c": {"type": "boolean"}
+                    }
+                }
+            }
+        }"#;
+        let (ty, type_defs) = parse_property_type_and_typedefs(
+            root,
+            r##"{"$ref": "#/$defs/Outer/properties/inner"}"##,
+        )
+        .expect("`$ref` should resolve");
+        assert_matches!(ty, PropertyType::Ref { name } if name == "Outer_properties_inner");
+        assert_matches!(type_defs.get("Outer_properties_inner"), Some(def) => {
+            assert_matches!(def.property_type(), PropertyType::Integer);
+            assert_matches!(def.description(), Some("An inner value"));
+        });
+        assert!(type_defs.contains_key("Outer"));
+
+        // Escaped and percent-encoded reference tokens
+        let ty =
+            parse_property_type_in_root(root, r##"{"$ref": "#/$defs/Outer/properties/a~1b%20c"}"##);
+        assert_matches!(ty, Ok(PropertyType::Ref { name }) if name == "Outer_properties_a_b_c");
+
+        // Repeated references share a single type definition
+        let (ty, type_defs) = parse_property_type_and_typedefs(
+            root,
+            r##"{"type": "array", "prefixItems": [{"$ref": "#/$defs/Outer/properties/inner"}, {"$ref": "#/$defs/Outer/properties/inner"}], "items": false}"##,
+        )
+        .expect("`$ref` should resolve");
+        assert_matches!(ty, PropertyType::Tuple { types } if types.len() == 2);
+        assert_eq!(type_defs.len(), 2);
+    }
+
+    #[test]
+    fn test_ref_to_anchor_and_id() {
+        let root = r#"{
+            "$defs": {
+                "Foo": {"$anchor": "foo", "type": "string"},
+                "Bar": {"type": "object", "properties": {"baz": {"$anchor": "baz", "type": "boolean"}}},
+                "Address": {
+                    "$id": "https://example.com/address.json",
+                    "type": "object",
+                    "properties": {"zip": {"type": "string"}}
+                }
+            }
+        }"#;
+        let check = |reference: &str, expected: &str| {
+            let json = format!(r#"{{"$ref": "{reference}"}}"#);
+            assert_matches!(
+                parse_property_type_in_root(root, &json),
+                Ok(PropertyType::Ref { name }) if name == expected,
+                "expected {reference} to resolve to {expected}"
+            );
+        };
+        check("#foo", "Foo");
+        check("#baz", "Bar_properties_baz");
+        check("https://example.com/address.json", "Address");
+        check(
+            "https://example.com/address.json#/properties/zip",
+            "Address_properties_zip",
+        );
+    }
+
+    #[test]
+    fn test_unresolvable_refs_error() {
+        let root = r#"{
+            "$defs": {
+                "Outer": {"type": "object", "properties": {"inner": {"type": "integer"}}},
+                "Outer_properties_inner": {"type": "string"}
+            }
+        }"#;
+        let cases = [
+            r##"{"$ref": "#/$defs/Missing/properties/inner"}"##,
+            r##"{"$ref": "#"}"##,
+            r##"{"$ref": "#missing"}"##,
+            r##"{"$ref": "other.json"}"##,
+            r##"{"$ref": "#/$defs/Outer/properties/a~2"}"##,
+            // The synthesized name conflicts with an existing type definition
+            r##"{"$ref": "#/$defs/Outer/properties/inner"}"##,
+        ];
+        for json in cases {
+            assert_matches!(
+                parse_property_type_in_root(root, json),
+                Err(DeserializationError::UnexpectedValue(..)),
+                "expected {json} to fail"
+            );
+        }
+        // References to missing type definitions are kept
+        assert_matches!(
+            parse_property_type_in_root(root, r##"{"$ref": "#/$defs/Missing"}"##),
+            Ok(PropertyType::Ref { name }) if name == "Missing"
+        );
+    }
+
+    #[test]
+    fn test_ref_typedefs_errors() {
+        assert_matches!(
+            parse_property_type_and_typedefs(
+                r#"{"$defs": {"Foo": {"type": "string"}}, "definitions": {"Foo": {"type": "integer"}}}"#,
+                r#"{"type": "string"}"#,
+            ),
+            Err(DeserializationError::UnexpectedValue(..))
+        );
+        assert_matches!(
+            parse_property_type_and_typedefs(
+                r##"{"$defs": {"A": {"type": "object", "properties": {"b": {"$ref": "#/$defs/A/properties/b"}}}}}"##,
+                r##"{"$ref": "#/$defs/A"}"##,
+            ),
+            Err(DeserializationError::NonWellFoundedTypeDefinitions(..))
+        );
+    }
+
+    #[test]
+    fn test_property_type_constraints() {
+        let result = parse_property_type(
+            r#"{"type": "integer", "minimum": 0, "maximum": 100, "multipleOf": 5}"#,
+        );
+        assert_matches!(result, Ok(PropertyType::Constrained { ty, constraints }) => {
+            assert_matches!(*ty, PropertyType::Integer);
+            assert_matches!(constraints.minimum().map(Number::as_str), Some("0"));
+            assert_matches!(constraints.maximum().map(Number::as_str), Some("100"));
+            assert_matches!(constraints.multiple_of().map(Number::as_str), Some("5"));
+            assert_matches!(constraints.exclusive_minimum(), None);
+        });
+
+        let result = parse_property_type(
+            r#"{"type": "string", "minLength": 1, "maxLength": 8, "pattern": "^[a-z]+$"}"#,
+        );
+        assert_matches!(result, Ok(PropertyType::Constrained { ty, constraints }) => {
+            assert_matches!(*ty, PropertyType::String);
+            assert_matches!(constraints.min_length(), Some(1));
+            assert_matches!(constraints.max_length(), Some(8));
+            assert_matches!(constraints.pattern().map(Pattern::as_str), Some("^[a-z]+$"));
+        });
+
+        let result = parse_property_type(
+            r#"{"type": "array", "items": {"type": "string"}, "minItems": 1, "uniqueItems": true}"#,
+        );
+        assert_matches!(result, Ok(PropertyType::Constrained { ty, constraints }) => {
+            assert_matches!(*ty, PropertyType::Array { .. });
+            assert_matches!(constraints.min_items(), Some(1));
+            assert_matches!(constraints.max_items(), None);
+            assert!(constraints.unique_items());
+        });
+    }
+
+    #[test]
+    fn test_property_type_without_constraints_is_not_wrapped() {
+        let result = parse_property_type(r#"{"type": "array", "uniqueItems": false}"#);
+        assert_matches!(result, Ok(PropertyType::Array { .. }));
+    }
+
+    #[test]
+    fn test_property_type_draft4_exclusive_bounds() {
+        let result = parse_property_type(
+            r#"{"type": "number", "minimum": 0, "exclusiveMinimum": true, "maximum": 1, "exclusiveMaximum": false}"#,
+        );
+        assert_matches!(result, Ok(PropertyType::Constrained { constraints, .. }) => {
+            assert_matches!(constraints.minimum(), None);
+            assert_matches!(constraints.exclusive_minimum().map(Number::as_str), Some("0"));
+            assert_matches!(constraints.maximum().map(Number::as_str), Some("1"));
+            assert_matches!(constraints.exclusive_maximum(), None);
+        });
+    }
+
+    #[test]
+    fn test_property_type_malformed_constraints_error() {
+        let cases = [
+            r#"{"type": "integer", "minimum": "0"}"#,
+            r#"{"type": "integer", "exclusiveMaximum": null}"#,
+            r#"{"type": "number", "multipleOf": 0}"#,
+            r#"{"type": "string", "minLength": -1}"#,
+            r#"{"type": "string", "maxLength": 1.5}"#,
+            r#"{"type": "string", "pattern": 5}"#,
+            r#"{"type": "string", "pattern": "(unclosed"}"#,
+            r#"{"type": "array", "uniqueItems": "yes"}"#,
+        ];
+        for json in cases {
+            assert_matches!(
+                parse_property_type(json),
+                Err(DeserializationError::UnexpectedType(..)
+                    | DeserializationError::UnexpectedValue(..)),
+                "expected {json} to fail"
+            );
+        }
+    }
+
+    #[test]
+    fn test_all_of_merges_objects() {
+        let tool = ToolDescription::from_json_str(
+            r##"{
+    "name": "create_user",
+    "inputSchema": {
+        "type": "object",
+        "properties": {
+            "user": {
+                "allOf": [
+                    { "$ref": "#/$defs/Base" },
+                    {
+                        "properties": { "email": { "type": "string", "description": "Email" } },
+                        "required": ["id", "email"]
+                    }
+                ],
+                "description": "The user"
             }
-            match type_defs.get(name) {
-                Some(tdef) => {
-                    cycle.push(name.clone());
-                    ty_def = tdef
-                }
-                _ => break,
+        },
+        "$defs": {
+            "Base": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "integer" },
+                    "name": { "type": "string" }
+                },
+                "required": ["name"]
             }
         }
     }
-    Ok(())
-}
+}"##,
+        )
+        .expect("Failed to parse tool");
+        let user = tool
+            .inputs()
+            .properties()
+            .find(|p| p.name() == "user")
+            .expect("Expected property `user`");
+        assert_matches!(user.description(), Some("The user"));
+        assert_matches!(user.property_type(), PropertyType::Object { properties, additional_properties: None } => {
+            let props = properties
+                .iter()
+                .map(|p| (p.name(), p.property_type(), p.is_required()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                props,
+                vec![
+                    ("id", &PropertyType::Integer, true),
+                    ("name", &PropertyType::String, true),
+                    ("email", &PropertyType::String, true),
+                ]
+            );
+        });
+    }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::parser::json_parser::JsonParser;
-    use cool_asserts::assert_matches;
+    #[test]
+    fn test_all_of_single_schema_is_unwrapped() {
+        let result = parse_property_type(
+            r##"{"allOf": [{"$ref": "#/$defs/Color"}], "description": "A color"}"##,
+        );
+        assert_matches!(result, Ok(PropertyType::Ref { name }) if name == "Color");
 
-    fn parse_property_type(json: &str) -> Result<PropertyType, DeserializationError> {
-        let mut parser = JsonParser::new(json);
-        let value = parser.get_value().expect("JSON should parse");
-        property_type_from_json_value(&value)
+        let result =
+            parse_property_type(r#"{"allOf": [{"type": "string"}, {"maxLength": 8}, {}]}"#);
+        assert_matches!(result, Ok(PropertyType::Constrained { ty, constraints }) => {
+            assert_matches!(*ty, PropertyType::String);
+            assert_matches!(constraints.max_length(), Some(8));
+        });
+    }
+
+    #[test]
+    fn test_all_of_conflicting_property_types_errors() {
+        let json = r#"{"allOf": [
+                {"type": "object", "properties": {"id": {"type": "integer"}}},
+                {"type": "object", "properties": {"id": {"type": "string"}}}
+            ]}"#;
+        let conflict_offset = json.find(r#"{"type": "string"}"#);
+        assert_matches!(
+            parse_property_type(json),
+            Err(e @ DeserializationError::ConflictingPropertyTypes(..)) => {
+                let label = miette::Diagnostic::labels(&e).and_then(|mut labels| labels.next());
+                assert_eq!(label.map(|label| label.offset()), conflict_offset);
+            }
+        );
+    }
+
+    #[test]
+    fn test_all_of_malformed_errors() {
+        // Not an array
+        assert_matches!(
+            parse_property_type(r#"{"allOf": {"type": "object"}}"#),
+            Err(DeserializationError::UnexpectedType(..))
+        );
+        // Cannot merge non-object types
+        assert_matches!(
+            parse_property_type(r#"{"allOf": [{"type": "string"}, {"type": "integer"}]}"#),
+            Err(DeserializationError::UnexpectedValue(..))
+        );
+        // Reference to undefined type
+        assert_matches!(
+            parse_property_type(
+                r##"{"allOf": [{"$ref": "#/$defs/Missing"}, {"type": "object"}]}"##
+            ),
+            Err(DeserializationError::UnexpectedValue(..))
+        );
+    }
+
+    #[test]
+    fn test_all_of_cycle_errors() {
+        let result = ToolDescription::from_json_str(
+            r##"{
+    "name": "test_tool",
+    "inputSchema": { "type": "object", "properties": {} },
+    "$defs": {
+        "A": { "allOf": [{ "$ref": "#/$defs/B" }, { "type": "object" }] },
+        "B": { "allOf": [{ "$ref": "#/$defs/A" }, { "type": "object" }] }
+    }
+}"##,
+        );
+        assert_matches!(
+            result,
+            Err(DeserializationError::NonWellFoundedTypeDefinitions(..))
+        );
     }
 
     #[test]
@@ -687,7 +2345,7 @@ mod test {
     #[test]
     fn test_property_type_primitive_type_array() {
         let result = parse_property_type(r#"{"type": ["null", "string"]}"#);
-        assert_matches!(result, Ok(PropertyType::Union { types }) if types.len() == 2);
+        assert_matches!(result, Ok(PropertyType::Union { types, .. }) if types.len() == 2);
     }
 
     #[test]
@@ -823,10 +2481,207 @@ mod test {
         assert_matches!(result, Ok(PropertyType::Enum { variants }) if variants.len() == 3);
     }
 
+    #[test]
+    fn test_property_type_const_and_non_string_enums() {
+        assert_matches!(
+            parse_property_type(r#"{"const": "create"}"#),
+            Ok(PropertyType::Enum { variants }) if variants == ["create"]
+        );
+        assert_matches!(
+            parse_property_type(r#"{"enum": [1, 2, 3]}"#),
+            Ok(PropertyType::IntegerEnum { variants }) if variants == [1, 2, 3]
+        );
+        assert_matches!(
+            parse_property_type(r#"{"type": "number", "enum": [-1, 0]}"#),
+            Ok(PropertyType::IntegerEnum { variants }) if variants == [-1, 0]
+        );
+        assert_matches!(
+            parse_property_type(r#"{"type": "boolean", "const": true}"#),
+            Ok(PropertyType::BoolEnum { variants }) if variants == [true]
+        );
+        // `const` takes precedence over `enum`
+        assert_matches!(
+            parse_property_type(r#"{"const": 2, "enum": [1, 2]}"#),
+            Ok(PropertyType::IntegerEnum { variants }) if variants == [2]
+        );
+    }
+
+    #[test]
+    fn test_property_type_union_kinds() {
+        assert_matches!(
+            parse_property_type(r#"{"anyOf": [{"type": "string"}, {"type": "integer"}]}"#),
+            Ok(PropertyType::Union {
+                kind: UnionKind::AnyOf,
+                discriminator: None,
+                ..
+            })
+        );
+        assert_matches!(
+            parse_property_type(r#"{"oneOf": [{"type": "string"}, {"type": "integer"}]}"#),
+            Ok(PropertyType::Union {
+                kind: UnionKind::OneOf,
+                discriminator: None,
+                ..
+            })
+        );
+        assert_matches!(
+            parse_property_type(r#"{"type": ["string", "integer"]}"#),
+            Ok(PropertyType::Union {
+                kind: UnionKind::AnyOf,
+                discriminator: None,
+                ..
+            })
+        );
+    }
+
+    #[test]
+    fn test_property_type_discriminator_values() {
+        let result = parse_property_type(
+            r#"{
+                "oneOf": [
+                    {"type": "object", "properties": {"kind": {"const": "circle"}, "radius": {"type": "number"}}},
+                    {"properties": {"kind": {"type": "string", "enum": ["square"]}, "side": {"type": "number"}}}
+                ],
+                "discriminator": {"propertyName": "kind"}
+            }"#,
+        );
+        assert_matches!(result, Ok(PropertyType::Union { discriminator: Some(discriminator), .. }) => {
+            assert_eq!(discriminator.property_name(), "kind");
+            assert_eq!(discriminator.values(), ["circle", "square"]);
+        });
+
+        let result = parse_property_type_in_root(
+            r##"{
+                "$defs": {
+                    "Circle": {"type": "object", "properties": {"radius": {"type": "number"}}},
+                    "Square": {"type": "object", "properties": {"side": {"type": "number"}}}
+                }
+            }"##,
+            r##"{
+                "oneOf": [{"$ref": "#/$defs/Circle"}, {"$ref": "#/$defs/Square"}],
+                "discriminator": {"propertyName": "kind", "mapping": {"round": "#/$defs/Circle"}}
+            }"##,
+        );
+        assert_matches!(result, Ok(PropertyType::Union { discriminator: Some(discriminator), .. }) => {
+            assert_eq!(discriminator.values(), ["round", "Square"]);
+        });
+    }
+
+    #[test]
+    fn test_property_type_incomplete_discriminator_is_dropped() {
+        let result = parse_property_type(
+            r#"{
+                "oneOf": [
+                    {"type": "object", "properties": {"kind": {"const": "circle"}}},
+                    {"type": "object", "properties": {"kind": {"type": "string"}}}
+                ],
+                "discriminator": {"propertyName": "kind"}
+            }"#,
+        );
+        assert_matches!(
+            result,
+            Ok(PropertyType::Union {
+                kind: UnionKind::OneOf,
+                discriminator: None,
+                ..
+            })
+        );
+
+        let result = parse_property_type(
+            r#"{
+                "oneOf": [
+                    {"type": "object", "properties": {"kind": {"const": "circle"}}},
+                    {"type": "object", "properties": {"kind": {"const": "circle"}}}
+                ],
+                "discriminator": {"propertyName": "kind"}
+            }"#,
+        );
+        assert_matches!(
+            result,
+            Ok(PropertyType::Union {
+                discriminator: None,
+                ..
+            })
+        );
+    }
+
+    #[test]
+    fn test_property_type_malformed_discriminator_errors() {
+        let cases = [
+            r#"{"oneOf": [{"type": "object"}], "discriminator": "kind"}"#,
+            r#"{"oneOf": [{"type": "object"}], "discriminator": {"propertyName": 1}}"#,
+            r#"{"oneOf": [{"type": "object"}], "discriminator": {"propertyName": "kind", "mapping": []}}"#,
+            r#"{"oneOf": [{"type": "object"}], "discriminator": {"propertyName": "kind", "mapping": {"a": 1}}}"#,
+        ];
+        for json in cases {
+            assert_matches!(
+                parse_property_type(json),
+                Err(DeserializationError::UnexpectedType(..)),
+                "expected {json} to fail"
+            );
+        }
+    }
+
+    #[test]
+    fn test_property_type_mixed_enum_is_union() {
+        let result =
+            parse_property_type(r#"{"type": ["string", "null"], "enum": ["a", null, "b"]}"#);
+        assert_matches!(result, Ok(PropertyType::Union { types, .. }) => {
+            assert_matches!(&types[..], [PropertyType::Enum { variants }, PropertyType::Null] if variants == &["a", "b"]);
+        });
+
+        let result = parse_property_type(r#"{"enum": [1, "one", false]}"#);
+        assert_matches!(result, Ok(PropertyType::Union { types, .. }) => {
+            assert_matches!(
+                &types[..],
+                [PropertyType::Enum { .. }, PropertyType::IntegerEnum { .. }, PropertyType::BoolEnum { .. }]
+            );
+        });
+    }
+
+    #[test]
+    fn test_property_type_non_scalar_literals_do_not_constrain() {
+        assert_matches!(
+            parse_property_type(
+                r#"{"type": "object", "properties": {"a": {"type": "integer"}}, "const": {"a": 1}}"#
+            ),
+            Ok(PropertyType::Object { properties, .. }) if properties.len() == 1
+        );
+        assert_matches!(
+            parse_property_type(r#"{"type": "array", "items": {"type": "integer"}, "enum": [[1, 2], [3]]}"#),
+            Ok(PropertyType::Array { element_ty }) if *element_ty == PropertyType::Integer
+        );
+        assert_matches!(
+            parse_property_type(r#"{"type": "number", "enum": [1.5, 2.5]}"#),
+            Ok(PropertyType::Number)
+        );
+        assert_matches!(
+            parse_property_type(r#"{"const": {"a": 1}}"#),
+            Ok(PropertyType::Unknown)
+        );
+    }
+
+    #[test]
+    fn test_property_type_unsupported_enum_values_error() {
+        let cases = [
+            r#"{"type": "integer", "enum": ["1"]}"#,
+            r#"{"type": "boolean", "const": 1}"#,
+            r#"{"type": ["string", "integer"], "enum": [null]}"#,
+        ];
+        for json in cases {
+            assert_matches!(
+                parse_property_type(json),
+                Err(DeserializationError::UnexpectedType(..)
+                    | DeserializationError::UnexpectedValue(..)),
+                "expected {json} to fail"
+            );
+        }
+    }
+
     #[test]
     fn test_property_type_anyof_union() {
         let result = parse_property_type(r#"{"anyOf": [{"type": "string"}, {"type": "integer"}]}"#);
-        assert_matches!(result, Ok(PropertyType::Union { types }) if types.len() == 2);
+        assert_matches!(result, Ok(PropertyType::Union { types, .. }) if types.len() == 2);
     }
 
     #[test]
@@ -868,7 +2723,7 @@ mod test {
         }"#;
         let mut parser = JsonParser::new(json);
         let value = parser.get_value().expect("JSON should parse");
-        let result = property_type_from_json_value(&value);
+        let result = property_type_from_json_value(&value, &DefsScope::default());
         assert!(result.is_ok(), "Expected successful parse, got: {result:?}");
     }
 
@@ -940,9 +2795,282 @@ mod test {
         );
         assert_matches!(
             result,
-            Ok(PropertyType::Union { types }) if types.len() == 2
+            Ok(PropertyType::Union { types, .. }) if types.len() == 2
                 && matches!(types[0], PropertyType::Null)
                 && matches!(types[1], PropertyType::Tuple { ref types } if types.len() == 2)
         );
     }
+
+    #[test]
+    fn test_tool_title_and_annotations() {
+        let tool = ToolDescription::from_json_str(
+            r#"{
+                "name": "delete_file",
+                "title": "Delete File",
+                "inputSchema": {"type": "object", "properties": {}},
+                "annotations": {
+                    "title": "Delete a file",
+                    "readOnlyHint": false,
+                    "idempotentHint": true,
+                    "openWorldHint": false,
+                    "x-vendor": "ignored"
+                }
+            }"#,
+        )
+        .expect("tool should parse");
+        assert_eq!(tool.title(), Some("Delete File"));
+        let annotations = tool.annotations();
+        assert_eq!(annotations.title(), Some("Delete a file"));
+        assert_eq!(annotations.read_only_hint(), Some(false));
+        assert_eq!(annotations.destructive_hint(), None);
+        assert!(annotations.is_destructive());
+        assert!(annotations.is_idempotent());
+        assert!(!annotations.is_open_world());
+
+        let tool = ToolDescription::from_json_str(
+            r#"{"name": "t", "inputSchema": {"type": "object"}, "annotations": {"title": "T", "readOnlyHint": true}}"#,
+        )
+        .expect("tool should parse");
+        assert_eq!(tool.title(), Some("T"));
+        assert!(tool.annotations().is_read_only());
+        assert!(!tool.annotations().is_destructive());
+        assert!(tool.annotations().is_idempotent());
+
+        let tool =
+            ToolDescription::from_json_str(r#"{"name": "t", "inputSchema": {"type": "object"}}"#)
+                .expect("tool should parse");
+        assert_eq!(tool.title(), None);
+        assert!(tool.annotations().is_empty());
+        assert!(tool.annotations().is_destructive());
+        assert!(tool.annotations().is_open_world());
+    }
+
+    #[test]
+    fn test_malformed_tool_annotations_error() {
+        for json in [
+            r#"{"name": "t", "inputSchema": {"type": "object"}, "title": 1}"#,
+            r#"{"name": "t", "inputSchema": {"type": "object"}, "annotations": []}"#,
+            r#"{"name": "t", "inputSchema": {"type": "object"}, "annotations": {"readOnlyHint": "yes"}}"#,
+            r#"{"name": "t", "inputSchema": {"type": "object"}, "annotations": {"title": false}}"#,
+        ] {
+            assert_matches!(
+                ToolDescription::from_json_str(json),
+                Err(DeserializationError::UnexpectedType(_)),
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_resource_list() {
+        let resources = ResourceList::from_json_str(
+            r#"{"jsonrpc": "2.0", "id": 1, "result": {"resources": [
+                {"uri": "file:///project/README.md", "name": "README", "title": "Project README", "mimeType": "text/markdown"},
+                {"uri": "config://app", "name": "config", "description": "The application configuration"}
+            ]}}"#,
+        )
+        .expect("resources/list response should parse");
+        let resources = resources.resources().collect::<Vec<_>>();
+        assert_matches!(resources.as_slice(), [readme, config] => {
+            assert_eq!(readme.uri(), "file:///project/README.md");
+            assert_eq!(readme.name(), "README");
+            assert_eq!(readme.title(), Some("Project README"));
+            assert_eq!(readme.mime_type(), Some("text/markdown"));
+            assert_eq!(readme.description(), None);
+            assert_eq!(config.uri(), "config://app");
+            assert_eq!(config.description(), Some("The application configuration"));
+        });
+
+        let mut templates = ResourceList::from_json_str(
+            r#"{"result": {"resourceTemplates": [
+                {"uriTemplate": "file:///{+path}", "name": "file", "description": "A project file"},
+                {"uriTemplate": "users://{id}/profile", "name": "profile", "mimeType": "application/json"}
+            ]}}"#,
+        )
+        .expect("resources/templates/list response should parse");
+        assert_eq!(templates.resources().count(), 0);
+        let names = templates
+            .resource_templates()
+            .map(|template| (template.name(), template.uri_template().as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                ("file", "file:///{+path}"),
+                ("profile", "users://{id}/profile")
+            ]
+        );
+
+        let (template, values) = templates
+            .match_uri("users://alice/profile")
+            .expect("URI should match a template");
+        assert_eq!(template.name(), "profile");
+        assert_eq!(values.get("id").map(SmolStr::as_str), Some("alice"));
+        assert_matches!(templates.match_uri("https://example.com"), None);
+
+        templates.extend(
+            ResourceList::from_json_str(
+                r#"[{"uri": "config://app", "name": "config"}, {"uriTemplate": "logs://{date}", "name": "logs"}]"#,
+            )
+            .expect("JSON array of descriptions should parse"),
+        );
+        assert_eq!(templates.resources().count(), 1);
+        assert_eq!(templates.resource_templates().count(), 3);
+    }
+
+    #[test]
+    fn test_malformed_resource_list_error() {
+        for json in [
+            r#"{"result": {"resources": {}}}"#,
+            r#"{"result": {"resourceTemplates": [{"uriTemplate": 1, "name": "t"}]}}"#,
+            r#"{"result": {"resources": [{"uri": "config://app", "name": "config", "mimeType": 1}]}}"#,
+            r#""file:///README.md""#,
+        ] {
+            assert_matches!(
+                ResourceList::from_json_str(json),
+                Err(DeserializationError::UnexpectedType(_)),
+                "{json}"
+            );
+        }
+        for json in [
+            r#"{"result": {}}"#,
+            r#"{"result": {"resources": [{"name": "config"}]}}"#,
+            r#"{"result": {"resourceTemplates": [{"uriTemplate": "logs://{date}"}]}}"#,
+        ] {
+            assert_matches!(
+                ResourceList::from_json_str(json),
+                Err(DeserializationError::MissingExpectedAttribute(_)),
+                "{json}"
+            );
+        }
+        assert_matches!(
+            ResourceList::from_json_str(
+                r#"{"result": {"resourceTemplates": [{"uriTemplate": "logs://{date", "name": "logs"}]}}"#
+            ),
+            Err(DeserializationError::UnexpectedValue(_))
+        );
+    }
+
+    #[test]
+    fn test_resource_read() {
+        let read = ResourceRead::from_json_str(
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "resources/read", "params": {"uri": "file:///project/src/main.rs"}}"#,
+        )
+        .expect("resources/read request should parse");
+        assert_eq!(read.uri(), "file:///project/src/main.rs");
+
+        assert_matches!(
+            ResourceRead::from_json_str(r#"{"params": {"name": "file"}}"#),
+            Err(DeserializationError::MissingExpectedAttribute(_))
+        );
+        assert_matches!(
+            ResourceRead::from_json_str(r#"{"params": {"uri": 1}}"#),
+            Err(DeserializationError::UnexpectedType(_))
+        );
+    }
+
+    #[test]
+    fn test_prompt_list() {
+        let prompts = PromptList::from_json_str(
+            r#"{"jsonrpc": "2.0", "id": 1, "result": {"prompts": [
+                {"name": "code_review", "title": "Request Code Review", "description": "Asks the LLM to review code",
+                 "arguments": [
+                    {"name": "code", "description": "The code to review", "required": true},
+                    {"name": "language", "title": "Language"}
+                 ]},
+                {"name": "greeting"}
+            ]}}"#,
+        )
+        .expect("prompts/list response should parse");
+        let prompts = prompts.prompts().collect::<Vec<_>>();
+        assert_matches!(prompts.as_slice(), [review, greeting] => {
+            assert_eq!(review.name(), "code_review");
+            assert_eq!(review.title(), Some("Request Code Review"));
+            assert_eq!(review.description(), Some("Asks the LLM to review code"));
+            let args = review.arguments().collect::<Vec<_>>();
+            assert_matches!(args.as_slice(), [code, language] => {
+                assert_eq!(code.name(), "code");
+                assert!(code.is_required());
+                assert_eq!(code.description(), Some("The code to review"));
+                assert_eq!(language.name(), "language");
+                assert!(!language.is_required());
+                assert_eq!(language.title(), Some("Language"));
+            });
+            assert_eq!(greeting.name(), "greeting");
+            assert_eq!(greeting.arguments().count(), 0);
+        });
+
+        let prompts = PromptList::from_json_str(r#"[{"name": "a"}, {"name": "b"}]"#)
+            .expect("array of prompts should parse");
+        assert!(prompts.prompt("b").is_some());
+        assert!(prompts.prompt("c").is_none());
+    }
+
+    #[test]
+    fn test_malformed_prompt_list_error() {
+        assert_matches!(
+            PromptList::from_json_str(r#"{"result": {"tools": []}}"#),
+            Err(DeserializationError::MissingExpectedAttribute(_))
+        );
+        assert_matches!(
+            PromptList::from_json_str(
+                r#"[{"name": "a", "arguments": [{"name": "x", "required": "yes"}]}]"#
+            ),
+            Err(DeserializationError::UnexpectedType(_))
+        );
+        assert_matches!(
+            PromptList::from_json_str(
+                r#"[{"name": "a", "arguments": [{"name": "x"}, {"name": "x"}]}]"#
+            ),
+            Err(DeserializationError::UnexpectedValue(_))
+        );
+    }
+
+    #[test]
+    fn test_prompt_get() {
+        let prompts = PromptList::from_json_str(
+            r#"[{"name": "code_review", "arguments": [
+                {"name": "code", "required": true},
+                {"name": "language"}
+            ]}]"#,
+        )
+        .expect("prompts should parse");
+
+        let get = PromptGet::from_json_str(
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "prompts/get", "params": {"name": "code_review", "arguments": {"code": "fn main() {}"}}}"#,
+        )
+        .expect("prompts/get request should parse");
+        assert_eq!(get.name(), "code_review");
+        let input = prompts.validate_get(&get).expect("request should validate");
+        assert_eq!(input.name(), "code_review");
+        assert!(input.get_arg("code").is_some());
+        assert!(input.get_arg("language").is_none());
+
+        let get = PromptGet::from_json_str(r#"{"params": {"name": "code_review"}}"#)
+            .expect("prompts/get request without arguments should parse");
+        assert_matches!(
+            prompts.validate_get(&get),
+            Err(ValidationError::MissingRequiredProperty(_))
+        );
+        let get = PromptGet::from_json_str(
+            r#"{"params": {"name": "code_review", "arguments": {"code": 1}}}"#,
+        )
+        .expect("prompts/get request should parse");
+        assert_matches!(prompts.validate_get(&get), Err(_));
+        let get = PromptGet::from_json_str(r#"{"params": {"name": "summarize"}}"#)
+            .expect("prompts/get request should parse");
+        assert_matches!(
+            prompts.validate_get(&get),
+            Err(ValidationError::PromptNotFound(_))
+        );
+
+        assert_matches!(
+            PromptGet::from_json_str(r#"{"params": {"arguments": {}}}"#),
+            Err(DeserializationError::MissingExpectedAttribute(_))
+        );
+        assert_matches!(
+            PromptGet::from_json_str(r#"{"params": {"name": "code_review", "arguments": []}}"#),
+            Err(DeserializationError::UnexpectedType(_))
+        );
+    }
 }
@@ -17,7 +17,11 @@
 //! This module contains definitions for errors that can occur when deserializing a MCP server's
 //! tool descriptions.
 
-use super::parser::{json_value::LocatedValue, loc::Loc};
+use super::data::Number;
+use super::parser::{
+    json_value::{to_pointer, LocatedValue},
+    loc::Loc,
+};
 use miette::Diagnostic;
 use smol_str::SmolStr;
 use std::path::PathBuf;
@@ -50,6 +54,11 @@ pub enum DeserializationError {
     #[diagnostic(transparent)]
     UnexpectedValue(LocationFound),
 
+    /// Deserializer found a property defined with different types by the schemas of an `allOf`
+    #[error("Conflicting property definitions while deserializing {}.", .0.content_type)]
+    #[diagnostic(transparent)]
+    ConflictingPropertyTypes(LocationFound),
+
     /// Deserializer could not open the provided JSON file
     #[error("Error reading {}: {}", .0.file_name.display(), .0.error)]
     #[diagnostic()]
@@ -115,6 +124,20 @@ impl DeserializationError {
         })
     }
 
+    /// Construct a new `DeserizlizerError` signaling that the schemas of an `allOf` define a property with different types
+    pub(crate) fn conflicting_property_types(json_value: &LocatedValue, property: &str) -> Self {
+        let loc = json_value.as_loc().clone();
+        Self::ConflictingPropertyTypes(LocationFound {
+            src: loc,
+            label: "Conflicting definition".to_string(),
+            msg: format!(
+                "Ensure every schema in `allOf` defining `{property}` gives it the same type."
+            ),
+            code: "deserialization::conflicting_property_types".to_string(),
+            content_type: ContentType::PropertyType,
+        })
+    }
+
     /// Construct a new `DeserizlizerError` signaling that input JSON file could not be read
     pub(crate) fn read_error(file_name: PathBuf, error: String) -> Self {
         Self::ReadError(ReadError { file_name, error })
@@ -212,6 +235,12 @@ pub(crate) enum ContentType {
     PropertyType,
     ToolInputRequest,
     ToolOutputResponse,
+    ResourceList,
+    ResourceDescription,
+    ResourceReadRequest,
+    PromptList,
+    PromptDescription,
+    PromptGetRequest,
 }
 
 impl std::fmt::Display for ContentType {
@@ -224,6 +253,15 @@ impl std::fmt::Display for ContentType {
             Self::PropertyType => write!(f, "JSON Schema Property Type"),
             Self::ToolInputRequest => write!(f, "MCP `tools/call` JSON request"),
             Self::ToolOutputResponse => write!(f, "MCP `tools/call` JSON response"),
+            Self::ResourceList => write!(
+                f,
+                "MCP `resources/list` or `resources/templates/list` JSON response"
+            ),
+            Self::ResourceDescription => write!(f, "MCP Resource Description"),
+            Self::ResourceReadRequest => write!(f, "MCP `resources/read` JSON request"),
+            Self::PromptList => write!(f, "MCP `prompts/list` JSON response"),
+            Self::PromptDescription => write!(f, "MCP Prompt Description"),
+            Self::PromptGetRequest => write!(f, "MCP `prompts/get` JSON request"),
         }
     }
 }
@@ -266,6 +304,14 @@ pub enum ValidationError {
     )]
     ToolNotFound(ToolNotFoundError),
 
+    /// The requested prompt was not found in the prompt list
+    #[error(transparent)]
+    #[diagnostic(
+        code = "validation_error::prompt_not_found",
+        help = "Cannot validate a `prompts/get` request against a prompt not found in the Prompt List."
+    )]
+    PromptNotFound(PromptNotFoundError),
+
     /// A required property is missing from the input object
     #[error(transparent)]
     #[diagnostic(
@@ -346,6 +392,22 @@ pub enum ValidationError {
     )]
     InvalidValueForUnionType,
 
+    /// A value matches more than one variant of a `oneOf` union type
+    #[error("Value matches more than one type of `oneOf` union type")]
+    #[diagnostic(
+        code = "validation_error::ambiguous_value_for_one_of_type",
+        help = "Ensure the input value matches exactly one of the types within the `oneOf` union type"
+    )]
+    AmbiguousValueForOneOfType,
+
+    /// The discriminator property of a value does not name a variant of a union type
+    #[error("Unknown discriminator value: {}", .0.literal)]
+    #[diagnostic(
+        code = "validation_error::unknown_discriminator_value",
+        help = "Ensure the discriminator property of the input value names one of the types within the union type"
+    )]
+    UnknownDiscriminatorValue(InvalidLiteralError),
+
     /// An object contains a property not defined in its schema
     #[error("Unexpected property on object: {}", .0.name)]
     #[diagnostic(
@@ -369,6 +431,94 @@ pub enum ValidationError {
         help = "Ensure input value matches expected type in MCP tool description"
     )]
     InvalidValueForType,
+
+    /// A number is less than the `minimum` of its type
+    #[error("Value {} is less than the minimum {}", .0.value, .0.bound)]
+    #[diagnostic(
+        code = "validation_error::value_below_minimum",
+        help = "Ensure numeric value is greater than or equal to `minimum`"
+    )]
+    ValueBelowMinimum(NumericBoundError),
+
+    /// A number is greater than the `maximum` of its type
+    #[error("Value {} is greater than the maximum {}", .0.value, .0.bound)]
+    #[diagnostic(
+        code = "validation_error::value_above_maximum",
+        help = "Ensure numeric value is less than or equal to `maximum`"
+    )]
+    ValueAboveMaximum(NumericBoundError),
+
+    /// A number is not greater than the `exclusiveMinimum` of its type
+    #[error("Value {} is not greater than the exclusive minimum {}", .0.value, .0.bound)]
+    #[diagnostic(
+        code = "validation_error::value_not_above_exclusive_minimum",
+        help = "Ensure numeric value is strictly greater than `exclusiveMinimum`"
+    )]
+    ValueNotAboveExclusiveMinimum(NumericBoundError),
+
+    /// A number is not less than the `exclusiveMaximum` of its type
+    #[error("Value {} is not less than the exclusive maximum {}", .0.value, .0.bound)]
+    #[diagnostic(
+        code = "validation_error::value_not_below_exclusive_maximum",
+        help = "Ensure numeric value is strictly less than `exclusiveMaximum`"
+    )]
+    ValueNotBelowExclusiveMaximum(NumericBoundError),
+
+    /// A number is not a multiple of the `multipleOf` of its type
+    #[error("Value {} is not a multiple of {}", .0.value, .0.bound)]
+    #[diagnostic(
+        code = "validation_error::value_not_multiple_of",
+        help = "Ensure numeric value is a multiple of `multipleOf`"
+    )]
+    ValueNotMultipleOf(NumericBoundError),
+
+    /// A string has fewer characters than the `minLength` of its type
+    #[error("String of length {} is shorter than the minimum length {}", .0.length, .0.bound)]
+    #[diagnostic(
+        code = "validation_error::string_too_short",
+        help = "Ensure string value has at least `minLength` characters"
+    )]
+    StringTooShort(LengthBoundError),
+
+    /// A string has more characters than the `maxLength` of its type
+    #[error("String of length {} is longer than the maximum length {}", .0.length, .0.bound)]
+    #[diagnostic(
+        code = "validation_error::string_too_long",
+        help = "Ensure string value has at most `maxLength` characters"
+    )]
+    StringTooLong(LengthBoundError),
+
+    /// A string does not match the `pattern` of its type
+    #[error("String {} does not match pattern {}", .0.literal, .0.pattern)]
+    #[diagnostic(
+        code = "validation_error::pattern_mismatch",
+        help = "Ensure string value matches the regular expression `pattern`"
+    )]
+    PatternMismatch(PatternMismatchError),
+
+    /// An array has fewer elements than the `minItems` of its type
+    #[error("Array of length {} has fewer than the minimum {} items", .0.length, .0.bound)]
+    #[diagnostic(
+        code = "validation_error::too_few_items",
+        help = "Ensure array value has at least `minItems` elements"
+    )]
+    TooFewItems(LengthBoundError),
+
+    /// An array has more elements than the `maxItems` of its type
+    #[error("Array of length {} has more than the maximum {} items", .0.length, .0.bound)]
+    #[diagnostic(
+        code = "validation_error::too_many_items",
+        help = "Ensure array value has at most `maxItems` elements"
+    )]
+    TooManyItems(LengthBoundError),
+
+    /// An array whose type requires `uniqueItems` contains duplicate elements
+    #[error("Array contains duplicate items")]
+    #[diagnostic(
+        code = "validation_error::duplicate_items",
+        help = "Ensure array value does not contain duplicate elements"
+    )]
+    DuplicateItems,
 }
 
 impl ValidationError {
@@ -383,6 +533,10 @@ impl ValidationError {
         Self::ToolNotFound(ToolNotFoundError { tool_name })
     }
 
+    pub(crate) fn prompt_not_found(prompt_name: SmolStr) -> Self {
+        Self::PromptNotFound(PromptNotFoundError { prompt_name })
+    }
+
     pub(crate) fn missing_required_property(property_name: SmolStr) -> Self {
         Self::MissingRequiredProperty(MissingRequiredPropertyError { property_name })
     }
@@ -429,6 +583,12 @@ impl ValidationError {
         })
     }
 
+    pub(crate) fn unknown_discriminator_value(literal: &str) -> Self {
+        Self::UnknownDiscriminatorValue(InvalidLiteralError {
+            literal: literal.to_string(),
+        })
+    }
+
     pub(crate) fn wrong_tuple_size(expected: usize, found: usize) -> Self {
         Self::WrongTupleSize(WrongTupleSizeError { expected, found })
     }
@@ -444,6 +604,207 @@ impl ValidationError {
             name: name.to_string(),
         })
     }
+
+    pub(crate) fn value_below_minimum(value: &Number, bound: &Number) -> Self {
+        Self::ValueBelowMinimum(NumericBoundError::new(value, bound))
+    }
+
+    pub(crate) fn value_above_maximum(value: &Number, bound: &Number) -> Self {
+        Self::ValueAboveMaximum(NumericBoundError::new(value, bound))
+    }
+
+    pub(crate) fn value_not_above_exclusive_minimum(value: &Number, bound: &Number) -> Self {
+        Self::ValueNotAboveExclusiveMinimum(NumericBoundError::new(value, bound))
+    }
+
+    pub(crate) fn value_not_below_exclusive_maximum(value: &Number, bound: &Number) -> Self {
+        Self::ValueNotBelowExclusiveMaximum(NumericBoundError::new(value, bound))
+    }
+
+    pub(crate) fn value_not_multiple_of(value: &Number, bound: &Number) -> Self {
+        Self::ValueNotMultipleOf(NumericBoundError::new(value, bound))
+    }
+
+    pub(crate) fn string_too_short(length: usize, bound: usize) -> Self {
+        Self::StringTooShort(LengthBoundError { length, bound })
+    }
+
+    pub(crate) fn string_too_long(length: usize, bound: usize) -> Self {
+        Self::StringTooLong(LengthBoundError { length, bound })
+    }
+
+    pub(crate) fn pattern_mismatch(literal: &str, pattern: &str) -> Self {
+        Self::PatternMismatch(PatternMismatchError {
+            literal: literal.to_string(),
+            pattern: pattern.to_string(),
+        })
+    }
+
+    pub(crate) fn too_few_items(length: usize, bound: usize) -> Self {
+        Self::TooFewItems(LengthBoundError { length, bound })
+    }
+
+    pub(crate) fn too_many_items(length: usize, bound: usize) -> Self {
+        Self::TooManyItems(LengthBoundError { length, bound })
+    }
+}
+
+/// A `ValidationError` together with where in an MCP tool input or output it was found
+#[derive(Debug, Error)]
+#[error("{}", display_located(.path, .error))]
+pub struct ValidationDiagnostic {
+    path: String,
+    error: ValidationError,
+    loc: Option<Loc>,
+}
+
+fn display_located(path: &str, error: &ValidationError) -> String {
+    if path.is_empty() {
+        error.to_string()
+    } else {
+        format!("{path}: {error}")
+    }
+}
+
+impl ValidationDiagnostic {
+    pub(crate) fn new<T: AsRef<str>>(path: &[T], error: ValidationError, loc: Option<Loc>) -> Self {
+        Self {
+            path: to_pointer(path),
+            error,
+            loc,
+        }
+    }
+
+    /// The JSON Pointer to the offending value, relative to the tool's arguments (or results),
+    /// e.g., `/filters/2/date`. The empty string refers to the arguments as a whole.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The `ValidationError` found
+    pub fn error(&self) -> &ValidationError {
+        &self.error
+    }
+
+    /// Consume this diagnostic, returning the `ValidationError` found
+    pub fn into_error(self) -> ValidationError {
+        self.error
+    }
+}
+
+impl Diagnostic for ValidationDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        self.error.code()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        self.error.help()
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        self.loc.as_ref().map(|loc| loc as &dyn miette::SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        self.loc.as_ref().map(|loc| {
+            Box::new(std::iter::once(miette::LabeledSpan::new(
+                Some(self.error.to_string()),
+                loc.start(),
+                loc.end() - loc.start(),
+            ))) as Box<dyn Iterator<Item = miette::LabeledSpan>>
+        })
+    }
+}
+
+/// Every `ValidationError` found while validating an MCP tool input or output
+#[derive(Debug, Error)]
+#[error("Found {} validation error(s)", .diagnostics.len())]
+pub struct ValidationErrors {
+    diagnostics: Vec<ValidationDiagnostic>,
+}
+
+impl ValidationErrors {
+    pub(crate) fn new(diagnostics: Vec<ValidationDiagnostic>) -> Self {
+        Self { diagnostics }
+    }
+
+    /// The errors found, in the order they were found
+    pub fn diagnostics(&self) -> &[ValidationDiagnostic] {
+        &self.diagnostics
+    }
+
+    /// Consume this collection, returning the errors found
+    pub fn into_diagnostics(self) -> Vec<ValidationDiagnostic> {
+        self.diagnostics
+    }
+}
+
+impl From<ValidationError> for ValidationErrors {
+    fn from(error: ValidationError) -> Self {
+        Self::new(vec![ValidationDiagnostic::new::<&str>(&[], error, None)])
+    }
+}
+
+impl Diagnostic for ValidationErrors {
+    fn code<'a>(&'a self) -> Option<Box<dyn std::fmt::Display + 'a>> {
+        Some(Box::new("validation_error::validation_errors"))
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        Some(Box::new(
+            self.diagnostics.iter().map(|d| d as &dyn Diagnostic),
+        ))
+    }
+}
+
+/// The type of errors that may be encountered when parsing an RFC 6570 URI Template
+#[derive(Error, Debug, Diagnostic, Clone, PartialEq, Eq)]
+pub enum UriTemplateError {
+    /// An expression of the URI Template is not closed by a `}`
+    #[error("Unclosed expression in URI template `{0}`.")]
+    #[diagnostic(
+        code(uri_template_error::unclosed_expression),
+        help("Ensure every `{{` of the URI template is closed by a `}}`.")
+    )]
+    UnclosedExpression(SmolStr),
+    /// The URI Template contains a `}` outside of an expression
+    #[error("Unexpected `}}` in URI template `{0}`.")]
+    #[diagnostic(
+        code(uri_template_error::unexpected_closing_brace),
+        help("Ensure every `}}` of the URI template closes an expression.")
+    )]
+    UnexpectedClosingBrace(SmolStr),
+    /// An expression of the URI Template uses an operator reserved for future extensions
+    #[error("Unsupported operator `{operator}` in URI template `{template}`.")]
+    #[diagnostic(
+        code(uri_template_error::unsupported_operator),
+        help("Supported operators are `+`, `#`, `.`, `/`, `;`, `?` and `&`.")
+    )]
+    UnsupportedOperator {
+        /// The URI Template
+        template: SmolStr,
+        /// The unsupported operator
+        operator: char,
+    },
+    /// An expression of the URI Template contains a malformed variable specification
+    #[error("Malformed variable `{variable}` in URI template `{template}`.")]
+    #[diagnostic(
+        code(uri_template_error::malformed_variable),
+        help("Variable names are non-empty sequences of letters, digits, `_`, `.` and percent-encoded characters, optionally followed by an explode modifier `*` or a prefix modifier `:N` (with N between 1 and 9999).")
+    )]
+    MalformedVariable {
+        /// The URI Template
+        template: SmolStr,
+        /// The malformed variable specification
+        variable: SmolStr,
+    },
+    /// The URI Template is too large to be matched against URIs
+    #[error("URI template `{0}` is too large.")]
+    #[diagnostic(
+        code(uri_template_error::too_large),
+        help("Consider splitting the URI template into several smaller templates.")
+    )]
+    TooLarge(SmolStr),
 }
 
 #[derive(Debug, Error)]
@@ -459,6 +820,12 @@ pub(crate) struct ToolNotFoundError {
     tool_name: SmolStr,
 }
 
+#[derive(Debug, Error)]
+#[error("Validation failed because no prompt named {prompt_name} was found.")]
+pub(crate) struct PromptNotFoundError {
+    prompt_name: SmolStr,
+}
+
 #[derive(Debug, Error)]
 #[error("Validation failed because required property {property_name} is missing.")]
 pub(crate) struct MissingRequiredPropertyError {
@@ -485,3 +852,30 @@ pub(crate) struct UnexpectedPropertyError {
 pub(crate) struct UnexpectedTypeNameError {
     name: String,
 }
+
+#[derive(Debug)]
+pub(crate) struct NumericBoundError {
+    value: String,
+    bound: String,
+}
+
+impl NumericBoundError {
+    fn new(value: &Number, bound: &Number) -> Self {
+        Self {
+            value: value.as_str().to_string(),
+            bound: bound.as_str().to_string(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct LengthBoundError {
+    length: usize,
+    bound: usize,
+}
+
+#[derive(Debug)]
+pub(crate) struct PatternMismatchError {
+    literal: String,
+    pattern: String,
+}
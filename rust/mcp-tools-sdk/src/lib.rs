@@ -23,6 +23,13 @@
 //!
 //! This library also includes a `ServerDescription` struct that represents a collection of MCP tool descriptions
 //! (i.e., the output of `list_tools` from an MCP Server).
+//!
+//! This library also includes a `ResourceList` struct that represents the resources and resource templates
+//! provided by an MCP Server (i.e., the output of `resources/list` and `resources/templates/list`), along with
+//! RFC 6570 URI Templates that `resources/read` request URIs can be matched against.
+//!
+//! This library also includes a `PromptList` struct that represents the prompts provided by an MCP Server
+//! (i.e., the output of `prompts/list`), against which the arguments of `prompts/get` requests can be validated.
 
 #![deny(
     missing_docs,
@@ -42,4 +49,6 @@ pub mod description;
 mod deserializer;
 pub mod err;
 pub mod parser;
+mod serializer;
+pub mod uri_template;
 mod validation;
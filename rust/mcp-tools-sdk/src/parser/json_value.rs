@@ -15,7 +15,7 @@
  */
 
 use linked_hash_map::LinkedHashMap;
-use smol_str::SmolStr;
+use smol_str::{SmolStr, ToSmolStr};
 
 use std::borrow::Borrow;
 use std::hash::{Hash, Hasher};
@@ -270,7 +270,6 @@ impl LocatedValue {
     }
 
     /// Retrieve the kind of the `LocatedValue`
-    #[expect(dead_code, reason = "Added for completeness.")]
     pub(crate) fn as_kind(&self) -> &ValueKind {
         &self.kind
     }
@@ -394,11 +393,104 @@ impl LocatedValue {
         }
     }
 
+    /// Returns `Some(key_value_map)` where `key_value_map` is a mutable mapping from [`LocatedString`]s to
+    /// [`LocatedValue`]s if this [`LocatedValue`] is of kind Object. Otherwise, returns `None`
+    pub(crate) fn get_object_mut(
+        &mut self,
+    ) -> Option<&mut LinkedHashMap<LocatedString, LocatedValue>> {
+        match &mut self.kind {
+            ValueKind::Object(items) => Some(items),
+            _ => None,
+        }
+    }
+
     /// returns Some(value) if this `LocatedValue` is of kind Object and the
     /// (key, value) mapping appears within the Object
     pub(crate) fn get(&self, key: impl AsRef<str>) -> Option<&LocatedValue> {
         self.get_object().and_then(|obj| obj.get(key.as_ref()))
     }
+
+    /// Returns `Some(value)` where value is the `LocatedValue` found by following the reference
+    /// tokens of a JSON Pointer (see [`parse_pointer`]): object keys or array indices.
+    pub(crate) fn get_path<T: AsRef<str>>(&self, path: &[T]) -> Option<&LocatedValue> {
+        path.iter().try_fold(self, |value, token| {
+            let token = token.as_ref();
+            match &value.kind {
+                ValueKind::Object(items) => items.get(token),
+                ValueKind::Array(items) => {
+                    // Array indices do not have leading zeros (RFC 6901, Section 4)
+                    if token.len() > 1 && token.starts_with('0') {
+                        None
+                    } else {
+                        token.parse::<usize>().ok().and_then(|i| items.get(i))
+                    }
+                }
+                _ => None,
+            }
+        })
+    }
+
+    /// Returns the path to (see [`LocatedValue::get_path`]) and value of the first `LocatedValue`
+    /// satisfying `pred` in a pre-order traversal of this `LocatedValue`.
+    pub(crate) fn find_path(
+        &self,
+        pred: &impl Fn(&LocatedValue) -> bool,
+    ) -> Option<(Vec<SmolStr>, &LocatedValue)> {
+        if pred(self) {
+            return Some((Vec::new(), self));
+        }
+        let (token, (mut path, value)) = match &self.kind {
+            ValueKind::Object(items) => items.iter().find_map(|(key, value)| {
+                value.find_path(pred).map(|found| (key.to_smolstr(), found))
+            }),
+            ValueKind::Array(items) => items
+                .iter()
+                .enumerate()
+                .find_map(|(i, value)| value.find_path(pred).map(|found| (i.to_smolstr(), found))),
+            _ => None,
+        }?;
+        path.insert(0, token);
+        Some((path, value))
+    }
+}
+
+/// Split a JSON Pointer (RFC 6901), e.g., `/$defs/a~1b`, into its unescaped
+/// reference tokens, e.g., `["$defs", "a/b"]`. Returns `None` if the pointer is malformed.
+pub(crate) fn parse_pointer(pointer: &str) -> Option<Vec<SmolStr>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    pointer
+        .strip_prefix('/')?
+        .split('/')
+        .map(|token| {
+            let mut unescaped = String::with_capacity(token.len());
+            let mut chars = token.chars();
+            while let Some(c) = chars.next() {
+                if c != '~' {
+                    unescaped.push(c);
+                    continue;
+                }
+                match chars.next() {
+                    Some('0') => unescaped.push('~'),
+                    Some('1') => unescaped.push('/'),
+                    _ => return None,
+                }
+            }
+            Some(SmolStr::from(unescaped))
+        })
+        .collect()
+}
+
+/// Join reference tokens, e.g., `["$defs", "a/b"]`, into a JSON Pointer (RFC 6901),
+/// e.g., `/$defs/a~1b`. The inverse of [`parse_pointer`].
+pub(crate) fn to_pointer<T: AsRef<str>>(tokens: &[T]) -> String {
+    let mut pointer = String::new();
+    for token in tokens {
+        pointer.push('/');
+        pointer.push_str(&token.as_ref().replace('~', "~0").replace('/', "~1"));
+    }
+    pointer
 }
 
 #[cfg(test)]
@@ -689,4 +781,46 @@ mod tests {
             Some(..)
         );
     }
+
+    #[test]
+    fn test_parse_pointer() {
+        assert_matches!(parse_pointer(""), Some(tokens) if tokens.is_empty());
+        assert_matches!(parse_pointer("/"), Some(tokens) if tokens == vec![""]);
+        assert_matches!(
+            parse_pointer("/$defs/a~1b/c~0d~01"),
+            Some(tokens) if tokens == vec!["$defs", "a/b", "c~d~1"]
+        );
+        assert_matches!(parse_pointer("$defs"), None);
+        assert_matches!(parse_pointer("/a~2"), None);
+        assert_matches!(parse_pointer("/a~"), None);
+
+        assert_eq!(to_pointer::<&str>(&[]), "");
+        assert_eq!(
+            to_pointer(&["$defs", "a/b", "c~d~1"]),
+            "/$defs/a~1b/c~0d~01"
+        );
+    }
+
+    #[test]
+    fn test_get_path_and_find_path() {
+        let mut parser = crate::parser::json_parser::JsonParser::new(
+            r#"{"a": {"b": [true, {"$anchor": "x"}]}, "10": null}"#,
+        );
+        let value = parser.get_value().unwrap();
+        assert_matches!(value.get_path::<&str>(&[]), Some(v) if v.is_object());
+        assert_matches!(value.get_path(&["a", "b", "0"]), Some(v) if v.is_bool());
+        assert_matches!(value.get_path(&["10"]), Some(v) if v.is_null());
+        assert_matches!(value.get_path(&["a", "b", "01"]), None);
+        assert_matches!(value.get_path(&["a", "b", "2"]), None);
+        assert_matches!(value.get_path(&["a", "c"]), None);
+
+        let found = value.find_path(&|v| {
+            v.get_object()
+                .and_then(|obj| obj.get("$anchor"))
+                .and_then(LocatedValue::get_str)
+                == Some("x")
+        });
+        assert_matches!(found, Some((path, _)) if path == vec!["a", "b", "1"]);
+        assert_matches!(value.find_path(&|v| v.is_number()), None);
+    }
 }
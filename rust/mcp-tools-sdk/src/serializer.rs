@@ -0,0 +1,990 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Serialization of tool descriptions back into the JSON Schemas accepted by the deserializer.
+//!
+//! The JSON produced for a value parses back into an equal value.
+
+use super::data::Output;
+use super::description::{
+    DatetimeFormat, Discriminator, IpAddrFormat, Parameters, Property, PropertyConstraints,
+    PropertyType, PropertyTypeDef, PropertyTypeDefs, ServerDescription, ToolAnnotations,
+    ToolDescription, UnionKind,
+};
+use super::parser::json_parser::JsonParser;
+use super::parser::json_value::{to_pointer, LocatedValue, ValueKind};
+
+use smol_str::{SmolStr, ToSmolStr};
+use std::fmt::{self, Display, Write};
+
+/// A JSON value to be written. Unlike `data::Value`, objects keep the order of their attributes.
+#[derive(Debug, Clone)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(SmolStr),
+    String(SmolStr),
+    Array(Vec<Json>),
+    Object(Vec<(SmolStr, Json)>),
+}
+
+impl Json {
+    fn object() -> Self {
+        Self::Object(Vec::new())
+    }
+
+    fn string(s: impl Into<SmolStr>) -> Self {
+        Self::String(s.into())
+    }
+
+    /// Add an attribute to this value if it is an object.
+    fn insert(&mut self, key: &str, value: Json) {
+        if let Self::Object(attrs) = self {
+            attrs.push((key.into(), value));
+        }
+    }
+
+    fn with(mut self, key: &str, value: Json) -> Self {
+        self.insert(key, value);
+        self
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("null"),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Number(num) => write!(f, "{num}"),
+            Self::String(s) => write_json_string(f, s),
+            Self::Array(vals) => {
+                f.write_char('[')?;
+                for (i, val) in vals.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{val}")?;
+                }
+                f.write_char(']')
+            }
+            Self::Object(attrs) => {
+                f.write_char('{')?;
+                for (i, (key, val)) in attrs.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_json_string(f, key)?;
+                    write!(f, ":{val}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_json_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Serialize an `Output` as an MCP `tools/call` response. Results are ordered by name. The
+/// `content` of the response is kept, except for the text of its text blocks holding a JSON
+/// object, which repeat the structured content and are replaced by the serialized results.
+/// Without a `content`, the structured content is repeated in a single text block.
+pub(crate) fn output_to_json(output: &Output) -> Json {
+    let mut results = output.results.iter().collect::<Vec<_>>();
+    results.sort_by_key(|(name, _)| *name);
+    let structured = Json::Object(
+        results
+            .into_iter()
+            .map(|(name, value)| (name.clone(), located_value_to_json(value)))
+            .collect(),
+    );
+    let text = Json::string(structured.to_string());
+    let content = match &output.content {
+        Some(content) => match content.get_array() {
+            Some(blocks) => Json::Array(
+                blocks
+                    .iter()
+                    .map(|block| match located_value_to_json(block) {
+                        Json::Object(attrs) if repeats_structured_content(block) => Json::Object(
+                            attrs
+                                .into_iter()
+                                .map(|(key, value)| match key.as_str() {
+                                    "text" => (key, text.clone()),
+                                    _ => (key, value),
+                                })
+                                .collect(),
+                        ),
+                        block => block,
+                    })
+                    .collect(),
+            ),
+            None => located_value_to_json(content),
+        },
+        None => Json::Array(vec![Json::object()
+            .with("type", Json::string("text"))
+            .with("text", text)]),
+    };
+    Json::object().with(
+        "result",
+        Json::object()
+            .with("content", content)
+            .with("structuredContent", structured),
+    )
+}
+
+/// Returns `true` if the content `block` is a text block holding a JSON object
+fn repeats_structured_content(block: &LocatedValue) -> bool {
+    block.get("type").and_then(LocatedValue::get_str) == Some("text")
+        && block
+            .get("text")
+            .and_then(LocatedValue::get_str)
+            .is_some_and(|text| {
+                JsonParser::new(text)
+                    .get_value()
+                    .is_ok_and(|v| v.is_object())
+            })
+}
+
+fn located_value_to_json(value: &LocatedValue) -> Json {
+    match value.as_kind() {
+        ValueKind::Null => Json::Null,
+        ValueKind::Bool(b) => Json::Bool(*b),
+        #[expect(
+            clippy::unwrap_used,
+            reason = "`get_numeric_str` returns the literal of values of kind `Number`."
+        )]
+        ValueKind::Number => Json::Number(value.get_numeric_str().unwrap().into()),
+        ValueKind::String(s) => Json::String(s.clone()),
+        ValueKind::Array(items) => Json::Array(items.iter().map(located_value_to_json).collect()),
+        ValueKind::Object(attrs) => Json::Object(
+            attrs
+                .iter()
+                .map(|(key, value)| (key.to_smolstr(), located_value_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Serialize a `ServerDescription` as an MCP `tools/list` response.
+/// Tools and type definitions are ordered by name.
+pub(crate) fn server_description_to_json(server: &ServerDescription) -> Json {
+    let mut tools = server.tools.values().collect::<Vec<_>>();
+    tools.sort_by(|l, r| l.name.cmp(&r.name));
+    let mut result = Json::object().with(
+        "tools",
+        Json::Array(tools.into_iter().map(tool_description_to_json).collect()),
+    );
+    insert_type_defs(&mut result, &server.type_defs);
+    Json::object().with("result", result)
+}
+
+/// Serialize a `ToolDescription` as an MCP tool description.
+pub(crate) fn tool_description_to_json(tool: &ToolDescription) -> Json {
+    let mut json = Json::object().with("name", Json::string(tool.name.clone()));
+    if let Some(title) = &tool.title {
+        json.insert("title", Json::string(title));
+    }
+    if let Some(description) = &tool.description {
+        json.insert("description", Json::string(description));
+    }
+    json.insert("inputSchema", parameters_to_json(&tool.inputs));
+    if tool.outputs.properties.len() + tool.outputs.type_defs.type_defs.len() > 0 {
+        json.insert("outputSchema", parameters_to_json(&tool.outputs));
+    }
+    if !tool.annotations.is_empty() {
+        json.insert("annotations", tool_annotations_to_json(&tool.annotations));
+    }
+    insert_type_defs(&mut json, &tool.type_defs);
+    json
+}
+
+/// Serialize `ToolAnnotations` as the `annotations` attribute of an MCP Tool Description.
+fn tool_annotations_to_json(annotations: &ToolAnnotations) -> Json {
+    let mut json = Json::object();
+    if let Some(title) = &annotations.title {
+        json.insert("title", Json::string(title));
+    }
+    let hints = [
+        ("readOnlyHint", annotations.read_only_hint),
+        ("destructiveHint", annotations.destructive_hint),
+        ("idempotentHint", annotations.idempotent_hint),
+        ("openWorldHint", annotations.open_world_hint),
+    ];
+    for (name, hint) in hints {
+        if let Some(hint) = hint {
+            json.insert(name, Json::Bool(hint));
+        }
+    }
+    json
+}
+
+/// Serialize `Parameters` as the JSON Schema of an object.
+pub(crate) fn parameters_to_json(params: &Parameters) -> Json {
+    let mut json = Json::object().with("type", Json::string("object"));
+    insert_properties(&mut json, &params.properties);
+    insert_type_defs(&mut json, &params.type_defs);
+    json
+}
+
+/// Serialize a `PropertyTypeDef` as the JSON Schema it would have in `$defs`.
+pub(crate) fn type_def_to_json(type_def: &PropertyTypeDef) -> Json {
+    with_description(
+        property_type_to_json(&type_def.prop_type),
+        type_def.description.as_deref(),
+    )
+}
+
+/// Serialize a `PropertyType` as a JSON Schema.
+pub(crate) fn property_type_to_json(ty: &PropertyType) -> Json {
+    let simple = |name: &str| Json::object().with("type", Json::string(name));
+    let string_format =
+        |format: &str| simple("string").with("format", Json::string(format.to_smolstr()));
+    match ty {
+        PropertyType::Unknown => Json::object(),
+        PropertyType::Bool => simple("boolean"),
+        PropertyType::Integer => simple("integer"),
+        PropertyType::Float => simple("float"),
+        PropertyType::Number => simple("number"),
+        PropertyType::String => simple("string"),
+        PropertyType::Decimal => string_format("decimal"),
+        PropertyType::Datetime { format } => string_format(match format {
+            DatetimeFormat::Date => "date",
+            DatetimeFormat::DateTime => "date-time",
+        }),
+        PropertyType::Duration => string_format("duration"),
+        PropertyType::IpAddr { format } => string_format(match format {
+            IpAddrFormat::Ipv4 => "ipv4",
+            IpAddrFormat::Ipv6 => "ipv6",
+        }),
+        PropertyType::Null => simple("null"),
+        PropertyType::Enum { variants } => simple("string").with(
+            "enum",
+            Json::Array(variants.iter().cloned().map(Json::String).collect()),
+        ),
+        PropertyType::IntegerEnum { variants } => simple("integer").with(
+            "enum",
+            Json::Array(
+                variants
+                    .iter()
+                    .map(|i| Json::Number(i.to_smolstr()))
+                    .collect(),
+            ),
+        ),
+        PropertyType::BoolEnum { variants } => simple("boolean").with(
+            "enum",
+            Json::Array(variants.iter().copied().map(Json::Bool).collect()),
+        ),
+        PropertyType::Array { element_ty } => {
+            simple("array").with("items", property_type_to_json(element_ty))
+        }
+        PropertyType::Tuple { types } => simple("array")
+            .with(
+                "prefixItems",
+                Json::Array(types.iter().map(property_type_to_json).collect()),
+            )
+            .with("items", Json::Bool(false)),
+        PropertyType::Union {
+            types,
+            kind,
+            discriminator,
+        } => {
+            let keyword = match kind {
+                UnionKind::AnyOf => "anyOf",
+                UnionKind::OneOf => "oneOf",
+            };
+            let mut json = Json::object().with(
+                keyword,
+                Json::Array(types.iter().map(property_type_to_json).collect()),
+            );
+            if let Some(discriminator) = discriminator {
+                json.insert("discriminator", discriminator_to_json(discriminator, types));
+            }
+            json
+        }
+        PropertyType::Object {
+            properties,
+            additional_properties,
+        } => {
+            let mut json = simple("object");
+            insert_properties(&mut json, properties);
+            if let Some(additional_properties) = additional_properties {
+                json.insert(
+                    "additionalProperties",
+                    property_type_to_json(additional_properties),
+                );
+            }
+            json
+        }
+        PropertyType::Ref { name } => Json::object().with("$ref", Json::String(ref_to(name))),
+        PropertyType::Constrained { .. } => constrained_to_json(ty),
+    }
+}
+
+/// The `$ref` to the type definition `name`.
+fn ref_to(name: &str) -> SmolStr {
+    // `$ref` fragments are percent-decoded before being resolved
+    let pointer = to_pointer(&["$defs", name])
+        .replace('%', "%25")
+        .replace('#', "%23");
+    format!("#{pointer}").into()
+}
+
+/// Serialize a discriminator. The discriminator values of `$ref`s that are not the name of the
+/// type definition they reference (nor the `const` value of its discriminator property) are
+/// given by the `mapping`.
+fn discriminator_to_json(discriminator: &Discriminator, types: &[PropertyType]) -> Json {
+    let mut json = Json::object().with(
+        "propertyName",
+        Json::string(discriminator.property_name.clone()),
+    );
+    let mapping = discriminator
+        .values
+        .iter()
+        .zip(types)
+        .filter_map(|(value, ty)| match ty {
+            PropertyType::Ref { name } if name != value => {
+                Some((value.clone(), Json::String(ref_to(name))))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if !mapping.is_empty() {
+        json.insert("mapping", Json::Object(mapping));
+    }
+    json
+}
+
+/// Serialize a [`PropertyType::Constrained`]. The outermost constraints are added to the schema
+/// of the underlying type, and any nested constraints are added to the schema as an `allOf`
+/// (innermost first) as the deserializer wraps the type in the constraints of each `allOf` schema in order.
+fn constrained_to_json(ty: &PropertyType) -> Json {
+    let mut layers = Vec::new();
+    let mut base = ty;
+    while let PropertyType::Constrained { ty, constraints } = base {
+        layers.push(constraints.as_ref());
+        base = ty;
+    }
+    let mut json = property_type_to_json(base);
+    let mut layers = layers.into_iter();
+    if let Some(outermost) = layers.next() {
+        insert_constraints(&mut json, outermost);
+    }
+    let nested = layers
+        .rev()
+        .map(|constraints| {
+            let mut json = Json::object();
+            insert_constraints(&mut json, constraints);
+            json
+        })
+        .collect::<Vec<_>>();
+    if !nested.is_empty() {
+        json.insert("allOf", Json::Array(nested));
+    }
+    json
+}
+
+fn insert_constraints(json: &mut Json, constraints: &PropertyConstraints) {
+    let numbers = [
+        ("minimum", &constraints.minimum),
+        ("maximum", &constraints.maximum),
+        ("exclusiveMinimum", &constraints.exclusive_minimum),
+        ("exclusiveMaximum", &constraints.exclusive_maximum),
+        ("multipleOf", &constraints.multiple_of),
+    ];
+    for (keyword, num) in numbers {
+        if let Some(num) = num {
+            json.insert(keyword, Json::Number(num.as_str().into()));
+        }
+    }
+    if let Some(min) = constraints.min_length {
+        json.insert("minLength", Json::Number(min.to_smolstr()));
+    }
+    if let Some(max) = constraints.max_length {
+        json.insert("maxLength", Json::Number(max.to_smolstr()));
+    }
+    if let Some(pattern) = &constraints.pattern {
+        json.insert("pattern", Json::string(pattern.as_str()));
+    }
+    if let Some(min) = constraints.min_items {
+        json.insert("minItems", Json::Number(min.to_smolstr()));
+    }
+    if let Some(max) = constraints.max_items {
+        json.insert("maxItems", Json::Number(max.to_smolstr()));
+    }
+    if constraints.unique_items {
+        json.insert("uniqueItems", Json::Bool(true));
+    }
+}
+
+fn with_description(mut json: Json, description: Option<&str>) -> Json {
+    if let Some(description) = description {
+        json.insert("description", Json::string(description));
+    }
+    json
+}
+
+fn insert_properties(json: &mut Json, properties: &[Property]) {
+    json.insert(
+        "properties",
+        Json::Object(
+            properties
+                .iter()
+                .map(|property| {
+                    let ty = property_type_to_json(&property.prop_type);
+                    (
+                        property.name.clone(),
+                        with_description(ty, property.description.as_deref()),
+                    )
+                })
+                .collect(),
+        ),
+    );
+    let required = properties
+        .iter()
+        .filter(|property| property.required)
+        .map(|property| Json::String(property.name.clone()))
+        .collect::<Vec<_>>();
+    if !required.is_empty() {
+        json.insert("required", Json::Array(required));
+    }
+}
+
+fn insert_type_defs(json: &mut Json, type_defs: &PropertyTypeDefs) {
+    if type_defs.type_defs.is_empty() {
+        return;
+    }
+    let mut defs = type_defs.type_defs.iter().collect::<Vec<_>>();
+    defs.sort_by_key(|(name, _)| *name);
+    json.insert(
+        "$defs",
+        Json::Object(
+            defs.into_iter()
+                .map(|(name, def)| (name.clone(), type_def_to_json(def)))
+                .collect(),
+        ),
+    );
+}
+
+#[cfg(test)]
+mod test {
+    #![expect(clippy::panic, clippy::unwrap_used, reason = "test assertions")]
+    use super::*;
+    use crate::data::{Number, Value};
+    use crate::description::{Pattern, UnionKind};
+    use crate::parser::json_parser::JsonParser;
+    use proptest::prelude::*;
+    use std::collections::HashMap;
+
+    fn round_trip_tool(json: &str) -> ToolDescription {
+        let tool = ToolDescription::from_json_str(json).unwrap();
+        let serialized = tool.to_json_string();
+        let reparsed = ToolDescription::from_json_str(&serialized).unwrap();
+        assert_eq!(tool, reparsed, "{serialized}");
+        // Serialization is stable
+        assert_eq!(serialized, reparsed.to_json_string());
+        tool
+    }
+
+    #[test]
+    fn test_serialize_tool_description() {
+        let tool = ToolDescription::from_json_str(
+            r#"{
+                "name": "get_weather",
+                "description": "Get the \"weather\"\n",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "city": {"type": "string", "description": "The city", "minLength": 1},
+                        "days": {"type": "integer"}
+                    },
+                    "required": ["city"]
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            tool.to_json_string(),
+            r#"{"name":"get_weather","description":"Get the \"weather\"\n","inputSchema":{"type":"object","properties":{"city":{"type":"string","minLength":1,"description":"The city"},"days":{"type":"integer"}},"required":["city"]}}"#
+        );
+    }
+
+    #[test]
+    fn test_serialize_tool_annotations() {
+        let tool = round_trip_tool(
+            r#"{
+                "name": "delete_file",
+                "annotations": {"openWorldHint": false, "destructiveHint": true, "title": "Delete"},
+                "inputSchema": {"type": "object", "properties": {}},
+                "title": "Delete File"
+            }"#,
+        );
+        assert_eq!(
+            tool.to_json_string(),
+            r#"{"name":"delete_file","title":"Delete File","inputSchema":{"type":"object","properties":{}},"annotations":{"title":"Delete","destructiveHint":true,"openWorldHint":false}}"#
+        );
+    }
+
+    #[test]
+    fn test_round_trip_tool_description() {
+        round_trip_tool(
+            r##"{
+                "name": "create_ticket",
+                "description": "Create a ticket",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "title": {"type": "string", "pattern": "^[A-Z]", "maxLength": 80},
+                        "priority": {"enum": [1, 2, 3], "description": "Priority"},
+                        "labels": {"type": "array", "items": {"type": "string"}, "uniqueItems": true, "maxItems": 4},
+                        "point": {"type": "array", "prefixItems": [{"type": "number"}, {"type": "float"}], "items": false},
+                        "due": {"type": ["string", "null"], "format": "date"},
+                        "owner": {"$ref": "#/definitions/user"},
+                        "role": {"$ref": "#/definitions/user/properties/role"},
+                        "meta": {"type": "object", "additionalProperties": {"type": "boolean", "enum": [true]}},
+                        "amount": {"type": "number", "exclusiveMinimum": 0, "allOf": [{"maximum": 1e3}, {"multipleOf": 0.5}]},
+                        "target": {
+                            "oneOf": [{"$ref": "#/$defs/team"}, {"$ref": "#/$defs/user"}, {"type": "object", "properties": {"kind": {"const": "anyone"}}}],
+                            "discriminator": {"propertyName": "kind", "mapping": {"person": "#/$defs/user"}}
+                        },
+                        "other": {"enum": ["a", 1, true, null]},
+                        "anything": {}
+                    },
+                    "required": ["title", "target"]
+                },
+                "outputSchema": {
+                    "type": "object",
+                    "properties": {"id": {"type": "string", "format": "decimal"}},
+                    "$defs": {"unused": {"type": "string", "format": "duration"}}
+                },
+                "definitions": {
+                    "user": {
+                        "type": "object",
+                        "description": "A user",
+                        "properties": {"kind": {"type": "string"}, "role": {"enum": ["admin", "member"]}}
+                    }
+                },
+                "$defs": {
+                    "team": {"type": "object", "properties": {"kind": {"const": "team"}, "ip": {"type": "string", "format": "ipv6"}}}
+                }
+            }"##,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_server_description() {
+        let json = r##"{"result": {
+            "tools": [
+                {"name": "b", "inputSchema": {"type": "object", "properties": {"x": {"$ref": "#/$defs/shared"}}}},
+                {"name": "a", "parameters": {"json": {"type": "object", "properties": {}}}}
+            ],
+            "$defs": {"shared": {"type": "integer", "description": "Shared"}}
+        }}"##;
+        let server = ServerDescription::from_json_str(json).unwrap();
+        let serialized = server.to_json_string();
+        assert_eq!(
+            serialized,
+            r##"{"result":{"tools":[{"name":"a","inputSchema":{"type":"object","properties":{}}},{"name":"b","inputSchema":{"type":"object","properties":{"x":{"$ref":"#/$defs/shared"}}}}],"$defs":{"shared":{"type":"integer","description":"Shared"}}}}"##
+        );
+        assert_eq!(
+            ServerDescription::from_json_str(&serialized).unwrap(),
+            server
+        );
+    }
+
+    #[test]
+    fn test_serialize_escapes_ref_names() {
+        let ty = PropertyType::Ref {
+            name: "a/b~c%d#e".into(),
+        };
+        assert_eq!(
+            ty.to_json_string(),
+            r##"{"$ref":"#/$defs/a~1b~0c%25d%23e"}"##
+        );
+        let tool = ToolDescription::new(
+            "tool".into(),
+            Parameters::new(
+                vec![Property::new("p".into(), true, ty, None)],
+                HashMap::new(),
+            ),
+            Parameters::new(Vec::new(), HashMap::new()),
+            HashMap::from([(
+                "a/b~c%d#e".into(),
+                PropertyTypeDef::new("a/b~c%d#e".into(), PropertyType::String, None),
+            )]),
+            None,
+        );
+        assert_eq!(
+            ToolDescription::from_json_str(&tool.to_json_string()).unwrap(),
+            tool
+        );
+    }
+
+    #[test]
+    fn test_serialize_escapes_strings() {
+        let ty = PropertyType::Enum {
+            variants: vec![
+                "\"quoted\"".into(),
+                "back\\slash".into(),
+                "\u{1}\u{7f}\t".into(),
+                "ünï".into(),
+            ],
+        };
+        assert_eq!(
+            ty.to_json_string(),
+            r#"{"type":"string","enum":["\"quoted\"","back\\slash","\u0001\u007f\t","ünï"]}"#
+        );
+    }
+
+    #[test]
+    fn test_serialize_output() {
+        let mut output = Output::from_json_str(
+            r#"{"result": {"structuredContent": {
+                "name": "Alice \"A\"",
+                "ssn": "123-45-6789",
+                "age": 1.50e1,
+                "tags": [true, null, {"k": "v"}]
+            }}}"#,
+        )
+        .unwrap();
+        assert!(output.remove_result("ssn"));
+        assert!(!output.remove_result("ssn"));
+
+        let structured = r#"{"age":1.50e1,"name":"Alice \"A\"","tags":[true,null,{"k":"v"}]}"#;
+        let serialized = output.to_json_string();
+        assert_eq!(
+            serialized,
+            format!(
+                r#"{{"result":{{"content":[{{"type":"text","text":{}}}],"structuredContent":{structured}}}}}"#,
+                Json::string(structured)
+            )
+        );
+
+        let reparsed = Output::from_json_str(&serialized).unwrap();
+        assert_eq!(reparsed.to_json_string(), serialized);
+        assert!(reparsed.get_result("ssn").is_none());
+    }
+
+    #[test]
+    fn test_serialize_output_keeps_content() {
+        let mut output = Output::from_json_str(
+            r#"{"result": {
+                "content": [
+                    {"type": "text", "text": "{\"name\": \"Alice\", \"ssn\": \"123-45-6789\"}", "annotations": {"priority": 1}},
+                    {"type": "text", "text": "Found 1 customer"},
+                    {"type": "image", "data": "aGk=", "mimeType": "image/png"}
+                ],
+                "structuredContent": {"name": "Alice", "ssn": "123-45-6789"}
+            }}"#,
+        )
+        .unwrap();
+        assert!(output.remove_result("ssn"));
+
+        let structured = r#"{"name":"Alice"}"#;
+        let serialized = output.to_json_string();
+        assert_eq!(
+            serialized,
+            format!(
+                concat!(
+                    r#"{{"result":{{"content":["#,
+                    r#"{{"type":"text","text":{},"annotations":{{"priority":1}}}},"#,
+                    r#"{{"type":"text","text":"Found 1 customer"}},"#,
+                    r#"{{"type":"image","data":"aGk=","mimeType":"image/png"}}"#,
+                    r#"],"structuredContent":{}}}}}"#
+                ),
+                Json::string(structured),
+                structured
+            )
+        );
+        let reparsed = Output::from_json_str(&serialized).unwrap();
+        assert_eq!(reparsed.to_json_string(), serialized);
+    }
+
+    #[test]
+    fn test_serialize_formats() {
+        let cases = [
+            (r#"{"type":"string","format":"date"}"#, "date"),
+            (r#"{"type":"string","format":"date-time"}"#, "date-time"),
+            (r#"{"type":"string","format":"ipv4"}"#, "ipv4"),
+            (r#"{"type":"string","format":"ipv6"}"#, "ipv6"),
+        ];
+        for (schema, format) in cases {
+            let tool = ToolDescription::from_json_str(&format!(
+                r#"{{"name": "tool", "inputSchema": {{"properties": {{"attr": {schema}}}}}}}"#
+            ))
+            .unwrap();
+            let ty = tool.inputs().properties().next().unwrap().property_type();
+            assert_eq!(ty.to_json_string(), schema, "format {format}");
+        }
+    }
+
+    fn number(literal: &str) -> Number {
+        let mut parser = JsonParser::new(literal);
+        match Value::from(&parser.get_value().unwrap()) {
+            Value::Number(num) => num,
+            _ => panic!("expected a number literal"),
+        }
+    }
+
+    fn arb_constraints() -> impl Strategy<Value = PropertyConstraints> {
+        let bound = || prop::option::of(prop::sample::select(vec!["0", "-3", "1.5", "2e3"]));
+        (
+            (bound(), bound(), bound(), bound()),
+            prop::option::of(prop::sample::select(vec!["0.5", "3", "1e2"])),
+            (
+                prop::option::of(0..10usize),
+                prop::option::of(0..10usize),
+                prop::option::of(prop::sample::select(vec!["^a+$", "[0-9]{2}", "\\d"])),
+            ),
+            (
+                prop::option::of(0..10usize),
+                prop::option::of(0..10usize),
+                any::<bool>(),
+            ),
+        )
+            .prop_map(
+                |(
+                    (minimum, maximum, exclusive_minimum, exclusive_maximum),
+                    multiple_of,
+                    (min_length, max_length, pattern),
+                    (min_items, max_items, unique_items),
+                )| PropertyConstraints {
+                    minimum: minimum.map(number),
+                    maximum: maximum.map(number),
+                    exclusive_minimum: exclusive_minimum.map(number),
+                    exclusive_maximum: exclusive_maximum.map(number),
+                    multiple_of: multiple_of.map(number),
+                    min_length,
+                    max_length,
+                    pattern: pattern.map(|p| Pattern::new(p.into()).unwrap()),
+                    min_items,
+                    max_items,
+                    unique_items,
+                },
+            )
+            .prop_filter("constraints must not be empty", |c| !c.is_empty())
+    }
+
+    /// Names of type definitions that are never defined, so `$ref`s to them cannot form cycles.
+    fn arb_undefined_name() -> BoxedStrategy<SmolStr> {
+        prop::sample::select(vec!["Missing", "a/b", "c~d", "e%f#g", "h i"])
+            .prop_map(SmolStr::from)
+            .boxed()
+    }
+
+    fn arb_property_type(ref_name: BoxedStrategy<SmolStr>) -> impl Strategy<Value = PropertyType> {
+        let leaf = prop_oneof![
+            Just(PropertyType::Unknown),
+            Just(PropertyType::Bool),
+            Just(PropertyType::Integer),
+            Just(PropertyType::Float),
+            Just(PropertyType::Number),
+            Just(PropertyType::String),
+            Just(PropertyType::Decimal),
+            Just(PropertyType::Datetime {
+                format: DatetimeFormat::Date
+            }),
+            Just(PropertyType::Datetime {
+                format: DatetimeFormat::DateTime
+            }),
+            Just(PropertyType::Duration),
+            Just(PropertyType::IpAddr {
+                format: IpAddrFormat::Ipv4
+            }),
+            Just(PropertyType::IpAddr {
+                format: IpAddrFormat::Ipv6
+            }),
+            Just(PropertyType::Null),
+            prop::collection::vec(any::<String>().prop_map(SmolStr::from), 1..4)
+                .prop_map(|variants| PropertyType::Enum { variants }),
+            prop::collection::vec(any::<i64>(), 1..4)
+                .prop_map(|variants| PropertyType::IntegerEnum { variants }),
+            prop::collection::vec(any::<bool>(), 1..3)
+                .prop_map(|variants| PropertyType::BoolEnum { variants }),
+            ref_name.prop_map(|name| PropertyType::Ref { name }),
+        ];
+        leaf.prop_recursive(4, 32, 4, |inner| {
+            prop_oneof![
+                inner.clone().prop_map(|ty| PropertyType::Array {
+                    element_ty: Box::new(ty)
+                }),
+                prop::collection::vec(inner.clone(), 0..4)
+                    .prop_map(|types| PropertyType::Tuple { types }),
+                (
+                    prop::collection::vec(inner.clone(), 1..4),
+                    prop_oneof![Just(UnionKind::AnyOf), Just(UnionKind::OneOf)],
+                )
+                    .prop_map(|(types, kind)| PropertyType::Union {
+                        types,
+                        kind,
+                        discriminator: None,
+                    }),
+                (
+                    arb_properties(inner.clone()),
+                    prop::option::of(inner.clone())
+                )
+                    .prop_map(|(properties, additional_properties)| {
+                        PropertyType::Object {
+                            properties,
+                            additional_properties: additional_properties.map(Box::new),
+                        }
+                    }),
+                (inner, arb_constraints()).prop_map(|(ty, constraints)| {
+                    PropertyType::Constrained {
+                        ty: Box::new(ty),
+                        constraints: Box::new(constraints),
+                    }
+                }),
+            ]
+        })
+    }
+
+    fn arb_properties(
+        ty: impl Strategy<Value = PropertyType>,
+    ) -> impl Strategy<Value = Vec<Property>> {
+        prop::collection::btree_map(
+            any::<String>().prop_map(SmolStr::from),
+            (any::<bool>(), ty, prop::option::of(any::<String>())),
+            0..4,
+        )
+        .prop_map(|props| {
+            props
+                .into_iter()
+                .map(|(name, (required, ty, description))| {
+                    Property::new(name, required, ty, description)
+                })
+                .collect()
+        })
+    }
+
+    fn arb_type_defs(
+        prefix: &'static str,
+    ) -> impl Strategy<Value = HashMap<SmolStr, PropertyTypeDef>> {
+        prop::collection::vec(
+            (
+                arb_property_type(arb_undefined_name()),
+                prop::option::of(any::<String>()),
+            ),
+            0..3,
+        )
+        .prop_map(move |defs| {
+            defs.into_iter()
+                .enumerate()
+                .map(|(i, (ty, description))| {
+                    let name = SmolStr::from(format!("{prefix}{i}"));
+                    (name.clone(), PropertyTypeDef::new(name, ty, description))
+                })
+                .collect()
+        })
+    }
+
+    fn arb_parameters(prefix: &'static str) -> impl Strategy<Value = Parameters> {
+        let ref_name = prop_oneof![
+            arb_undefined_name(),
+            prop::sample::select(vec!["ToolDef0", "ToolDef1"]).prop_map(SmolStr::from),
+        ]
+        .boxed();
+        (
+            arb_properties(arb_property_type(ref_name)),
+            arb_type_defs(prefix),
+        )
+            .prop_map(|(properties, type_defs)| Parameters::new(properties, type_defs))
+    }
+
+    fn arb_tool_description() -> impl Strategy<Value = ToolDescription> {
+        (
+            any::<String>().prop_map(SmolStr::from),
+            prop::option::of(any::<String>()),
+            arb_parameters("InputDef"),
+            arb_parameters("OutputDef"),
+            arb_type_defs("ToolDef"),
+            prop::option::of(any::<String>()),
+            arb_tool_annotations(),
+        )
+            .prop_map(
+                |(name, description, inputs, outputs, type_defs, title, annotations)| {
+                    let tool = ToolDescription::new(name, inputs, outputs, type_defs, description)
+                        .with_annotations(annotations);
+                    match title {
+                        Some(title) => tool.with_title(title),
+                        None => tool,
+                    }
+                },
+            )
+    }
+
+    fn arb_tool_annotations() -> impl Strategy<Value = ToolAnnotations> {
+        (
+            prop::option::of(any::<String>()),
+            prop::option::of(any::<bool>()),
+            prop::option::of(any::<bool>()),
+            prop::option::of(any::<bool>()),
+            prop::option::of(any::<bool>()),
+        )
+            .prop_map(
+                |(title, read_only_hint, destructive_hint, idempotent_hint, open_world_hint)| {
+                    ToolAnnotations {
+                        title,
+                        read_only_hint,
+                        destructive_hint,
+                        idempotent_hint,
+                        open_world_hint,
+                    }
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_property_type_round_trips(ty in arb_property_type(arb_undefined_name())) {
+            let tool = ToolDescription::new(
+                "tool".into(),
+                Parameters::new(vec![Property::new("p".into(), true, ty, None)], HashMap::new()),
+                Parameters::new(Vec::new(), HashMap::new()),
+                HashMap::new(),
+                None,
+            );
+            let reparsed = ToolDescription::from_json_str(&tool.to_json_string());
+            prop_assert_eq!(reparsed.ok(), Some(tool));
+        }
+
+        #[test]
+        fn prop_tool_description_round_trips(tool in arb_tool_description()) {
+            let serialized = tool.to_json_string();
+            let reparsed = ToolDescription::from_json_str(&serialized);
+            prop_assert_eq!(reparsed.ok(), Some(tool.clone()));
+
+            // Parsing what was serialized and serializing it again yields the same JSON
+            let server = ServerDescription::new(std::iter::once(tool), HashMap::new());
+            let serialized = server.to_json_string();
+            let reparsed = ServerDescription::from_json_str(&serialized).unwrap();
+            prop_assert_eq!(&reparsed, &server);
+            prop_assert_eq!(reparsed.to_json_string(), serialized);
+        }
+    }
+}
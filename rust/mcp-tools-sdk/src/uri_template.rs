@@ -0,0 +1,655 @@
+/*
+ * Copyright Cedar Contributors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      https://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The `uri_template` module defines [RFC 6570](https://www.rfc-editor.org/rfc/rfc6570) URI Templates,
+//! as used by MCP resource templates, and the matching of URIs against them.
+
+use regex::Regex;
+use smol_str::{SmolStr, ToSmolStr};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use super::deserializer::percent_decode;
+use super::err::UriTemplateError;
+
+/// The operator of a URI Template expression (RFC 6570, Section 2.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    /// `{var}`
+    Simple,
+    /// `{+var}`
+    Reserved,
+    /// `{#var}`
+    Fragment,
+    /// `{.var}`
+    Label,
+    /// `{/var}`
+    PathSegment,
+    /// `{;var}`
+    PathParameter,
+    /// `{?var}`
+    Query,
+    /// `{&var}`
+    QueryContinuation,
+}
+
+impl Operator {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '+' => Some(Self::Reserved),
+            '#' => Some(Self::Fragment),
+            '.' => Some(Self::Label),
+            '/' => Some(Self::PathSegment),
+            ';' => Some(Self::PathParameter),
+            '?' => Some(Self::Query),
+            '&' => Some(Self::QueryContinuation),
+            _ => None,
+        }
+    }
+
+    /// Whether `c` is an operator reserved for future extensions
+    fn is_reserved(c: char) -> bool {
+        matches!(c, '=' | ',' | '!' | '@' | '|')
+    }
+
+    /// The (regular expression matching the) string preceding the first variable of an expansion
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::Simple | Self::Reserved => "",
+            Self::Fragment => "#",
+            Self::Label => r"\.",
+            Self::PathSegment => "/",
+            Self::PathParameter => ";",
+            Self::Query => r"[?&]",
+            Self::QueryContinuation => "&",
+        }
+    }
+
+    /// The (regular expression matching the) string separating the variables of an expansion
+    fn separator(self) -> &'static str {
+        match self {
+            Self::Simple | Self::Reserved | Self::Fragment => ",",
+            Self::Label => r"\.",
+            Self::PathSegment => "/",
+            Self::PathParameter => ";",
+            // The first defined variable of a query is preceded by `?` (not necessarily the first variable)
+            Self::Query => r"[?&]",
+            Self::QueryContinuation => "&",
+        }
+    }
+
+    /// Whether variables are expanded within the path of URIs (i.e., not as parameters, in
+    /// the query or in the fragment)
+    fn is_path(self) -> bool {
+        matches!(
+            self,
+            Self::Simple | Self::Reserved | Self::Label | Self::PathSegment
+        )
+    }
+
+    /// Whether variables are expanded as `name=value` pairs
+    fn is_named(self) -> bool {
+        matches!(
+            self,
+            Self::PathParameter | Self::Query | Self::QueryContinuation
+        )
+    }
+
+    /// The characters that cannot occur in the expansion of a variable. Besides the expression's
+    /// separators, these are the delimiters structuring URIs (which are percent-encoded in
+    /// expansions other than `{+var}` and `{#var}`).
+    fn excluded_chars(self, explode: bool, single: bool) -> &'static str {
+        match (self, explode) {
+            (Self::Simple, _) if single || explode => "/?#&=;",
+            (Self::Simple, _) => "/?#&=;,",
+            (Self::Reserved, _) if single || explode => "#",
+            (Self::Reserved, _) => "#,",
+            (Self::Fragment, _) if single || explode => "",
+            (Self::Fragment, _) => ",",
+            (Self::Label, false) => "/?#&=;.",
+            (Self::Label, true) => "/?#&=;",
+            (Self::PathSegment, false) => "/?#&=;",
+            (Self::PathSegment, true) => "?#&=;",
+            (Self::PathParameter, false) => "/?#&=;",
+            (Self::PathParameter, true) => "/?#",
+            (Self::Query | Self::QueryContinuation, false) => "#&",
+            (Self::Query | Self::QueryContinuation, true) => "#",
+        }
+    }
+}
+
+/// A variable of a URI Template
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UriTemplateVariable {
+    name: SmolStr,
+    required: bool,
+}
+
+impl UriTemplateVariable {
+    /// Get the name of this variable
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether every URI matching the template defines this variable
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+}
+
+/// A capturing group of the regular expression matching a URI Template
+#[derive(Debug, Clone)]
+struct Capture {
+    /// The index of the captured variable within the template's variables
+    variable: usize,
+    /// Whether the group captures the `=value` part of a `name=value` pair
+    named: bool,
+    /// Whether the captured value is part of the URI's path, in which case it cannot
+    /// contain dot-segments
+    path: bool,
+    /// Whether the captured value is a single path segment, in which case it cannot
+    /// contain (percent-encoded) `/`s
+    segment: bool,
+}
+
+/// A URI Template (see [RFC 6570](https://www.rfc-editor.org/rfc/rfc6570)), e.g., `file:///{path}`
+/// or `https://api.example.com/users/{id}{?fields}`.
+///
+/// URIs are matched against a template by reversing its expansion. The values of the variables of
+/// `{var}`, `{+var}`, `{#var}`, `{.var}` and `{/var}` expressions are separated by the expression's
+/// separator, so the first variable of such an expression is required (i.e., defined by every matching
+/// URI) while the others are optional. The variables of `{;var}`, `{?var}` and `{&var}` expressions
+/// are matched as `name=value` pairs in the order of the template, and are optional. Exploded variables
+/// (e.g., `{/path*}`) are matched as a single value containing the separators (e.g., `a/b/c`), and
+/// prefix modifiers (e.g., `{var:3}`) do not restrict the length of the matched value. When a URI
+/// matches a template in several ways, the shortest values are preferred for the first variables
+/// (e.g., `file:///notes?encoding=utf-8` matches `file:///{+path}{?encoding}` with `path` being `notes`).
+///
+/// So that the values of variables can safely be compared to paths (e.g., in policies), a URI does
+/// not match a template if the (percent-decoded) value of a variable within the URI's path (i.e., of
+/// `{var}`, `{+var}`, `{.var}` and `{/var}` expressions) contains a dot-segment (`.` or `..`, e.g.,
+/// `src/../secrets` or `src/%2e%2e/secrets`), or if the value of a variable that cannot contain `/`s
+/// when expanded (e.g., `{var}`) contains a percent-encoded `/` (`%2F`).
+#[derive(Debug, Clone)]
+pub struct UriTemplate {
+    template: SmolStr,
+    variables: Vec<UriTemplateVariable>,
+    regex: Regex,
+    captures: Vec<Capture>,
+}
+
+impl UriTemplate {
+    /// Parse a URI Template, failing if `template` is not a well-formed RFC 6570 URI Template
+    pub fn new(template: &str) -> Result<Self, UriTemplateError> {
+        let mut variables: Vec<UriTemplateVariable> = Vec::new();
+        let mut captures = Vec::new();
+        let mut pattern = String::from("^");
+
+        let mut rest = template;
+        while !rest.is_empty() {
+            let literal_end = rest.find(['{', '}']).unwrap_or(rest.len());
+            let (literal, remainder) = rest.split_at(literal_end);
+            pattern.push_str(&regex::escape(literal));
+            rest = remainder;
+            if rest.starts_with('}') {
+                return Err(UriTemplateError::UnexpectedClosingBrace(template.into()));
+            }
+            let Some(expression) = rest.strip_prefix('{') else {
+                continue;
+            };
+            let Some(expression_end) = expression.find('}') else {
+                return Err(UriTemplateError::UnclosedExpression(template.into()));
+            };
+            let (expression, remainder) = expression.split_at(expression_end);
+            rest = remainder.get(1..).unwrap_or_default();
+
+            let (operator, var_list) = match expression.chars().next() {
+                Some(c) if Operator::is_reserved(c) => {
+                    return Err(UriTemplateError::UnsupportedOperator {
+                        template: template.into(),
+                        operator: c,
+                    })
+                }
+                Some(c) => match Operator::from_char(c) {
+                    Some(operator) => {
+                        (operator, expression.get(c.len_utf8()..).unwrap_or_default())
+                    }
+                    None => (Operator::Simple, expression),
+                },
+                None => (Operator::Simple, expression),
+            };
+
+            let var_specs = var_list
+                .split(',')
+                .map(|spec| parse_var_spec(template, spec))
+                .collect::<Result<Vec<_>, _>>()?;
+            let single = var_specs.len() == 1;
+            for (i, (name, explode)) in var_specs.into_iter().enumerate() {
+                let excluded_chars = operator.excluded_chars(explode, single);
+                let value = format!(
+                    "(?:%[0-9A-Fa-f]{{2}}|[^%{}])*?",
+                    excluded_chars
+                        .chars()
+                        .map(|c| regex::escape(&c.to_string()))
+                        .collect::<String>()
+                );
+                let (group, named, required) = if operator.is_named() {
+                    let prefix = if i == 0 {
+                        operator.prefix()
+                    } else {
+                        operator.separator()
+                    };
+                    if explode {
+                        (format!("(?:{prefix}({value}))?"), false, false)
+                    } else {
+                        (
+                            format!("(?:{prefix}{}(={value}|))?", regex::escape(&name)),
+                            true,
+                            false,
+                        )
+                    }
+                } else if i == 0 {
+                    (format!("{}({value})", operator.prefix()), false, true)
+                } else {
+                    (
+                        format!("(?:{}({value}))?", operator.separator()),
+                        false,
+                        false,
+                    )
+                };
+                pattern.push_str(&group);
+
+                let variable = match variables.iter().position(|var| var.name == name) {
+                    Some(index) => {
+                        if let Some(var) = variables.get_mut(index) {
+                            var.required |= required;
+                        }
+                        index
+                    }
+                    None => {
+                        variables.push(UriTemplateVariable { name, required });
+                        variables.len() - 1
+                    }
+                };
+                captures.push(Capture {
+                    variable,
+                    named,
+                    path: operator.is_path(),
+                    segment: operator.is_path() && excluded_chars.contains('/'),
+                });
+            }
+        }
+        pattern.push('$');
+
+        let regex =
+            Regex::new(&pattern).map_err(|_| UriTemplateError::TooLarge(template.into()))?;
+        Ok(Self {
+            template: template.into(),
+            variables,
+            regex,
+            captures,
+        })
+    }
+
+    /// Get the source of this URI Template
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// Get the variables of this URI Template, in the order of their first occurrence
+    pub fn variables(&self) -> impl Iterator<Item = &UriTemplateVariable> {
+        self.variables.iter()
+    }
+
+    /// Returns `true` if `uri` matches this URI Template
+    pub fn is_match(&self, uri: &str) -> bool {
+        self.match_uri(uri).is_some()
+    }
+
+    /// Match `uri` against this URI Template, returning the (percent-decoded) values of the
+    /// variables it defines, or `None` if `uri` does not match this URI Template.
+    ///
+    /// If a variable occurs several times in the template, the value of its first defined
+    /// occurrence is returned. URIs whose path variables contain dot-segments or percent-encoded
+    /// `/`s do not match (see [`UriTemplate`]).
+    pub fn match_uri(&self, uri: &str) -> Option<HashMap<SmolStr, SmolStr>> {
+        let captures = self.regex.captures(uri)?;
+        let mut values = HashMap::new();
+        for (capture, group) in self.captures.iter().zip(captures.iter().skip(1)) {
+            let (Some(group), Some(var)) = (group, self.variables.get(capture.variable)) else {
+                continue;
+            };
+            let value = if capture.named {
+                group.as_str().strip_prefix('=').unwrap_or_default()
+            } else {
+                group.as_str()
+            };
+            let value = percent_decode(value);
+            if capture.path
+                && value
+                    .split('/')
+                    .any(|segment| matches!(segment, "." | ".."))
+            {
+                return None;
+            }
+            if capture.segment && value.contains('/') {
+                return None;
+            }
+            values
+                .entry(var.name.clone())
+                .or_insert_with(|| value.to_smolstr());
+        }
+        Some(values)
+    }
+}
+
+/// Parse the variable specification `spec` of an expression of `template`,
+/// returning the variable's name and whether it is exploded
+fn parse_var_spec(template: &str, spec: &str) -> Result<(SmolStr, bool), UriTemplateError> {
+    let malformed = || UriTemplateError::MalformedVariable {
+        template: template.into(),
+        variable: spec.into(),
+    };
+    let (name, explode) = if let Some(name) = spec.strip_suffix('*') {
+        (name, true)
+    } else if let Some((name, max_length)) = spec.split_once(':') {
+        let is_valid_length = (1..=4).contains(&max_length.len())
+            && !max_length.starts_with('0')
+            && max_length.bytes().all(|b| b.is_ascii_digit());
+        if !is_valid_length {
+            return Err(malformed());
+        }
+        (name, false)
+    } else {
+        (spec, false)
+    };
+    if is_var_name(name) {
+        Ok((name.into(), explode))
+    } else {
+        Err(malformed())
+    }
+}
+
+/// Whether `name` is a valid variable name: a non-empty sequence of letters, digits, `_` and
+/// percent-encoded characters, optionally separated by single `.`s
+fn is_var_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('.').all(|part| {
+            let mut bytes = part.bytes();
+            !part.is_empty()
+                && std::iter::from_fn(|| {
+                    bytes.next().map(|b| match b {
+                        b'%' => {
+                            bytes.next().is_some_and(|b| b.is_ascii_hexdigit())
+                                && bytes.next().is_some_and(|b| b.is_ascii_hexdigit())
+                        }
+                        b => b.is_ascii_alphanumeric() || b == b'_',
+                    })
+                })
+                .all(|valid| valid)
+        })
+}
+
+impl FromStr for UriTemplate {
+    type Err = UriTemplateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl std::fmt::Display for UriTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.template)
+    }
+}
+
+impl PartialEq for UriTemplate {
+    fn eq(&self, other: &Self) -> bool {
+        self.template == other.template
+    }
+}
+
+impl Eq for UriTemplate {}
+
+impl std::hash::Hash for UriTemplate {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.template.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #![expect(clippy::expect_used, clippy::unwrap_used, reason = "test assertions")]
+    use super::*;
+    use cool_asserts::assert_matches;
+
+    fn matches(template: &str, uri: &str) -> Option<Vec<(String, String)>> {
+        let template = UriTemplate::new(template).expect("URI template should parse");
+        template.match_uri(uri).map(|values| {
+            let mut values = values
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<Vec<_>>();
+            values.sort();
+            values
+        })
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> Option<Vec<(String, String)>> {
+        Some(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_match_simple_expressions() {
+        assert_eq!(
+            matches("users://{id}/profile", "users://alice/profile"),
+            values(&[("id", "alice")])
+        );
+        assert_eq!(
+            matches("users://{id}/profile", "users://a%20b/profile"),
+            values(&[("id", "a b")])
+        );
+        assert_eq!(matches("users://{id}/profile", "users://a/b/profile"), None);
+        // A percent-encoded `/` does not turn a single segment into a path
+        assert_eq!(
+            matches("users://{id}/profile", "users://a%2Fb/profile"),
+            None
+        );
+        assert_eq!(matches("users://{id}/profile", "users://../profile"), None);
+        assert_eq!(matches("users://{id}/profile", "users://alice"), None);
+        assert_eq!(
+            matches("map://{x,y}", "map://1,2"),
+            values(&[("x", "1"), ("y", "2")])
+        );
+        assert_eq!(matches("map://{x,y}", "map://1"), values(&[("x", "1")]));
+        assert_eq!(matches("static://config", "static://config"), values(&[]));
+        assert_eq!(matches("static://config", "static://other"), None);
+    }
+
+    #[test]
+    fn test_match_reserved_and_fragment_expressions() {
+        assert_eq!(
+            matches("file:///{+path}", "file:///home/user/notes.txt"),
+            values(&[("path", "home/user/notes.txt")])
+        );
+        assert_eq!(
+            matches("file:///{path}", "file:///home/user/notes.txt"),
+            None
+        );
+        assert_eq!(
+            matches("file:///{+path}{?encoding}", "file:///notes?encoding=utf-8"),
+            values(&[("encoding", "utf-8"), ("path", "notes")])
+        );
+        assert_eq!(
+            matches("docs://{+path}{#section}", "docs://guide/intro#setup"),
+            values(&[("path", "guide/intro"), ("section", "setup")])
+        );
+        assert_eq!(
+            matches("file:///{+path}", "file:///src/a%2Fb"),
+            values(&[("path", "src/a/b")])
+        );
+        assert_eq!(
+            matches("file:///{+path}", "file:///src/..data/.env"),
+            values(&[("path", "src/..data/.env")])
+        );
+        // Dot-segments could escape the directory a policy restricts the path to
+        for uri in [
+            "file:///src/../secrets/key.pem",
+            "file:///src/%2e%2e/secrets/key.pem",
+            "file:///src/%2E%2E%2Fsecrets",
+            "file:///src/./main.rs",
+            "file:///..",
+        ] {
+            assert_eq!(
+                matches("file:///{+path}", uri),
+                None,
+                "{uri} should not match"
+            );
+        }
+        assert_eq!(
+            matches("https://{host}{/segments*}", "https://example.com/a/../b"),
+            None
+        );
+        // Values outside of the path are not restricted
+        assert_eq!(
+            matches(
+                "https://example.com/search{?q}",
+                "https://example.com/search?q=../a%2Fb"
+            ),
+            values(&[("q", "../a/b")])
+        );
+    }
+
+    #[test]
+    fn test_match_label_and_path_expressions() {
+        assert_eq!(
+            matches("https://{host}{/segments*}", "https://example.com/a/b/c"),
+            values(&[("host", "example.com"), ("segments", "a/b/c")])
+        );
+        assert_eq!(
+            matches("repo://{owner}{/name,ref}", "repo://cedar/mcp/main"),
+            values(&[("name", "mcp"), ("owner", "cedar"), ("ref", "main")])
+        );
+        assert_eq!(
+            matches("repo://{owner}{/name,ref}", "repo://cedar/mcp"),
+            values(&[("name", "mcp"), ("owner", "cedar")])
+        );
+        assert_eq!(
+            matches("file://report{.format}", "file://report.json"),
+            values(&[("format", "json")])
+        );
+    }
+
+    #[test]
+    fn test_match_named_expressions() {
+        assert_eq!(
+            matches(
+                "https://api.example.com/search{?q,limit}",
+                "https://api.example.com/search?q=cedar%20policy&limit=10"
+            ),
+            values(&[("limit", "10"), ("q", "cedar policy")])
+        );
+        assert_eq!(
+            matches(
+                "https://api.example.com/search{?q,limit}",
+                "https://api.example.com/search?limit=10"
+            ),
+            values(&[("limit", "10")])
+        );
+        assert_eq!(
+            matches(
+                "https://api.example.com/search{?q,limit}",
+                "https://api.example.com/search"
+            ),
+            values(&[])
+        );
+        assert_eq!(
+            matches(
+                "https://example.com/search?q={q}{&page}",
+                "https://example.com/search?q=x&page=2"
+            ),
+            values(&[("page", "2"), ("q", "x")])
+        );
+        assert_eq!(
+            matches("map://point{;x,y}", "map://point;x=1;y"),
+            values(&[("x", "1"), ("y", "")])
+        );
+    }
+
+    #[test]
+    fn test_variables() {
+        let template = UriTemplate::new("repo://{owner}{/name,ref}{?q}{&owner}").unwrap();
+        assert_eq!(
+            template
+                .variables()
+                .map(|var| (var.name(), var.is_required()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("owner", true),
+                ("name", true),
+                ("ref", false),
+                ("q", false)
+            ]
+        );
+        assert_eq!(template.as_str(), "repo://{owner}{/name,ref}{?q}{&owner}");
+        assert_eq!(template.to_string(), template.as_str());
+        assert!(template.is_match("repo://cedar/mcp?q=x&owner=other"));
+        assert_eq!(
+            template
+                .match_uri("repo://cedar/mcp?q=x&owner=other")
+                .and_then(|values| values.get("owner").cloned()),
+            Some("cedar".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_matches!(
+            UriTemplate::new("users://{id"),
+            Err(UriTemplateError::UnclosedExpression(_))
+        );
+        assert_matches!(
+            UriTemplate::new("users://id}"),
+            Err(UriTemplateError::UnexpectedClosingBrace(_))
+        );
+        assert_matches!(
+            UriTemplate::new("users://{!id}"),
+            Err(UriTemplateError::UnsupportedOperator { operator: '!', .. })
+        );
+        for template in [
+            "users://{}",
+            "users://{id,}",
+            "users://{.id.}",
+            "users://{i-d}",
+            "users://{/id:0}",
+            "users://{/id:10000}",
+            "users://{id%2}",
+        ] {
+            assert_matches!(
+                UriTemplate::new(template),
+                Err(UriTemplateError::MalformedVariable { .. }),
+                "{template} should not parse"
+            );
+        }
+        assert!(UriTemplate::new("users://{user.id:12}{/path*}{?a%20b}").is_ok());
+    }
+}
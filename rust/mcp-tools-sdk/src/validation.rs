@@ -14,29 +14,110 @@
  * limitations under the License.
  */
 
-use crate::data::{self, Input, Output, TypedInput, TypedOutput, TypedValue, Value};
-use crate::description::{self, PropertyType, ToolDescription};
-use crate::err::ValidationError;
+use crate::data::{self, Input, Number, Output, TypedInput, TypedOutput, TypedValue, Value};
+use crate::description::{
+    self, Discriminator, PropertyConstraints, PropertyType, ToolDescription, UnionKind,
+};
+use crate::err::{ValidationDiagnostic, ValidationError, ValidationErrors};
+use crate::parser::json_value::LocatedValue;
 use itertools::Itertools;
 use smol_str::{SmolStr, ToSmolStr};
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
+/// The `ValidationError`s found while validating a value, each paired with the
+/// reference tokens of the JSON Pointer to the value it was found at.
+#[derive(Debug, Default)]
+pub(crate) struct Diagnostics {
+    path: Vec<SmolStr>,
+    errors: Vec<(Vec<SmolStr>, ValidationError)>,
+}
+
+impl Diagnostics {
+    fn report(&mut self, error: ValidationError) {
+        self.errors.push((self.path.clone(), error));
+    }
+
+    fn report_at(&mut self, token: &str, error: ValidationError) {
+        self.at(token, |diagnostics| diagnostics.report(error))
+    }
+
+    /// Run `f` with `token` appended to the current path.
+    fn at<T>(&mut self, token: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        self.path.push(token.to_smolstr());
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+
+    fn finish<T>(self, value: Option<T>) -> Result<T, Self> {
+        match value {
+            Some(value) if self.errors.is_empty() => Ok(value),
+            _ => Err(self),
+        }
+    }
+
+    /// The first `ValidationError` found.
+    pub(crate) fn into_first_error(self) -> ValidationError {
+        self.errors
+            .into_iter()
+            .next()
+            .map_or(ValidationError::InvalidValueForType, |(_, error)| error)
+    }
+
+    /// All `ValidationError`s found, located within the validated `vals`. An error at a
+    /// value that does not exist (e.g., a missing property) is located at its closest ancestor.
+    pub(crate) fn into_validation_errors(
+        self,
+        vals: &HashMap<SmolStr, LocatedValue>,
+    ) -> ValidationErrors {
+        let locate = |path: &[SmolStr]| {
+            (1..=path.len()).rev().find_map(|len| {
+                let (name, rest) = path.get(..len)?.split_first()?;
+                vals.get(name)?
+                    .get_path(rest)
+                    .map(|val| val.as_loc().clone())
+            })
+        };
+        ValidationErrors::new(
+            self.errors
+                .into_iter()
+                .map(|(path, error)| {
+                    let loc = locate(&path);
+                    ValidationDiagnostic::new(&path, error, loc)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<ValidationError> for Diagnostics {
+    fn from(error: ValidationError) -> Self {
+        let mut diagnostics = Self::default();
+        diagnostics.report(error);
+        diagnostics
+    }
+}
+
 pub(crate) fn validate_input(
     tool: &ToolDescription,
     input: &Input,
     mut type_defs: HashMap<SmolStr, description::PropertyTypeDef>,
-) -> Result<TypedInput, ValidationError> {
+) -> Result<TypedInput, Diagnostics> {
     if tool.name() != input.name() {
         return Err(ValidationError::mismatched_names(
             tool.name().to_smolstr(),
             input.name().to_smolstr(),
-        ));
+        )
+        .into());
     }
 
     type_defs.extend(tool.type_defs.type_defs.clone());
 
     let args = input.get_args().collect();
-    let args = validate_parameters(&tool.inputs, &args, &mut type_defs)?;
+    let mut diagnostics = Diagnostics::default();
+    let args = validate_parameters(&tool.inputs, &args, &mut type_defs, &mut diagnostics);
+    let args = diagnostics.finish(args)?;
     Ok(TypedInput {
         name: input.name.clone(),
         args,
@@ -47,54 +128,73 @@ pub(crate) fn validate_output(
     tool: &ToolDescription,
     output: &Output,
     mut type_defs: HashMap<SmolStr, description::PropertyTypeDef>,
-) -> Result<TypedOutput, ValidationError> {
+) -> Result<TypedOutput, Diagnostics> {
     type_defs.extend(tool.type_defs.type_defs.clone());
 
     let results = output.get_results().collect();
-    let results = validate_parameters(&tool.outputs, &results, &mut type_defs)?;
+    let mut diagnostics = Diagnostics::default();
+    let results = validate_parameters(&tool.outputs, &results, &mut type_defs, &mut diagnostics);
+    let results = diagnostics.finish(results)?;
     Ok(TypedOutput { results })
 }
 
+// The validation functions below report every error they find to `diagnostics`, and
+// return `None` if (and only if) they reported an error.
+
 fn validate_parameters(
     types: &description::Parameters,
     vals: &HashMap<&str, data::BorrowedValue<'_>>,
     type_defs: &mut HashMap<SmolStr, description::PropertyTypeDef>,
-) -> Result<HashMap<SmolStr, TypedValue>, ValidationError> {
+    diagnostics: &mut Diagnostics,
+) -> Option<HashMap<SmolStr, TypedValue>> {
     type_defs.extend(types.type_defs.type_defs.clone());
 
+    let mut valid = true;
     let mut props = HashMap::new();
     for property in types.properties() {
         match vals.get(property.name()) {
             Some(val) => {
-                let ty_val = validate_property_type(
-                    property.property_type(),
-                    val.clone().into(),
-                    type_defs,
-                )?;
-                props.insert(property.name().to_smolstr(), ty_val);
+                let ty_val = diagnostics.at(property.name(), |diagnostics| {
+                    validate_property_type(
+                        property.property_type(),
+                        val.clone().into(),
+                        type_defs,
+                        diagnostics,
+                    )
+                });
+                match ty_val {
+                    Some(ty_val) => {
+                        props.insert(property.name().to_smolstr(), ty_val);
+                    }
+                    None => valid = false,
+                }
             }
             None if property.is_required() => {
-                return Err(ValidationError::missing_required_property(
-                    property.name().into(),
-                ))
+                diagnostics.report_at(
+                    property.name(),
+                    ValidationError::missing_required_property(property.name().into()),
+                );
+                valid = false;
             }
             None => (),
         }
     }
     for val in vals.keys() {
-        if !props.contains_key(*val) {
-            return Err(ValidationError::unexpected_property(val));
+        if !types.properties().any(|property| property.name() == *val) {
+            diagnostics.report_at(val, ValidationError::unexpected_property(val));
+            valid = false;
         }
     }
-    Ok(props)
+    valid.then_some(props)
 }
 
 fn validate_property_type(
     ty: &PropertyType,
     val: Value,
     type_defs: &HashMap<SmolStr, description::PropertyTypeDef>,
-) -> Result<TypedValue, ValidationError> {
-    match (ty, val) {
+    diagnostics: &mut Diagnostics,
+) -> Option<TypedValue> {
+    let result = match (ty, val) {
         (PropertyType::Bool, Value::Bool(b)) => Ok(TypedValue::Bool(b)),
         (PropertyType::Integer, Value::Number(num)) => match num.to_i64() {
             Some(i) => Ok(TypedValue::Integer(i)),
@@ -113,7 +213,7 @@ fn validate_property_type(
                 Ok(TypedValue::Decimal(s))
             }
         }
-        (PropertyType::Datetime, Value::String(s)) => {
+        (PropertyType::Datetime { .. }, Value::String(s)) => {
             if !is_datetime(&s) {
                 Err(ValidationError::invalid_datetime_literal(&s))
             } else {
@@ -127,7 +227,7 @@ fn validate_property_type(
                 Ok(TypedValue::Duration(s))
             }
         }
-        (PropertyType::IpAddr, Value::String(s)) => {
+        (PropertyType::IpAddr { .. }, Value::String(s)) => {
             if !is_ipaddr(&s) {
                 Err(ValidationError::invalid_ipaddr_literal(&s))
             } else {
@@ -142,34 +242,49 @@ fn validate_property_type(
                 Ok(TypedValue::Enum(s))
             }
         }
+        (PropertyType::IntegerEnum { variants }, Value::Number(num)) => match num.to_i64() {
+            Some(i) if variants.contains(&i) => Ok(TypedValue::Integer(i)),
+            _ => Err(ValidationError::invalid_enum_variant(num.as_str())),
+        },
+        (PropertyType::BoolEnum { variants }, Value::Bool(b)) => {
+            if !variants.contains(&b) {
+                Err(ValidationError::invalid_enum_variant(&b.to_smolstr()))
+            } else {
+                Ok(TypedValue::Bool(b))
+            }
+        }
         (PropertyType::Array { element_ty }, Value::Array(vals)) => {
-            let vals = vals
-                .into_iter()
-                .map(|v| validate_property_type(element_ty, v, type_defs))
-                .collect::<Result<Vec<_>, _>>()?;
+            let vals = validate_elements(
+                vals.into_iter().map(|val| (element_ty.as_ref(), val)),
+                type_defs,
+                diagnostics,
+            )?;
             Ok(TypedValue::Array(vals))
         }
         (PropertyType::Tuple { types }, Value::Array(vals)) => {
             if types.len() != vals.len() {
-                return Err(ValidationError::wrong_tuple_size(types.len(), vals.len()));
+                Err(ValidationError::wrong_tuple_size(types.len(), vals.len()))
+            } else {
+                let vals = validate_elements(types.iter().zip(vals), type_defs, diagnostics)?;
+                Ok(TypedValue::Tuple(vals))
             }
-            let vals = vals
-                .into_iter()
-                .zip(types.iter())
-                .map(|(val, ty)| validate_property_type(ty, val, type_defs))
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(TypedValue::Tuple(vals))
         }
-        (PropertyType::Union { types }, val) => {
-            for (index, ty) in types.iter().enumerate() {
-                if let Ok(ty_val) = validate_property_type(ty, val.clone(), type_defs) {
-                    return Ok(TypedValue::Union {
-                        index,
-                        value: Box::new(ty_val),
-                    });
-                }
-            }
-            Err(ValidationError::InvalidValueForUnionType)
+        (
+            PropertyType::Union {
+                types,
+                kind,
+                discriminator,
+            },
+            val,
+        ) => {
+            return validate_union(
+                types,
+                *kind,
+                discriminator.as_ref(),
+                val,
+                type_defs,
+                diagnostics,
+            )
         }
         (
             PropertyType::Object {
@@ -178,18 +293,32 @@ fn validate_property_type(
             },
             Value::Map(mut vals),
         ) => {
+            let mut valid = true;
             let mut props = HashMap::new();
             for property in properties {
                 match vals.remove(property.name()) {
                     Some(v) => {
-                        let ty_val =
-                            validate_property_type(property.property_type(), v.clone(), type_defs)?;
-                        props.insert(property.name().to_smolstr(), ty_val);
+                        let ty_val = diagnostics.at(property.name(), |diagnostics| {
+                            validate_property_type(
+                                property.property_type(),
+                                v,
+                                type_defs,
+                                diagnostics,
+                            )
+                        });
+                        match ty_val {
+                            Some(ty_val) => {
+                                props.insert(property.name().to_smolstr(), ty_val);
+                            }
+                            None => valid = false,
+                        }
                     }
                     None if property.is_required() => {
-                        return Err(ValidationError::missing_required_property(
-                            property.name().into(),
-                        ))
+                        diagnostics.report_at(
+                            property.name(),
+                            ValidationError::missing_required_property(property.name().into()),
+                        );
+                        valid = false;
                     }
                     None => (),
                 }
@@ -197,16 +326,26 @@ fn validate_property_type(
 
             let mut additional_props = HashMap::new();
             for (name, v) in vals.into_iter() {
-                if !props.contains_key(&name) {
-                    match additional_properties {
-                        Some(ty) => {
-                            let ty_val = validate_property_type(ty, v, type_defs)?;
-                            additional_props.insert(name, ty_val);
+                match additional_properties {
+                    Some(ty) => {
+                        match diagnostics.at(&name, |diagnostics| {
+                            validate_property_type(ty, v, type_defs, diagnostics)
+                        }) {
+                            Some(ty_val) => {
+                                additional_props.insert(name, ty_val);
+                            }
+                            None => valid = false,
                         }
-                        None => return Err(ValidationError::unexpected_property(&name)),
+                    }
+                    None => {
+                        diagnostics.report_at(&name, ValidationError::unexpected_property(&name));
+                        valid = false;
                     }
                 }
             }
+            if !valid {
+                return None;
+            }
             Ok(TypedValue::Object {
                 properties: props,
                 additional_properties: additional_props,
@@ -214,7 +353,8 @@ fn validate_property_type(
         }
         (PropertyType::Ref { name }, val) => match type_defs.get(name) {
             Some(ty) => {
-                let ty_val = validate_property_type(ty.property_type(), val, type_defs)?;
+                let ty_val =
+                    validate_property_type(ty.property_type(), val, type_defs, diagnostics)?;
                 Ok(TypedValue::Ref {
                     name: name.clone(),
                     val: Box::new(ty_val),
@@ -222,8 +362,240 @@ fn validate_property_type(
             }
             None => Err(ValidationError::unexpected_type_name(name.as_str())),
         },
+        (PropertyType::Constrained { ty, constraints }, val) => {
+            let satisfied = validate_constraints(constraints, &val)
+                .map_err(|error| diagnostics.report(error))
+                .is_ok();
+            let ty_val = validate_property_type(ty, val, type_defs, diagnostics);
+            return ty_val.filter(|_| satisfied);
+        }
         (PropertyType::Unknown, val) => Ok(TypedValue::Unknown(val)),
         _ => Err(ValidationError::InvalidValueForType),
+    };
+    result.map_err(|error| diagnostics.report(error)).ok()
+}
+
+/// Validate the elements of an array against their types, reporting errors at the element indices.
+fn validate_elements<'a>(
+    vals: impl Iterator<Item = (&'a PropertyType, Value)>,
+    type_defs: &HashMap<SmolStr, description::PropertyTypeDef>,
+    diagnostics: &mut Diagnostics,
+) -> Option<Vec<TypedValue>> {
+    let mut valid = true;
+    let mut ty_vals = Vec::new();
+    for (index, (ty, val)) in vals.enumerate() {
+        match diagnostics.at(&index.to_smolstr(), |diagnostics| {
+            validate_property_type(ty, val, type_defs, diagnostics)
+        }) {
+            Some(ty_val) => ty_vals.push(ty_val),
+            None => valid = false,
+        }
+    }
+    valid.then_some(ty_vals)
+}
+
+/// Match `val` against the types of a union. If the union has a discriminator and `val` has
+/// the discriminator property, then `val` must match the type named by the discriminator value
+/// (whose errors are reported) and, for a `oneOf` union, no other type. Otherwise, a value
+/// matching none of the types is reported as a single error.
+fn validate_union(
+    types: &[PropertyType],
+    kind: UnionKind,
+    discriminator: Option<&Discriminator>,
+    val: Value,
+    type_defs: &HashMap<SmolStr, description::PropertyTypeDef>,
+    diagnostics: &mut Diagnostics,
+) -> Option<TypedValue> {
+    let tag_of = |index: usize| discriminator.and_then(|d| d.values().get(index)).cloned();
+
+    let discriminator_value = match (&val, discriminator) {
+        (Value::Map(props), Some(discriminator)) => {
+            match props.get(discriminator.property_name()) {
+                Some(Value::String(s)) => Some((discriminator, s.clone())),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    if let Some((discriminator, tag)) = discriminator_value {
+        let Some((index, ty)) = discriminator
+            .values()
+            .iter()
+            .position(|value| *value == tag)
+            .and_then(|index| types.get(index).map(|ty| (index, ty)))
+        else {
+            diagnostics.report_at(
+                discriminator.property_name(),
+                ValidationError::unknown_discriminator_value(&tag),
+            );
+            return None;
+        };
+        // The discriminator selects the type to validate, but a value of a `oneOf` union
+        // must still match no other type of the union
+        let ambiguous = kind == UnionKind::OneOf
+            && types.iter().enumerate().any(|(other, ty)| {
+                other != index
+                    && validate_property_type(
+                        ty,
+                        val.clone(),
+                        type_defs,
+                        &mut Diagnostics::default(),
+                    )
+                    .is_some()
+            });
+        let ty_val = validate_property_type(ty, val, type_defs, diagnostics)?;
+        if ambiguous {
+            diagnostics.report(ValidationError::AmbiguousValueForOneOfType);
+            return None;
+        }
+        return Some(TypedValue::Union {
+            index,
+            tag: Some(tag),
+            value: Box::new(ty_val),
+        });
+    }
+
+    let mut matches = types.iter().enumerate().filter_map(|(index, ty)| {
+        validate_property_type(ty, val.clone(), type_defs, &mut Diagnostics::default())
+            .map(|ty_val| (index, ty_val))
+    });
+    let Some((index, ty_val)) = matches.next() else {
+        diagnostics.report(ValidationError::InvalidValueForUnionType);
+        return None;
+    };
+    if kind == UnionKind::OneOf && matches.next().is_some() {
+        diagnostics.report(ValidationError::AmbiguousValueForOneOfType);
+        return None;
+    }
+    Some(TypedValue::Union {
+        index,
+        tag: tag_of(index),
+        value: Box::new(ty_val),
+    })
+}
+
+/// Check `val` against the constraints applicable to its kind of JSON value.
+/// A bound that cannot be compared with `val` is treated as violated.
+fn validate_constraints(
+    constraints: &PropertyConstraints,
+    val: &Value,
+) -> Result<(), ValidationError> {
+    match val {
+        Value::Number(num) => {
+            if let Some(min) = constraints.minimum() {
+                if !matches!(
+                    compare_numbers(num, min),
+                    Some(Ordering::Greater | Ordering::Equal)
+                ) {
+                    return Err(ValidationError::value_below_minimum(num, min));
+                }
+            }
+            if let Some(max) = constraints.maximum() {
+                if !matches!(
+                    compare_numbers(num, max),
+                    Some(Ordering::Less | Ordering::Equal)
+                ) {
+                    return Err(ValidationError::value_above_maximum(num, max));
+                }
+            }
+            if let Some(min) = constraints.exclusive_minimum() {
+                if compare_numbers(num, min) != Some(Ordering::Greater) {
+                    return Err(ValidationError::value_not_above_exclusive_minimum(num, min));
+                }
+            }
+            if let Some(max) = constraints.exclusive_maximum() {
+                if compare_numbers(num, max) != Some(Ordering::Less) {
+                    return Err(ValidationError::value_not_below_exclusive_maximum(num, max));
+                }
+            }
+            if let Some(divisor) = constraints.multiple_of() {
+                if !is_multiple_of(num, divisor) {
+                    return Err(ValidationError::value_not_multiple_of(num, divisor));
+                }
+            }
+        }
+        Value::String(s) => {
+            // JSON Schema measures string length in characters, not bytes
+            let length = s.chars().count();
+            if let Some(min) = constraints.min_length() {
+                if length < min {
+                    return Err(ValidationError::string_too_short(length, min));
+                }
+            }
+            if let Some(max) = constraints.max_length() {
+                if length > max {
+                    return Err(ValidationError::string_too_long(length, max));
+                }
+            }
+            if let Some(pattern) = constraints.pattern() {
+                if !pattern.is_match(s) {
+                    return Err(ValidationError::pattern_mismatch(s, pattern.as_str()));
+                }
+            }
+        }
+        Value::Array(vals) => {
+            if let Some(min) = constraints.min_items() {
+                if vals.len() < min {
+                    return Err(ValidationError::too_few_items(vals.len(), min));
+                }
+            }
+            if let Some(max) = constraints.max_items() {
+                if vals.len() > max {
+                    return Err(ValidationError::too_many_items(vals.len(), max));
+                }
+            }
+            if constraints.unique_items() {
+                for (i, v) in vals.iter().enumerate() {
+                    if vals.iter().skip(i + 1).any(|w| values_equal(v, w)) {
+                        return Err(ValidationError::DuplicateItems);
+                    }
+                }
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Map(_) => (),
+    }
+    Ok(())
+}
+
+/// Compare two numbers exactly if both are 64-bit integers, otherwise as 64-bit floats.
+fn compare_numbers(lhs: &Number, rhs: &Number) -> Option<Ordering> {
+    match (lhs.to_i64(), rhs.to_i64()) {
+        (Some(l), Some(r)) => Some(l.cmp(&r)),
+        _ => lhs.to_f64()?.partial_cmp(&rhs.to_f64()?),
+    }
+}
+
+fn is_multiple_of(num: &Number, divisor: &Number) -> bool {
+    match (num.to_i64(), divisor.to_i64()) {
+        (Some(n), Some(d)) => n.checked_rem(d) == Some(0),
+        _ => match (num.to_f64(), divisor.to_f64()) {
+            (Some(n), Some(d)) => {
+                // Tolerate the rounding error of floating point division (e.g., `0.3 / 0.1`)
+                let quotient = n / d;
+                quotient.is_finite()
+                    && (quotient - quotient.round()).abs() <= 1e-9 * quotient.abs().max(1.0)
+            }
+            _ => false,
+        },
+    }
+}
+
+/// JSON equality of values, where numbers are equal if they have the same numeric value.
+fn values_equal(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::Null, Value::Null) => true,
+        (Value::Bool(l), Value::Bool(r)) => l == r,
+        (Value::Number(l), Value::Number(r)) => compare_numbers(l, r) == Some(Ordering::Equal),
+        (Value::String(l), Value::String(r)) => l == r,
+        (Value::Array(l), Value::Array(r)) => {
+            l.len() == r.len() && l.iter().zip(r.iter()).all(|(l, r)| values_equal(l, r))
+        }
+        (Value::Map(l), Value::Map(r)) => {
+            l.len() == r.len()
+                && l.iter()
+                    .all(|(k, l)| r.get(k).is_some_and(|r| values_equal(l, r)))
+        }
+        _ => false,
     }
 }
 